@@ -143,6 +143,7 @@ pub struct Column<'a> {
     pub auto_increment: bool,
     pub primary_key: bool,
     pub references: Option<ForeignKey<'a>>,
+    pub on_update: Option<Cow<'a, str>>,
 }
 
 impl Display for Column<'_> {
@@ -170,6 +171,11 @@ impl Display for Column<'_> {
             f.write_str(default.as_ref())?;
         }
 
+        if let Some(on_update) = &self.on_update {
+            f.write_str(" ON UPDATE ")?;
+            f.write_str(on_update.as_ref())?;
+        }
+
         if let Some(references) = &self.references {
             f.write_str(" ")?;
             Display::fmt(references, f)?;
@@ -302,6 +308,17 @@ impl Display for DropTable<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct TruncateTable<'a> {
+    pub table_name: Cow<'a, str>,
+}
+
+impl Display for TruncateTable<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TRUNCATE TABLE `{}`", self.table_name)
+    }
+}
+
 #[derive(Debug)]
 pub struct DropIndex<'a> {
     pub index_name: Cow<'a, str>,
@@ -409,6 +426,7 @@ mod tests {
                     auto_increment: true,
                     primary_key: true,
                     references: None,
+                    on_update: None,
                 },
                 Column {
                     column_type: "BINARY(16)".into(),
@@ -418,6 +436,7 @@ mod tests {
                     auto_increment: false,
                     primary_key: false,
                     references: None,
+                    on_update: None,
                 },
             ],
             indexes: vec![],
@@ -141,6 +141,38 @@ impl Display for DropTable<'_> {
     }
 }
 
+/// Render a `TRUNCATE TABLE` statement.
+///
+/// ```
+/// # use sql_ddl::postgres::TruncateTable;
+///
+/// let truncate_table = TruncateTable { table_name: "Cat".into(), cascade: false };
+/// assert_eq!(truncate_table.to_string(), r#"TRUNCATE TABLE "Cat""#);
+///
+/// let truncate_table = TruncateTable { table_name: "Cat".into(), cascade: true };
+/// assert_eq!(truncate_table.to_string(), r#"TRUNCATE TABLE "Cat" CASCADE"#);
+/// ```
+#[derive(Debug)]
+pub struct TruncateTable<'a> {
+    /// The name of the table to be truncated.
+    pub table_name: PostgresIdentifier<'a>,
+    /// Whether to also truncate tables with foreign keys referencing this table.
+    pub cascade: bool,
+}
+
+impl Display for TruncateTable<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TRUNCATE TABLE ")?;
+        Display::fmt(&self.table_name, f)?;
+
+        if self.cascade {
+            f.write_str(" CASCADE")?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Render a `DROP TYPE` statement.
 ///
 /// ```
@@ -190,6 +222,10 @@ pub struct ForeignKey<'a> {
     pub referenced_columns: Vec<Cow<'a, str>>,
     pub on_delete: Option<ForeignKeyAction>,
     pub on_update: Option<ForeignKeyAction>,
+    /// Renders the constraint as `DEFERRABLE INITIALLY DEFERRED`, so it is only checked at
+    /// transaction commit instead of immediately. Must come last in the rendered clause: Postgres
+    /// requires `DEFERRABLE` to follow any `ON DELETE`/`ON UPDATE` clause.
+    pub deferrable: bool,
 }
 
 impl Display for ForeignKey<'_> {
@@ -218,6 +254,10 @@ impl Display for ForeignKey<'_> {
             on_update.fmt(f)?;
         }
 
+        if self.deferrable {
+            f.write_str(" DEFERRABLE INITIALLY DEFERRED")?;
+        }
+
         Ok(())
     }
 }
@@ -347,6 +387,11 @@ pub struct CreateIndex<'a> {
     pub table_reference: &'a dyn Display,
     pub columns: Vec<IndexColumn<'a>>,
     pub using: Option<IndexAlgorithm>,
+    /// Render with `CONCURRENTLY`, so the index is built without holding a lock that blocks
+    /// writes to the table. Defaults to `false`.
+    pub concurrently: bool,
+    /// The `WHERE` clause of a partial index, verbatim as it should be rendered.
+    pub predicate: Option<&'a str>,
 }
 
 impl<'a> Display for CreateIndex<'a> {
@@ -362,8 +407,9 @@ impl<'a> Display for CreateIndex<'a> {
 
         write!(
             f,
-            "CREATE {uniqueness}INDEX {index_name} ON {table_reference}{using}(",
+            "CREATE {uniqueness}INDEX {concurrently}{index_name} ON {table_reference}{using}(",
             uniqueness = if self.is_unique { "UNIQUE " } else { "" },
+            concurrently = if self.concurrently { "CONCURRENTLY " } else { "" },
             index_name = self.index_name,
             table_reference = self.table_reference,
             using = using,
@@ -388,7 +434,13 @@ impl<'a> Display for CreateIndex<'a> {
             })
             .join(", ", f)?;
 
-        f.write_str(")")
+        f.write_str(")")?;
+
+        if let Some(predicate) = self.predicate {
+            write!(f, " WHERE {predicate}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -432,6 +484,8 @@ mod tests {
             table_reference: &PostgresIdentifier::Simple(Cow::Borrowed("Cat")),
             columns,
             using: None,
+            concurrently: false,
+            predicate: None,
         };
 
         assert_eq!(
@@ -450,6 +504,8 @@ mod tests {
             table_reference: &PostgresIdentifier::Simple(Cow::Borrowed("Cat")),
             columns,
             using: Some(IndexAlgorithm::Hash),
+            concurrently: false,
+            predicate: None,
         };
 
         assert_eq!(
@@ -479,6 +535,8 @@ mod tests {
             table_reference: &PostgresIdentifier::Simple("Cat".into()),
             columns,
             using: None,
+            concurrently: false,
+            predicate: None,
         };
 
         assert_eq!(
@@ -487,6 +545,46 @@ mod tests {
         )
     }
 
+    #[test]
+    fn create_index_concurrently() {
+        let columns = vec![IndexColumn::new("name")];
+
+        let create_index = CreateIndex {
+            is_unique: false,
+            index_name: "meow_idx".into(),
+            table_reference: &PostgresIdentifier::Simple(Cow::Borrowed("Cat")),
+            columns,
+            using: None,
+            concurrently: true,
+            predicate: None,
+        };
+
+        assert_eq!(
+            create_index.to_string(),
+            "CREATE INDEX CONCURRENTLY \"meow_idx\" ON \"Cat\"(\"name\")"
+        )
+    }
+
+    #[test]
+    fn create_partial_unique_index() {
+        let columns = vec![IndexColumn::new("a")];
+
+        let create_index = CreateIndex {
+            is_unique: true,
+            index_name: "meow_idx".into(),
+            table_reference: &PostgresIdentifier::Simple(Cow::Borrowed("Cat")),
+            columns,
+            using: None,
+            concurrently: false,
+            predicate: Some("\"deletedAt\" IS NULL"),
+        };
+
+        assert_eq!(
+            create_index.to_string(),
+            "CREATE UNIQUE INDEX \"meow_idx\" ON \"Cat\"(\"a\") WHERE \"deletedAt\" IS NULL"
+        )
+    }
+
     #[test]
     fn full_alter_table_add_foreign_key() {
         let alter_table = AlterTable {
@@ -14,6 +14,10 @@ pub struct CreateTable<'a> {
     pub columns: Vec<Column<'a>>,
     pub primary_key: Option<Vec<Cow<'a, str>>>,
     pub foreign_keys: Vec<ForeignKey<'a>>,
+    /// Whether to append `WITHOUT ROWID` to the statement. A `WITHOUT ROWID` table has no hidden
+    /// rowid column: its primary key is the table's only identity, and is used directly as the
+    /// clustering key. See <https://www.sqlite.org/withoutrowid.html>.
+    pub without_rowid: bool,
 }
 
 impl Display for CreateTable<'_> {
@@ -34,7 +38,13 @@ impl Display for CreateTable<'_> {
             write!(f, ",\n{SQL_INDENTATION}{foreign_key}")?;
         }
 
-        write!(f, "\n)")
+        f.write_str("\n)")?;
+
+        if self.without_rowid {
+            f.write_str(" WITHOUT ROWID")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -173,6 +183,7 @@ mod tests {
             ],
             primary_key: None,
             foreign_keys: Vec::new(),
+            without_rowid: false,
         };
 
         let expected = indoc::indoc!(
@@ -206,6 +217,7 @@ mod tests {
             ],
             primary_key: Some(vec!["id".into(), "boxId".into()]),
             foreign_keys: Vec::new(),
+            without_rowid: false,
         };
 
         let expected = indoc!(
@@ -253,6 +265,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            without_rowid: false,
         };
 
         let expected = indoc!(
@@ -270,4 +283,31 @@ mod tests {
 
         assert_eq!(create_table.to_string(), expected.trim_matches('\n'))
     }
+
+    #[test]
+    fn create_table_without_rowid() {
+        let create_table = CreateTable {
+            table_name: &SqliteIdentifier("Cat"),
+            columns: vec![Column {
+                name: "id".into(),
+                r#type: "uuid".into(),
+                ..Default::default()
+            }],
+            primary_key: Some(vec!["id".into()]),
+            foreign_keys: Vec::new(),
+            without_rowid: true,
+        };
+
+        let expected = indoc!(
+            r#"
+            CREATE TABLE "Cat" (
+                "id" uuid,
+
+                PRIMARY KEY ("id")
+            ) WITHOUT ROWID
+            "#
+        );
+
+        assert_eq!(create_table.to_string(), expected.trim_matches('\n'))
+    }
 }
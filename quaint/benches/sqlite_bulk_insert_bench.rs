@@ -0,0 +1,36 @@
+use codspeed_criterion_compat::{black_box, criterion_group, criterion_main, Criterion};
+use quaint::{
+    ast::Value,
+    connector::{Queryable, Sqlite},
+};
+
+/// Bulk-inserts `row_count` two-column rows into a fresh in-memory database, using a fresh
+/// runtime and connection per iteration so the measurement only covers `bulk_insert` itself.
+fn bulk_insert(row_count: i32) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    runtime.block_on(async {
+        let conn = Sqlite::new_in_memory().unwrap();
+        conn.raw_cmd("CREATE TABLE bench (a INTEGER, b INTEGER);")
+            .await
+            .unwrap();
+
+        let rows: Vec<Vec<Value>> = (0..row_count).map(|i| vec![Value::int32(i), Value::int32(i * 2)]).collect();
+
+        conn.bulk_insert("bench", &["a", "b"], rows).await.unwrap();
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // `rows_per_statement` for two columns is 999 / 2 = 499, so these sizes exercise: a single
+    // partial chunk (100), two full chunks followed by a 2-row final one (1,000), and twenty
+    // full chunks followed by a 20-row final one (10,000).
+    for row_count in [100, 1_000, 10_000] {
+        c.bench_function(&format!("sqlite bulk_insert ({row_count} rows)"), |b| {
+            b.iter(|| black_box(bulk_insert(row_count)))
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -258,6 +258,21 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// Comparing against a `NULL` value renders as `IS NULL` instead of `= ?`, since `= NULL`
+    /// never matches anything, not even another `NULL`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("foo".equals(Value::null_text()));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` IS NULL", sql);
+    /// assert!(params.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
     fn equals<T>(self, comparison: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
@@ -281,6 +296,21 @@ pub trait Comparable<'a> {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// Comparing against a `NULL` value renders as `IS NOT NULL` instead of `<> ?`, for the same
+    /// reason `equals` renders `IS NULL`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("foo".not_equals(Value::null_text()));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` IS NOT NULL", sql);
+    /// assert!(params.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
     fn not_equals<T>(self, comparison: T) -> Compare<'a>
     where
         T: Into<Expression<'a>>;
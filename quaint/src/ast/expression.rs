@@ -85,6 +85,15 @@ impl<'a> Expression<'a> {
         }
     }
 
+    /// Is this expression a parameterized or literal `NULL` value, of any type?
+    pub(crate) fn is_null_value(&self) -> bool {
+        match &self.kind {
+            ExpressionKind::Parameterized(value) => value.is_null(),
+            ExpressionKind::Value(expr) => expr.is_null_value(),
+            _ => false,
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn is_fun_retuning_json(&self) -> bool {
         match &self.kind {
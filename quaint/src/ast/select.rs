@@ -603,6 +603,29 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Combines this select with another one using `UNION` (or `UNION ALL` if `all` is `true`).
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let s1 = Select::from_table("users").column("id");
+    /// let s2 = Select::from_table("old_users").column("id");
+    /// let (sql, _) = Sqlite::build(s1.union(s2, true))?;
+    ///
+    /// assert_eq!("SELECT `id` FROM `users` UNION ALL SELECT `id` FROM `old_users`", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union(self, other: Select<'a>, all: bool) -> Union<'a> {
+        let union = Union::new(self);
+
+        if all {
+            union.all(other)
+        } else {
+            union.distinct(other)
+        }
+    }
+
     /// Adds a common table expression to the select.
     ///
     /// ```rust
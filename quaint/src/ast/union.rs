@@ -1,8 +1,9 @@
-use crate::ast::{Expression, Query, Select};
+use crate::ast::{Expression, Ordering, Query, Select, Value};
 use std::{collections::BTreeSet, fmt};
 
 use super::CommonTableExpression;
 use super::IntoCommonTableExpression;
+use super::IntoOrderDefinition;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub(crate) enum UnionType {
@@ -25,6 +26,9 @@ pub struct Union<'a> {
     pub(crate) selects: Vec<Select<'a>>,
     pub(crate) types: Vec<UnionType>,
     pub(crate) ctes: Vec<CommonTableExpression<'a>>,
+    pub(crate) ordering: Ordering<'a>,
+    pub(crate) limit: Option<Value<'a>>,
+    pub(crate) offset: Option<Value<'a>>,
 }
 
 impl<'a> From<Union<'a>> for Query<'a> {
@@ -45,6 +49,9 @@ impl<'a> Union<'a> {
             selects: vec![q],
             types: Vec::new(),
             ctes: Vec::new(),
+            ordering: Ordering::default(),
+            limit: None,
+            offset: None,
         }
     }
 
@@ -98,6 +105,44 @@ impl<'a> Union<'a> {
         self
     }
 
+    /// Adds an ordering to the combined result of the `UNION`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let s1 = Select::from_table("users").column("id");
+    /// let s2 = Select::from_table("old_users").column("id");
+    /// let query = Union::new(s1).all(s2).order_by("id".descend());
+    ///
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT `id` FROM `users` UNION ALL SELECT `id` FROM `old_users` ORDER BY `id` DESC",
+    ///     sql,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+
+    /// Sets the `LIMIT` value for the combined result of the `UNION`.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(Value::from(limit));
+        self
+    }
+
+    /// Sets the `OFFSET` value for the combined result of the `UNION`.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(Value::from(offset));
+        self
+    }
+
     /// A list of item names in the queries, skipping the anonymous values or
     /// columns.
     pub(crate) fn named_selection(&self) -> Vec<String> {
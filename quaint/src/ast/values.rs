@@ -1,4 +1,5 @@
 use crate::ast::*;
+use crate::connector::SqlFamily;
 use crate::error::{Error, ErrorKind};
 
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
@@ -70,6 +71,14 @@ impl<'a> Value<'a> {
         self
     }
 
+    /// Renders the value the way it would appear written literally inline in a `family` query,
+    /// for logging/debugging generated SQL with values inlined (e.g. before running `EXPLAIN`).
+    /// **Never** use this to build a query to actually execute: it does not protect against SQL
+    /// injection the way parameter binding does.
+    pub fn to_sql_literal(&self, family: SqlFamily) -> String {
+        self.typed.to_sql_literal(family)
+    }
+
     /// Creates a new 32-bit signed integer.
     pub fn int32<I>(value: I) -> Self
     where
@@ -86,6 +95,19 @@ impl<'a> Value<'a> {
         ValueType::int64(value).into_value()
     }
 
+    /// Creates a new integer value from a `u64`. Integer storage in this crate (and in SQLite,
+    /// which has no unsigned integer type at all) is 64-bit **signed**, so a `u64` above
+    /// `i64::MAX` can't be bound as an ordinary integer without silently wrapping. A value in
+    /// range binds as `Int64`; a value above `i64::MAX` is bound as `Text` instead, which
+    /// preserves it exactly at the cost of the column no longer sorting/comparing numerically
+    /// against other integers.
+    pub fn unsigned_int64(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Self::int64(value),
+            Err(_) => Self::text(value.to_string()),
+        }
+    }
+
     /// Creates a new decimal value.
     pub fn numeric(value: BigDecimal) -> Self {
         ValueType::numeric(value).into_value()
@@ -285,6 +307,12 @@ impl<'a> Value<'a> {
         self.typed.as_integer()
     }
 
+    /// Returns `Ok(None)` if the value is NULL, `Ok(Some(i64))` if it is (or parses as) a
+    /// signed integer, and `Err` if it is some other, non-NULL value.
+    pub fn as_i64_opt(&self) -> crate::Result<Option<i64>> {
+        self.typed.as_i64_opt()
+    }
+
     /// Returns a `f64` if the value is a double, otherwise `None`.
     pub fn as_f64(&self) -> Option<f64> {
         self.typed.as_f64()
@@ -673,11 +701,92 @@ impl<'a> From<ValueType<'a>> for serde_json::Value {
     }
 }
 
+/// Quote a string for inlining as a SQL string literal, doubling embedded single quotes the way
+/// every one of our supported flavours expects.
+fn quoted_sql_string_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn float_sql_literal(f: f64) -> String {
+    if f.is_nan() {
+        "'NaN'".to_owned()
+    } else if f == f64::INFINITY {
+        "'Infinity'".to_owned()
+    } else if f == f64::NEG_INFINITY {
+        "'-Infinity'".to_owned()
+    } else {
+        f.to_string()
+    }
+}
+
+fn bytes_sql_literal(bytes: &[u8], family: SqlFamily) -> String {
+    let hex = hex::encode(bytes);
+
+    match family {
+        #[cfg(feature = "postgresql")]
+        SqlFamily::Postgres => format!("'\\x{hex}'"),
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => format!("0x{hex}"),
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => format!("x'{hex}'"),
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => format!("x'{hex}'"),
+    }
+}
+
+fn boolean_sql_literal(b: bool, family: SqlFamily) -> String {
+    match family {
+        #[cfg(feature = "postgresql")]
+        SqlFamily::Postgres => if b { "TRUE" } else { "FALSE" }.to_owned(),
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => if b { "1" } else { "0" }.to_owned(),
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => if b { "1" } else { "0" }.to_owned(),
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => if b { "1" } else { "0" }.to_owned(),
+    }
+}
+
+fn sql_array_literal(items: impl Iterator<Item = String>) -> String {
+    format!("'{{{}}}'", items.collect::<Vec<_>>().join(","))
+}
+
 impl<'a> ValueType<'a> {
     pub fn into_value(self) -> Value<'a> {
         self.into()
     }
 
+    /// See [`Value::to_sql_literal`].
+    pub fn to_sql_literal(&self, family: SqlFamily) -> String {
+        match self {
+            ValueType::Int32(v) => v.map(|v| v.to_string()),
+            ValueType::Int64(v) => v.map(|v| v.to_string()),
+            ValueType::Float(v) => v.map(|v| float_sql_literal(*v as f64)),
+            ValueType::Double(v) => v.map(|v| float_sql_literal(*v)),
+            ValueType::Numeric(v) => v.as_ref().map(|v| v.to_string()),
+            ValueType::Text(v) => v.as_ref().map(|v| quoted_sql_string_literal(v)),
+            ValueType::Enum(v, _) => v.as_ref().map(|v| quoted_sql_string_literal(v)),
+            ValueType::EnumArray(vals, _) => vals
+                .as_ref()
+                .map(|vals| sql_array_literal(vals.iter().map(|v| quoted_sql_string_literal(v)))),
+            ValueType::Bytes(v) => v.as_ref().map(|v| bytes_sql_literal(v, family)),
+            ValueType::Boolean(v) => v.map(|v| boolean_sql_literal(v, family)),
+            ValueType::Char(v) => v.map(|v| quoted_sql_string_literal(&v.to_string())),
+            ValueType::Array(vals) => vals
+                .as_ref()
+                .map(|vals| sql_array_literal(vals.iter().map(|v| v.to_sql_literal(family)))),
+            ValueType::Json(v) => v
+                .as_ref()
+                .map(|v| quoted_sql_string_literal(&v.to_string())),
+            ValueType::Xml(v) => v.as_ref().map(|v| quoted_sql_string_literal(v)),
+            ValueType::Uuid(v) => v.map(|v| quoted_sql_string_literal(&v.hyphenated().to_string())),
+            ValueType::DateTime(v) => v.map(|v| quoted_sql_string_literal(&v.to_rfc3339())),
+            ValueType::Date(v) => v.map(|v| quoted_sql_string_literal(&v.to_string())),
+            ValueType::Time(v) => v.map(|v| quoted_sql_string_literal(&v.to_string())),
+        }
+        .unwrap_or_else(|| "NULL".to_owned())
+    }
+
     /// Creates a new 32-bit signed integer.
     pub(crate) fn int32<I>(value: I) -> Self
     where
@@ -946,6 +1055,25 @@ impl<'a> ValueType<'a> {
         }
     }
 
+    /// Returns `Ok(None)` if the value is NULL, `Ok(Some(i64))` if it is (or parses as) a
+    /// signed integer, and `Err` if it is some other, non-NULL value. Unlike [`Self::as_integer`],
+    /// which returns `None` for both NULL and a type mismatch, this lets a caller distinguish
+    /// "no value" from "not an integer".
+    pub(crate) fn as_i64_opt(&self) -> crate::Result<Option<i64>> {
+        match self {
+            Self::Int32(i) => Ok(i.map(i64::from)),
+            Self::Int64(i) => Ok(*i),
+            Self::Text(Some(s)) => s.parse::<i64>().map(Some).map_err(|_| {
+                Error::builder(ErrorKind::conversion(format!("Expected an integer, got a string: {s:?}"))).build()
+            }),
+            _ if self.is_null() => Ok(None),
+            other => Err(Error::builder(ErrorKind::conversion(format!(
+                "Expected an integer, got: {other:?}"
+            )))
+            .build()),
+        }
+    }
+
     /// Returns a `f64` if the value is a double, otherwise `None`.
     pub(crate) fn as_f64(&self) -> Option<f64> {
         match self {
@@ -1419,6 +1547,19 @@ mod tests {
         assert!(rslt.is_none());
     }
 
+    #[test]
+    fn unsigned_int64_in_range_binds_as_int64() {
+        let pv = Value::unsigned_int64(42_u64);
+        assert_eq!(pv.typed, ValueType::Int64(Some(42)));
+    }
+
+    #[test]
+    fn unsigned_int64_above_i64_max_binds_as_text() {
+        let value = u64::MAX;
+        let pv = Value::unsigned_int64(value);
+        assert_eq!(pv.typed, ValueType::Text(Some(value.to_string().into())));
+    }
+
     #[test]
     fn display_format_for_datetime() {
         let dt: DateTime<Utc> = DateTime::from_str("2019-07-27T05:30:30Z").expect("failed while parsing date");
@@ -1450,4 +1591,87 @@ mod tests {
 
         assert_eq!(format!("{pv}"), "\"67e55044-10b1-426f-9247-bb680e5fe0c8\"");
     }
+
+    #[test]
+    fn as_i64_opt_on_null_is_ok_none() {
+        let pv = Value::null_int64();
+        assert_eq!(pv.as_i64_opt().unwrap(), None);
+    }
+
+    #[test]
+    fn as_i64_opt_on_an_integer_returns_it() {
+        assert_eq!(Value::from(1_i64).as_i64_opt().unwrap(), Some(1));
+        assert_eq!(Value::from(1_i32).as_i64_opt().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn as_i64_opt_on_a_parseable_string_returns_the_parsed_integer() {
+        assert_eq!(Value::from("1").as_i64_opt().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn as_i64_opt_on_the_wrong_type_is_an_error() {
+        assert!(Value::from(true).as_i64_opt().is_err());
+        assert!(Value::from("not an integer").as_i64_opt().is_err());
+    }
+
+    #[test]
+    fn to_sql_literal_renders_null_for_every_variant() {
+        assert_eq!(Value::null_int64().to_sql_literal(SqlFamily::Sqlite), "NULL");
+        assert_eq!(Value::null_text().to_sql_literal(SqlFamily::Sqlite), "NULL");
+    }
+
+    #[test]
+    fn to_sql_literal_renders_an_integer() {
+        assert_eq!(Value::int64(42).to_sql_literal(SqlFamily::Sqlite), "42");
+    }
+
+    #[test]
+    fn to_sql_literal_renders_a_double() {
+        assert_eq!(Value::double(1.5).to_sql_literal(SqlFamily::Sqlite), "1.5");
+    }
+
+    #[test]
+    fn to_sql_literal_quotes_a_string() {
+        assert_eq!(Value::text("hello").to_sql_literal(SqlFamily::Sqlite), "'hello'");
+    }
+
+    #[test]
+    fn to_sql_literal_escapes_a_quote_inside_a_string() {
+        assert_eq!(
+            Value::text("it's here").to_sql_literal(SqlFamily::Sqlite),
+            "'it''s here'"
+        );
+    }
+
+    #[test]
+    fn to_sql_literal_renders_bytes_as_a_hex_blob() {
+        assert_eq!(Value::bytes(vec![0xDE, 0xAD]).to_sql_literal(SqlFamily::Sqlite), "x'dead'");
+    }
+
+    #[test]
+    fn to_sql_literal_renders_a_boolean() {
+        assert_eq!(Value::boolean(true).to_sql_literal(SqlFamily::Sqlite), "1");
+        assert_eq!(Value::boolean(false).to_sql_literal(SqlFamily::Sqlite), "0");
+    }
+
+    #[test]
+    fn to_sql_literal_quotes_a_character() {
+        assert_eq!(Value::character('a').to_sql_literal(SqlFamily::Sqlite), "'a'");
+    }
+
+    #[test]
+    fn to_sql_literal_renders_a_uuid() {
+        let id = Uuid::from_str("67e5504410b1426f9247bb680e5fe0c8").unwrap();
+        assert_eq!(
+            Value::uuid(id).to_sql_literal(SqlFamily::Sqlite),
+            "'67e55044-10b1-426f-9247-bb680e5fe0c8'"
+        );
+    }
+
+    #[test]
+    fn to_sql_literal_renders_a_date() {
+        let date = NaiveDate::from_ymd_opt(2022, 8, 11).unwrap();
+        assert_eq!(Value::date(date).to_sql_literal(SqlFamily::Sqlite), "'2022-08-11'");
+    }
 }
@@ -189,6 +189,7 @@ impl TypeIdentifier for my::Column {
         self.column_type() == ColumnType::MYSQL_TYPE_BIT && self.column_length() == 1
     }
 
+    #[cfg(any(feature = "mysql", feature = "sqlite"))]
     fn is_json(&self) -> bool {
         self.column_type() == ColumnType::MYSQL_TYPE_JSON
     }
@@ -51,6 +51,22 @@ pub trait Queryable: Send + Sync {
     /// prepared statements.
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()>;
 
+    /// Execute a batch of independent raw SQL statements, given as `(sql, params)` pairs, one
+    /// after another, collecting each statement's own [`ResultSet`] into the returned `Vec` in
+    /// the same order as `queries`. Unlike [`Self::execute_raw`], this preserves any rows a
+    /// statement returns, so a batch mixing `RETURNING` and non-`RETURNING` statements gets a
+    /// populated result set for the former and an empty one for the latter, rather than
+    /// collapsing everything down to an affected-row count.
+    async fn query_batch(&self, queries: &[(&str, &[Value<'_>])]) -> crate::Result<Vec<ResultSet>> {
+        let mut results = Vec::with_capacity(queries.len());
+
+        for (sql, params) in queries {
+            results.push(self.query_raw(sql, params).await?);
+        }
+
+        Ok(results)
+    }
+
     /// Return the version of the underlying database, queried directly from the
     /// source. This corresponds to the `version()` function on PostgreSQL for
     /// example. The version string is returned directly without any form of
@@ -64,6 +64,28 @@ impl ResultSet {
         })
     }
 
+    /// An iterator of borrowed rows, avoiding the clone of each row's values that collecting
+    /// `into_iter()`'s `ResultRow`s would require.
+    ///
+    /// ```
+    /// # use quaint::connector::*;
+    /// let names = vec!["id".to_string(), "name".to_string()];
+    /// let rows = vec![vec!["1234".into(), "Musti".into()], vec!["5678".into(), "Naukio".into()]];
+    /// let result_set = ResultSet::new(names, rows);
+    ///
+    /// let names: Vec<_> = result_set.iter().map(|row| row["name"].clone()).collect();
+    /// assert_eq!(names, vec!["Musti".into(), "Naukio".into()]);
+    ///
+    /// let row = result_set.iter().next().unwrap();
+    /// assert_eq!(row.get("nope"), None);
+    /// ```
+    pub fn iter(&self) -> ResultSetRefIterator<'_> {
+        ResultSetRefIterator {
+            columns: Arc::clone(&self.columns),
+            internal_iterator: self.rows.iter(),
+        }
+    }
+
     /// Takes the first row if existing, otherwise returns error.
     pub fn into_single(self) -> crate::Result<ResultRow> {
         match self.into_iter().next() {
@@ -106,6 +128,32 @@ impl Iterator for ResultSetIterator {
     }
 }
 
+/// Thin iterator for borrowed `ResultSet` rows.
+pub struct ResultSetRefIterator<'a> {
+    pub(crate) columns: Arc<Vec<String>>,
+    pub(crate) internal_iterator: std::slice::Iter<'a, Vec<Value<'static>>>,
+}
+
+impl<'a> Iterator for ResultSetRefIterator<'a> {
+    type Item = ResultRowRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.internal_iterator.next().map(|row| ResultRowRef {
+            columns: Arc::clone(&self.columns),
+            values: row,
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a ResultSet {
+    type Item = ResultRowRef<'a>;
+    type IntoIter = ResultSetRefIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl From<ResultSet> for serde_json::Value {
     fn from(result_set: ResultSet) -> Self {
         let columns: Vec<String> = result_set.columns().iter().map(ToString::to_string).collect();
@@ -84,7 +84,12 @@ impl From<SqliteError> for Error {
                 builder.build()
             }
 
-            SqliteError { extended_code, message } if error.primary_code() == super::ffi::SQLITE_BUSY => {
+            SqliteError { extended_code, message }
+                if matches!(
+                    error.primary_code(),
+                    super::ffi::SQLITE_BUSY | super::ffi::SQLITE_INTERRUPT
+                ) =>
+            {
                 let mut builder = Error::builder(ErrorKind::SocketTimeout);
                 builder.set_original_code(format!("{extended_code}"));
 
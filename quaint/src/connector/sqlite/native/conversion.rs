@@ -14,7 +14,28 @@ use rusqlite::{
     Column, Error as RusqlError, Row as SqliteRow, Rows as SqliteRows,
 };
 
-use chrono::TimeZone;
+use chrono::{NaiveDateTime, TimeZone};
+
+/// The Julian day number of the Unix epoch (1970-01-01T00:00:00Z). This is the same constant
+/// SQLite's own `julianday()` function is built on.
+/// <https://www.sqlite.org/lang_datefunc.html>
+const JULIAN_DAY_UNIX_EPOCH: f64 = 2_440_587.5;
+
+/// Converts a naive UTC timestamp into a Julian day number, preserving sub-day precision (down to
+/// the millisecond) as a fractional part. Julian days have no concept of time zone, so the input
+/// is always treated as UTC.
+fn datetime_to_julian_day(naive_utc: NaiveDateTime) -> f64 {
+    JULIAN_DAY_UNIX_EPOCH + naive_utc.timestamp_millis() as f64 / 86_400_000.0
+}
+
+/// Converts a Julian day number back into a naive UTC timestamp, the inverse of
+/// [`datetime_to_julian_day`].
+fn julian_day_to_datetime(julian_day: f64) -> NaiveDateTime {
+    let millis = (((julian_day - JULIAN_DAY_UNIX_EPOCH) * 86_400_000.0).round()) as i64;
+
+    chrono::NaiveDateTime::from_timestamp_opt(millis.div_euclid(1000), (millis.rem_euclid(1000) * 1_000_000) as u32)
+        .unwrap()
+}
 
 impl TypeIdentifier for Column<'_> {
     fn is_real(&self) -> bool {
@@ -119,9 +140,8 @@ impl TypeIdentifier for Column<'_> {
         matches!(self.decl_type(), Some("BOOLEAN") | Some("boolean"))
     }
 
-    #[cfg(feature = "mysql")]
     fn is_json(&self) -> bool {
-        false
+        matches!(self.decl_type(), Some("JSON") | Some("json"))
     }
 
     #[cfg(feature = "mysql")]
@@ -133,6 +153,17 @@ impl TypeIdentifier for Column<'_> {
     }
 }
 
+/// Whether `name` is one of SQLite's built-in aliases for the implicit `rowid` column
+/// (`rowid`, `oid`, `_rowid_`), matched case-insensitively as SQLite itself does.
+///
+/// A table can also declare an actual column named e.g. `rowid` with `INTEGER PRIMARY KEY`, in
+/// which case its `decl_type()` is `"INTEGER"` and it would otherwise be read back as an `i32`,
+/// overflowing for large rowid values. Selecting it explicitly should always be read back as an
+/// `i64`, so we special-case the name rather than relying on the declared type.
+fn is_rowid_alias(name: &str) -> bool {
+    name.eq_ignore_ascii_case("rowid") || name.eq_ignore_ascii_case("oid") || name.eq_ignore_ascii_case("_rowid_")
+}
+
 impl<'a> GetRow for SqliteRow<'a> {
     fn get_result_row(&self) -> crate::Result<Vec<Value<'static>>> {
         let statement = self.as_ref();
@@ -144,6 +175,7 @@ impl<'a> GetRow for SqliteRow<'a> {
                     // NOTE: A value without decl_type would be Int32(None)
                     c if c.is_int32() | c.is_null() => Value::null_int32(),
                     c if c.is_int64() => Value::null_int64(),
+                    c if c.is_json() => Value::null_json(),
                     c if c.is_text() => Value::null_text(),
                     c if c.is_bytes() => Value::null_bytes(),
                     c if c.is_float() => Value::null_float(),
@@ -165,6 +197,7 @@ impl<'a> GetRow for SqliteRow<'a> {
                 },
                 ValueRef::Integer(i) => {
                     match column {
+                        c if is_rowid_alias(c.name()) => Value::int64(i),
                         c if c.is_bool() => {
                             if i == 0 {
                                 Value::boolean(false)
@@ -200,6 +233,15 @@ impl<'a> GetRow for SqliteRow<'a> {
 
                     Value::numeric(BigDecimal::from_str(&f.to_string()).unwrap())
                 }
+                // A DATE/DATETIME column storing a REAL is assumed to hold a Julian day number,
+                // the convention used by schemas that don't store dates as ISO text or
+                // millisecond integers. This is detected from the storage class alone, regardless
+                // of whether this connection has Julian day binding enabled: a REAL in a
+                // date-affinity column can't mean anything else.
+                ValueRef::Real(f) if column.is_date() => Value::date(julian_day_to_datetime(f).date()),
+                ValueRef::Real(f) if column.is_datetime() => {
+                    Value::datetime(chrono::DateTime::<chrono::Utc>::from_utc(julian_day_to_datetime(f), chrono::Utc))
+                }
                 ValueRef::Real(f) => Value::double(f),
                 ValueRef::Text(bytes) if column.is_datetime() => {
                     let parse_res = std::str::from_utf8(bytes).map_err(|_| {
@@ -226,6 +268,53 @@ impl<'a> GetRow for SqliteRow<'a> {
                             })
                     })?
                 }
+                // The column's declared affinity may not match the storage class SQLite actually
+                // used for this value (e.g. a NUMERIC column holding an integer-looking literal
+                // that ended up with TEXT storage class). Parse it back into the shape writes to
+                // that column already produce, falling back to plain text if it doesn't parse —
+                // this also covers expression columns, which have no declared type at all.
+                ValueRef::Text(bytes) if column.is_real() || column.is_double() => {
+                    let s = String::from_utf8(bytes.to_vec())?;
+
+                    match s.parse::<f64>() {
+                        Ok(f) if column.is_real() => {
+                            use bigdecimal::BigDecimal;
+                            use std::str::FromStr;
+
+                            // `f64::from_str` accepts "inf"/"nan" spellings that `BigDecimal::from_str`
+                            // doesn't, so a non-finite value round-tripped through `f.to_string()` would
+                            // fail to parse here even though the `f64` parse above succeeded. Fall back
+                            // to text like every other unparseable case in this match, rather than
+                            // panicking on a value quaint itself never would have written.
+                            BigDecimal::from_str(&f.to_string())
+                                .map(Value::numeric)
+                                .unwrap_or_else(|_| Value::text(s))
+                        }
+                        Ok(f) => Value::double(f),
+                        Err(_) => Value::text(s),
+                    }
+                }
+                ValueRef::Text(bytes) if column.is_int32() || column.is_int64() => {
+                    let s = String::from_utf8(bytes.to_vec())?;
+
+                    match s.parse::<i64>() {
+                        Ok(i) if column.is_int64() => Value::int64(i),
+                        Ok(i) => i32::try_from(i).map(Value::int32).unwrap_or_else(|_| Value::int64(i)),
+                        Err(_) => Value::text(s),
+                    }
+                }
+                // A column declared JSON may still hold invalid JSON text, e.g. if it was written
+                // by a connection that bypassed this binding (a raw `INSERT` executed outside of
+                // quaint). Fall back to plain text in that case, same as the real/double/integer
+                // branches above do for their own declared-but-mismatched storage classes.
+                ValueRef::Text(bytes) if column.is_json() => {
+                    let s = String::from_utf8(bytes.to_vec())?;
+
+                    match serde_json::from_str(&s) {
+                        Ok(json) => Value::json(json),
+                        Err(_) => Value::text(s),
+                    }
+                }
                 ValueRef::Text(bytes) => Value::text(String::from_utf8(bytes.to_vec())?),
                 ValueRef::Blob(bytes) => Value::bytes(bytes.to_owned()),
             };
@@ -251,6 +340,11 @@ impl<'a> ToSql for Value<'a> {
         let value = match &self.typed {
             ValueType::Int32(integer) => integer.map(ToSqlOutput::from),
             ValueType::Int64(integer) => integer.map(ToSqlOutput::from),
+            // SQLite stores every REAL as an 8-byte double, so we widen f32 to f64 on bind; this
+            // is lossless, since every f32 value is exactly representable as an f64. Binding a
+            // NaN is converted to SQL NULL by SQLite itself (it has no on-disk REAL encoding for
+            // NaN); +/-Infinity are ordinary finite bit patterns for IEEE 754 storage purposes and
+            // round-trip unchanged.
             ValueType::Float(float) => float.map(|f| f as f64).map(ToSqlOutput::from),
             ValueType::Double(double) => double.map(ToSqlOutput::from),
             ValueType::Text(cow) => cow.as_ref().map(|cow| ToSqlOutput::from(cow.as_ref())),
@@ -298,3 +392,33 @@ impl<'a> ToSql for Value<'a> {
         }
     }
 }
+
+/// Binds a [`Value`] for a connection with Julian day (REAL) date/time encoding enabled, falling
+/// back to the default millisecond-since-epoch integer encoding (the plain `impl ToSql for
+/// Value`) for every other value. Used instead of binding `Value`s directly whenever
+/// [`super::Sqlite::set_julian_day_dates`] is on.
+pub(super) struct BoundValue<'a> {
+    pub(super) value: &'a Value<'a>,
+    pub(super) julian_day_dates: bool,
+}
+
+impl<'a> ToSql for BoundValue<'a> {
+    fn to_sql(&self) -> Result<ToSqlOutput, RusqlError> {
+        if self.julian_day_dates {
+            let julian_day = match &self.value.typed {
+                ValueType::DateTime(Some(dt)) => Some(datetime_to_julian_day(dt.naive_utc())),
+                ValueType::Date(Some(date)) => date.and_hms_opt(0, 0, 0).map(datetime_to_julian_day),
+                ValueType::Time(Some(time)) => {
+                    chrono::NaiveDate::from_ymd_opt(1970, 1, 1).map(|d| datetime_to_julian_day(d.and_time(*time)))
+                }
+                _ => None,
+            };
+
+            if let Some(julian_day) = julian_day {
+                return Ok(ToSqlOutput::from(julian_day));
+            }
+        }
+
+        self.value.to_sql()
+    }
+}
@@ -10,22 +10,50 @@ use crate::connector::IsolationLevel;
 pub use rusqlite::{params_from_iter, version as sqlite_version};
 
 use crate::{
-    ast::{Query, Value},
-    connector::{metrics, queryable::*, ResultSet},
+    ast::{Insert, Query, Value},
+    connector::{metrics, queryable::*, ResultSet, Transaction},
     error::{Error, ErrorKind},
     visitor::{self, Visitor},
 };
 use async_trait::async_trait;
+use futures::{future::BoxFuture, FutureExt};
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
+/// The maximum number of bound parameters accepted by a single SQLite statement in the default
+/// build configuration (`SQLITE_MAX_VARIABLE_NUMBER`). Older SQLite versions cap at 999; this is
+/// the conservative, widely-compatible value to chunk against.
+const SQLITE_MAX_BOUND_PARAMETERS: usize = 999;
+
 /// The underlying sqlite driver. Only available with the `expose-drivers` Cargo feature.
 #[cfg(feature = "expose-drivers")]
 pub use rusqlite;
 
+/// An event emitted to a callback registered with [`Sqlite::set_callback`].
+#[derive(Debug, Clone, Copy)]
+pub enum SqliteEvent<'a> {
+    /// The callback was just registered on an already-open connection.
+    Connect,
+    /// A query is about to be executed.
+    QueryStart { sql: &'a str },
+    /// A query finished executing successfully.
+    QueryEnd { sql: &'a str, duration: Duration },
+    /// A query failed.
+    Error { sql: &'a str, duration: Duration },
+}
+
+/// A callback observing [`SqliteEvent`]s, as registered with [`Sqlite::set_callback`].
+pub type SqliteEventCallback = Arc<dyn Fn(SqliteEvent<'_>) + Send + Sync>;
+
 /// A connector interface for the SQLite database
 pub struct Sqlite {
     pub(crate) client: Mutex<rusqlite::Connection>,
+    callback: Option<SqliteEventCallback>,
+    julian_day_dates: bool,
 }
 
 impl TryFrom<&str> for Sqlite {
@@ -37,13 +65,64 @@ impl TryFrom<&str> for Sqlite {
 
         let conn = rusqlite::Connection::open(file_path.as_str())?;
 
+        // `PRAGMA key` must be the very first statement run on an encrypted (SQLCipher)
+        // connection, before anything else touches the database file.
+        if let Some(key) = &params.key {
+            conn.pragma_update(None, "key", &key.0)?;
+            validate_encryption_key(&conn)?;
+        }
+
         if let Some(timeout) = params.socket_timeout {
             conn.busy_timeout(timeout)?;
         };
 
+        if let Some(case_sensitive_like) = params.case_sensitive_like {
+            conn.pragma_update(None, "case_sensitive_like", case_sensitive_like)?;
+        }
+
+        conn.pragma_update(None, "foreign_keys", params.foreign_keys.unwrap_or(true))?;
+
         let client = Mutex::new(conn);
 
-        Ok(Sqlite { client })
+        Ok(Sqlite {
+            client,
+            callback: None,
+            julian_day_dates: false,
+        })
+    }
+}
+
+/// Builds the SQL text for a multi-row `INSERT INTO table (columns...) VALUES (...), (...)`
+/// with `row_count` value tuples. Only the table, columns, and row count affect the resulting
+/// SQL, not the actual values being inserted, so callers can reuse the same SQL text (and the
+/// cached prepared statement behind it) across every chunk of a given size.
+fn multi_row_insert_sql<'a>(table: &'a str, columns: &'a [&'a str], row_count: usize) -> crate::Result<String> {
+    let mut insert = Insert::multi_into(table, columns.iter().copied());
+
+    for _ in 0..row_count {
+        let placeholders: Vec<Value<'a>> = (0..columns.len()).map(|_| Value::null_int32()).collect();
+        insert = insert.values(placeholders);
+    }
+
+    let (sql, _) = visitor::Sqlite::build(Insert::from(insert))?;
+
+    Ok(sql)
+}
+
+/// `PRAGMA key` never fails by itself, even with the wrong key: SQLCipher only notices on the
+/// first real read, where it surfaces as `SQLITE_NOTADB`, the same code used for plain file
+/// corruption. Since we know a key was just set on this connection, we can disambiguate here and
+/// turn that specific case into a clear authentication error instead of a misleading corruption
+/// one.
+fn validate_encryption_key(conn: &rusqlite::Connection) -> crate::Result<()> {
+    match conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0)) {
+        Ok(_) => Ok(()),
+        Err(rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { extended_code, .. }, _))
+            if extended_code == super::ffi::SQLITE_NOTADB =>
+        {
+            Err(Error::builder(ErrorKind::IncorrectDatabaseEncryptionKey).build())
+        }
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -55,9 +134,12 @@ impl Sqlite {
     /// Open a new SQLite database in memory.
     pub fn new_in_memory() -> crate::Result<Sqlite> {
         let client = rusqlite::Connection::open_in_memory()?;
+        client.pragma_update(None, "foreign_keys", true)?;
 
         Ok(Sqlite {
             client: Mutex::new(client),
+            callback: None,
+            julian_day_dates: false,
         })
     }
 
@@ -67,10 +149,517 @@ impl Sqlite {
     pub fn connection(&self) -> &Mutex<rusqlite::Connection> {
         &self.client
     }
+
+    /// Registers a callback invoked on connect, query start/end, and error, for observability
+    /// purposes. This complements [`metrics::query`](crate::connector::metrics::query) for
+    /// applications that want to plug in their own tracing.
+    ///
+    /// If the callback panics, the panic is caught and discarded: it must never be allowed to
+    /// unwind across the connection's internal mutex and poison it.
+    pub fn set_callback(&mut self, callback: impl Fn(SqliteEvent<'_>) + Send + Sync + 'static) {
+        self.callback = Some(Arc::new(callback));
+        self.fire_event(SqliteEvent::Connect);
+    }
+
+    /// Enables or disables binding `DATE`/`DATETIME`/`TIME` values as Julian day numbers
+    /// (`REAL`), instead of the default millisecond-since-epoch integer encoding. This is for
+    /// matching schemas that store temporal data as Julian days rather than ISO text or
+    /// millisecond integers; it only affects how new values are bound on this connection.
+    ///
+    /// Reading already decodes a `REAL` stored in a `DATE`/`DATETIME` column as a Julian day
+    /// regardless of this setting, since there is no other sensible interpretation for that
+    /// storage class in a date-affinity column.
+    pub fn set_julian_day_dates(&mut self, enabled: bool) {
+        self.julian_day_dates = enabled;
+    }
+
+    fn fire_event(&self, event: SqliteEvent<'_>) {
+        if let Some(callback) = &self.callback {
+            let _ = catch_unwind(AssertUnwindSafe(|| callback(event)));
+        }
+    }
+
+    /// Inserts many rows into `table` in as few multi-row `INSERT` statements as possible,
+    /// automatically splitting `rows` into multiple statements so that none of them exceeds
+    /// SQLite's limit on the number of bound parameters per statement.
+    ///
+    /// Every chunk but (potentially) the last has the same row count, and therefore the same
+    /// `INSERT` SQL text, so the statement is only re-prepared when the chunk size actually
+    /// changes: `prepare_cached` returns the same cached statement for matching chunks, and its
+    /// bindings are explicitly cleared before it's reused for the next one.
+    pub async fn bulk_insert<'a>(
+        &self,
+        table: &'a str,
+        columns: &'a [&'a str],
+        rows: Vec<Vec<Value<'a>>>,
+    ) -> crate::Result<u64> {
+        if columns.is_empty() || rows.is_empty() {
+            return Ok(0);
+        }
+
+        let rows_per_statement = (SQLITE_MAX_BOUND_PARAMETERS / columns.len()).max(1);
+        let client = self.client.lock().await;
+        let mut affected_rows = 0;
+        let mut prepared_row_count = None;
+
+        for chunk in rows.chunks(rows_per_statement) {
+            let sql = multi_row_insert_sql(table, columns, chunk.len())?;
+            let mut stmt = client.prepare_cached(&sql)?;
+
+            if prepared_row_count == Some(chunk.len()) {
+                stmt.clear_bindings();
+            }
+
+            prepared_row_count = Some(chunk.len());
+
+            let params = chunk.iter().flatten();
+            affected_rows += u64::try_from(stmt.execute(params_from_iter(params))?)?;
+        }
+
+        Ok(affected_rows)
+    }
+
+    /// Returns column metadata for `table`, as reported by `PRAGMA table_info`. This is a
+    /// lightweight alternative to the full schema describer for tools that only need
+    /// column-level information and don't want to pull it in.
+    pub async fn table_info(&self, table: &str) -> crate::Result<Vec<ColumnInfo>> {
+        let client = self.client.lock().await;
+        let mut columns = Vec::new();
+
+        client.pragma_query(None, "table_info", table, |row| {
+            columns.push(ColumnInfo {
+                name: row.get("name")?,
+                declared_type: row.get("type")?,
+                not_null: row.get::<_, i64>("notnull")? != 0,
+                default_value: row.get("dflt_value")?,
+                pk: row.get::<_, i64>("pk")? as u32,
+            });
+
+            Ok(())
+        })?;
+
+        Ok(columns)
+    }
+
+    /// Returns index metadata for `table`, as reported by `PRAGMA index_list`. This is a
+    /// lightweight alternative to the full schema describer for tools that only need
+    /// index-level information and don't want to pull it in.
+    pub async fn index_list(&self, table: &str) -> crate::Result<Vec<IndexListEntry>> {
+        let client = self.client.lock().await;
+        let mut indexes = Vec::new();
+
+        client.pragma_query(None, "index_list", table, |row| {
+            indexes.push(IndexListEntry {
+                name: row.get("name")?,
+                unique: row.get::<_, i64>("unique")? != 0,
+                origin: IndexOrigin::from_sql(&row.get::<_, String>("origin")?),
+                partial: row.get::<_, i64>("partial")? != 0,
+            });
+
+            Ok(())
+        })?;
+
+        Ok(indexes)
+    }
+
+    /// Returns the columns of `index`, in index order, as reported by `PRAGMA index_xinfo`.
+    /// `index_xinfo` is a superset of `PRAGMA index_info` that additionally reports each
+    /// column's sort direction (which `index_info` omits) and flags auxiliary rowid columns
+    /// SQLite appends to enforce uniqueness; those auxiliary columns are filtered out here so
+    /// the result only contains the columns the index was actually declared on.
+    pub async fn index_info(&self, index: &str) -> crate::Result<Vec<IndexInfoColumn>> {
+        let client = self.client.lock().await;
+        let mut columns = Vec::new();
+
+        client.pragma_query(None, "index_xinfo", index, |row| {
+            if row.get::<_, i64>("key")? == 0 {
+                return Ok(());
+            }
+
+            columns.push(IndexInfoColumn {
+                seqno: row.get::<_, i64>("seqno")? as u32,
+                name: row.get("name")?,
+                sort_order: match row.get::<_, i64>("desc")? {
+                    0 => IndexColumnSortOrder::Asc,
+                    _ => IndexColumnSortOrder::Desc,
+                },
+            });
+
+            Ok(())
+        })?;
+
+        columns.sort_by_key(|c| c.seqno);
+
+        Ok(columns)
+    }
+
+    /// Returns the database file's `application_id` pragma, an arbitrary 32-bit integer an
+    /// application can stamp into its own database files to distinguish them from other SQLite
+    /// files. A freshly created (or never explicitly tagged) database reads back as `0`.
+    pub async fn application_id(&self) -> crate::Result<i32> {
+        let client = self.client.lock().await;
+        let id = client.pragma_query_value(None, "application_id", |row| row.get(0))?;
+
+        Ok(id)
+    }
+
+    /// Sets the database file's `application_id` pragma. See [`Sqlite::application_id`].
+    pub async fn set_application_id(&self, id: i32) -> crate::Result<()> {
+        let client = self.client.lock().await;
+        client.pragma_update(None, "application_id", id)?;
+
+        Ok(())
+    }
+
+    /// Returns whether foreign key constraint enforcement is currently turned on for this
+    /// connection, as reported by `PRAGMA foreign_keys`. Enabled by default; disabled via the
+    /// `foreign_keys=false` connection string parameter, or by calling
+    /// [`Sqlite::set_foreign_keys_enabled`].
+    pub async fn foreign_keys_enabled(&self) -> crate::Result<bool> {
+        let client = self.client.lock().await;
+        let enabled: i64 = client.pragma_query_value(None, "foreign_keys", |row| row.get(0))?;
+
+        Ok(enabled != 0)
+    }
+
+    /// Turns foreign key constraint enforcement on or off for this connection (`PRAGMA
+    /// foreign_keys`), for bulk-load scenarios that want to defer enforcement until all the data
+    /// is in place.
+    ///
+    /// Turning enforcement back on does not itself check for violations introduced while it was
+    /// off; use [`Sqlite::foreign_key_check`] afterwards to find them.
+    pub async fn set_foreign_keys_enabled(&self, enabled: bool) -> crate::Result<()> {
+        let client = self.client.lock().await;
+        client.pragma_update(None, "foreign_keys", enabled)?;
+
+        Ok(())
+    }
+
+    /// Runs `PRAGMA foreign_key_check`, returning every foreign key constraint violation
+    /// currently present in the database. Foreign key enforcement only rejects violations as
+    /// they are introduced, so this is the way to find violations that were already let through
+    /// while enforcement was disabled with [`Sqlite::set_foreign_keys_enabled`].
+    pub async fn foreign_key_check(&self) -> crate::Result<Vec<ForeignKeyViolation>> {
+        let client = self.client.lock().await;
+        let mut violations = Vec::new();
+
+        let mut stmt = client.prepare("PRAGMA foreign_key_check")?;
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            violations.push(ForeignKeyViolation {
+                table: row.get("table")?,
+                rowid: row.get("rowid")?,
+                referenced_table: row.get("parent")?,
+                foreign_key_id: row.get::<_, i64>("fkid")? as u32,
+            });
+        }
+
+        Ok(violations)
+    }
+
+    /// Runs `PRAGMA wal_checkpoint(<mode>)`, moving frames from the write-ahead log into the
+    /// main database file. This lets an application bound WAL file growth by checkpointing
+    /// explicitly after a batch of writes, rather than waiting for SQLite's automatic
+    /// checkpointing (which only runs opportunistically, after the WAL crosses a page threshold).
+    ///
+    /// On a database that is not in WAL mode, this is a no-op: SQLite reports -1 for both frame
+    /// counts and `busy: false` without error.
+    pub async fn wal_checkpoint(&self, mode: CheckpointMode) -> crate::Result<CheckpointResult> {
+        let client = self.client.lock().await;
+        let sql = format!("PRAGMA wal_checkpoint({})", mode.as_sql());
+
+        let result = client.query_row(&sql, [], |row| {
+            Ok(CheckpointResult {
+                busy: row.get::<_, i64>(0)? != 0,
+                log_frames: row.get(1)?,
+                checkpointed_frames: row.get(2)?,
+            })
+        })?;
+
+        Ok(result)
+    }
+
+    /// Returns the total number of rows inserted, updated, or deleted by all statements executed
+    /// on this connection since it was opened, as reported by `sqlite3_total_changes()`. Unlike
+    /// the per-statement row count [`Queryable::execute`](crate::connector::Queryable::execute)
+    /// returns, this accumulates across the whole connection, including rows changed by triggers
+    /// fired as a side effect of a statement — useful for cheaply detecting whether anything at
+    /// all was written during a session.
+    pub async fn total_changes(&self) -> u64 {
+        let client = self.client.lock().await;
+        u64::try_from(client.total_changes()).unwrap_or(0)
+    }
+
+    /// Pre-compiles `statements` and stores them in the connection's prepared statement cache
+    /// (the same cache `query_raw`/`execute_raw` populate via `prepare_cached`), so the first
+    /// real query that uses one of them doesn't pay the compilation cost. Useful to warm up the
+    /// cache right after opening a connection, before the application starts serving traffic.
+    ///
+    /// Stops at the first statement that fails to compile, with an error naming it.
+    pub async fn prepare_cache(&self, statements: &[&str]) -> crate::Result<()> {
+        let client = self.client.lock().await;
+
+        for sql in statements {
+            client.prepare_cached(sql).map_err(|err| {
+                let mut builder = Error::builder(ErrorKind::QueryError(err.into()));
+                builder.set_original_message(format!("Failed to prepare statement for cache priming: {sql}"));
+                builder.build()
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs `sql`, binding its named placeholders (e.g. `:name`, `@name`, `$name`) from `params`
+    /// instead of positionally, for call sites that build the set of placeholders dynamically
+    /// and so don't know their order up front. A placeholder named `:name` is looked up in
+    /// `params` both as `"name"` and as `":name"`, so callers don't need to worry about whether
+    /// their map keys include the leading sigil.
+    ///
+    /// Errors if `sql` has a placeholder (named or positional) that `params` doesn't have an
+    /// entry for. Logs a warning, but does not error, for entries in `params` that don't
+    /// correspond to any placeholder in `sql`.
+    pub async fn query_raw_map(&self, sql: &str, params: &HashMap<String, Value<'_>>) -> crate::Result<ResultSet> {
+        let mut used_keys = std::collections::HashSet::new();
+
+        let bound_params = {
+            let client = self.client.lock().await;
+            let stmt = client.prepare_cached(sql)?;
+            let mut bound_params = Vec::with_capacity(stmt.parameter_count());
+
+            for i in 1..=stmt.parameter_count() {
+                let name = stmt.parameter_name(i).ok_or_else(|| {
+                    Error::builder(ErrorKind::QueryInvalidInput(format!(
+                        "query_raw_map only supports named placeholders, but parameter {i} in the query is positional"
+                    )))
+                    .build()
+                })?;
+                let unsigiled_name = name.trim_start_matches([':', '@', '$']);
+
+                let (key, value) = params
+                    .get_key_value(name)
+                    .or_else(|| params.get_key_value(unsigiled_name))
+                    .ok_or_else(|| {
+                        Error::builder(ErrorKind::QueryInvalidInput(format!(
+                            "Missing value for query parameter `{name}`"
+                        )))
+                        .build()
+                    })?;
+
+                used_keys.insert(key.as_str());
+                bound_params.push(value.clone());
+            }
+
+            bound_params
+        };
+
+        for key in params.keys() {
+            if !used_keys.contains(key.as_str()) {
+                tracing::warn!(message = "Unused parameter passed to query_raw_map", key = %key);
+            }
+        }
+
+        self.query_raw(sql, &bound_params).await
+    }
+}
+
+/// The mode for [`Sqlite::wal_checkpoint`], controlling how much it blocks other connections
+/// while checkpointing. See the SQLite documentation for `PRAGMA wal_checkpoint` for the exact
+/// semantics of each mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointMode {
+    /// Checkpoint as many frames as possible without blocking writers or readers.
+    Passive,
+    /// Block new writers, wait for readers to finish, then checkpoint all frames.
+    Full,
+    /// Like `Full`, and additionally reset the WAL file back to its start once the checkpoint
+    /// has emptied it.
+    Restart,
+    /// Like `Restart`, and additionally truncate the WAL file to zero bytes on completion.
+    Truncate,
+}
+
+impl CheckpointMode {
+    fn as_sql(self) -> &'static str {
+        match self {
+            CheckpointMode::Passive => "PASSIVE",
+            CheckpointMode::Full => "FULL",
+            CheckpointMode::Restart => "RESTART",
+            CheckpointMode::Truncate => "TRUNCATE",
+        }
+    }
+}
+
+/// The result of a [`Sqlite::wal_checkpoint`] call, as reported by `PRAGMA wal_checkpoint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointResult {
+    /// Whether the checkpoint could not acquire the locks it needed because another connection
+    /// was busy; when `true`, fewer frames may have been checkpointed than requested.
+    pub busy: bool,
+    /// The number of frames in the WAL file.
+    pub log_frames: i64,
+    /// The number of frames that were checkpointed into the database file.
+    pub checkpointed_frames: i64,
+}
+
+/// A single row of `PRAGMA foreign_key_check`, describing one foreign key constraint violation.
+/// See [`Sqlite::foreign_key_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyViolation {
+    /// The table containing the row that violates a foreign key constraint.
+    pub table: String,
+    /// The rowid of the offending row, or `None` if `table` is a `WITHOUT ROWID` table.
+    pub rowid: Option<i64>,
+    /// The table the violated foreign key refers to.
+    pub referenced_table: String,
+    /// The 0-based index of the violated foreign key among `table`'s foreign keys, as reported
+    /// by `PRAGMA foreign_key_list`.
+    pub foreign_key_id: u32,
+}
+
+/// A single row of `PRAGMA table_info(<table>)`, describing one column of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInfo {
+    /// The column name.
+    pub name: String,
+    /// The column's declared type, exactly as written in the `CREATE TABLE` statement (e.g.
+    /// `"INTEGER"`, `"TEXT"`). SQLite's type affinity rules mean this is advisory rather than
+    /// enforced.
+    pub declared_type: String,
+    /// Whether the column has a `NOT NULL` constraint.
+    pub not_null: bool,
+    /// The column's default value expression, if any, exactly as written in the schema.
+    pub default_value: Option<String>,
+    /// The column's 1-based position within the primary key, or `0` if it is not part of the
+    /// primary key. A table with a composite primary key has more than one column with a
+    /// nonzero `pk`.
+    pub pk: u32,
+}
+
+/// A single row of `PRAGMA index_list(<table>)`, describing one index on a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexListEntry {
+    /// The index name. For an index implicitly created to enforce a `UNIQUE` column or a
+    /// primary key, this is SQLite's generated `sqlite_autoindex_<table>_<n>` name rather than
+    /// one that appears anywhere in the schema's SQL text.
+    pub name: String,
+    /// Whether the index enforces uniqueness.
+    pub unique: bool,
+    /// How the index came to exist. See [`IndexOrigin`].
+    pub origin: IndexOrigin,
+    /// Whether the index is partial, i.e. has a `WHERE` clause restricting which rows it
+    /// covers.
+    pub partial: bool,
+}
+
+/// How an index reported by [`Sqlite::index_list`] came to exist, as encoded by SQLite's
+/// single-character `origin` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexOrigin {
+    /// Created by an explicit `CREATE INDEX` statement.
+    CreateIndex,
+    /// Created implicitly to enforce a column or table `UNIQUE` constraint.
+    Unique,
+    /// Created implicitly to enforce the table's primary key.
+    PrimaryKey,
+}
+
+impl IndexOrigin {
+    fn from_sql(origin: &str) -> Self {
+        match origin {
+            "c" => IndexOrigin::CreateIndex,
+            "u" => IndexOrigin::Unique,
+            "pk" => IndexOrigin::PrimaryKey,
+            other => panic!("Unrecognized index origin '{other}'"),
+        }
+    }
+}
+
+/// A single column of an index, as reported by [`Sqlite::index_info`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexInfoColumn {
+    /// The column's 0-based position within the index.
+    pub seqno: u32,
+    /// The column's name, or `None` if the index is on the rowid or an expression rather than a
+    /// named column.
+    pub name: Option<String>,
+    /// The direction this column is sorted in within the index.
+    pub sort_order: IndexColumnSortOrder,
+}
+
+/// The sort direction of an index column, as reported by [`Sqlite::index_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexColumnSortOrder {
+    Asc,
+    Desc,
+}
+
+impl Sqlite {
+    /// Runs `q`, failing with [`ErrorKind::SocketTimeout`] if it does not complete within
+    /// `timeout`. This bounds total query execution time, as opposed to the `socket_timeout`
+    /// connection option (a `busy_timeout` pragma under the hood), which only bounds how long
+    /// the connection waits to acquire a lock before giving up.
+    ///
+    /// Unlike a plain `tokio::time::timeout` race, this actually aborts the query on timeout:
+    /// `rusqlite` runs the query synchronously on the task that polls this future, so a bare
+    /// race would never get to poll its timer while a slow query is running. Instead, a
+    /// background task calls SQLite's interrupt handle once `timeout` elapses, which makes the
+    /// blocked `rusqlite` call return `SQLITE_INTERRUPT` and unblocks the task. To handle the
+    /// case where `q` completes right as the timer fires, the background task checks an atomic
+    /// flag — set right after `q` completes, before the interrupter is given a chance to run —
+    /// and skips the interrupt call if `q` already finished.
+    pub async fn query_with_timeout(&self, q: Query<'_>, timeout: Duration) -> crate::Result<ResultSet> {
+        let interrupt_handle = self.client.lock().await.get_interrupt_handle();
+        let done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let done_for_interrupter = done.clone();
+
+        let interrupter = tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+
+            if !done_for_interrupter.load(std::sync::atomic::Ordering::SeqCst) {
+                interrupt_handle.interrupt();
+            }
+        });
+
+        let result = self.query(q).await;
+        done.store(true, std::sync::atomic::Ordering::SeqCst);
+        interrupter.abort();
+
+        result
+    }
 }
 
 impl_default_TransactionCapable!(Sqlite);
 
+impl Sqlite {
+    /// Runs `f` inside a transaction: begins it, commits if `f` resolves to `Ok`, and rolls back
+    /// if `f` resolves to `Err` or panics. This saves callers from having to remember to roll
+    /// back manually on every exit path, which is easy to get wrong under the synchronous
+    /// `rusqlite` connection hidden behind this async API.
+    pub async fn transaction<T>(
+        &self,
+        f: impl for<'a> FnOnce(&'a dyn Transaction) -> BoxFuture<'a, crate::Result<T>>,
+    ) -> crate::Result<T> {
+        let tx = self.start_transaction(None).await?;
+
+        match AssertUnwindSafe(f(tx.as_ref())).catch_unwind().await {
+            Ok(Ok(value)) => {
+                tx.commit().await?;
+                Ok(value)
+            }
+            Ok(Err(error)) => {
+                tx.rollback().await?;
+                Err(error)
+            }
+            Err(panic) => {
+                let _ = tx.rollback().await;
+                std::panic::resume_unwind(panic);
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl Queryable for Sqlite {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
@@ -79,23 +668,52 @@ impl Queryable for Sqlite {
     }
 
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
-        metrics::query("sqlite.query_raw", sql, params, move || async move {
+        self.fire_event(SqliteEvent::QueryStart { sql });
+        let start = std::time::Instant::now();
+
+        let result = metrics::query("sqlite.query_raw", sql, params, move || async move {
             let client = self.client.lock().await;
 
             let mut stmt = client.prepare_cached(sql)?;
 
-            let mut rows = stmt.query(params_from_iter(params.iter()))?;
+            let bound_params: Vec<_> = params
+                .iter()
+                .map(|value| conversion::BoundValue {
+                    value,
+                    julian_day_dates: self.julian_day_dates,
+                })
+                .collect();
+
+            let mut rows = stmt.query(params_from_iter(bound_params.iter()))?;
             let mut result = ResultSet::new(rows.to_column_names(), Vec::new());
 
             while let Some(row) = rows.next()? {
                 result.rows.push(row.get_result_row()?);
             }
 
-            result.set_last_insert_id(u64::try_from(client.last_insert_rowid()).unwrap_or(0));
+            // `last_insert_rowid()` is a 64-bit signed value; SQLite rowids are virtually always
+            // non-negative, but if one isn't, reporting it as `0` would silently point callers at
+            // the wrong row, so it's left unset instead.
+            if let Ok(last_insert_id) = u64::try_from(client.last_insert_rowid()) {
+                result.set_last_insert_id(last_insert_id);
+            }
 
             Ok(result)
         })
-        .await
+        .await;
+
+        match &result {
+            Ok(_) => self.fire_event(SqliteEvent::QueryEnd {
+                sql,
+                duration: start.elapsed(),
+            }),
+            Err(_) => self.fire_event(SqliteEvent::Error {
+                sql,
+                duration: start.elapsed(),
+            }),
+        }
+
+        result
     }
 
     async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
@@ -108,14 +726,39 @@ impl Queryable for Sqlite {
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
-        metrics::query("sqlite.query_raw", sql, params, move || async move {
+        self.fire_event(SqliteEvent::QueryStart { sql });
+        let start = std::time::Instant::now();
+
+        let result = metrics::query("sqlite.query_raw", sql, params, move || async move {
             let client = self.client.lock().await;
             let mut stmt = client.prepare_cached(sql)?;
-            let res = u64::try_from(stmt.execute(params_from_iter(params.iter()))?)?;
+
+            let bound_params: Vec<_> = params
+                .iter()
+                .map(|value| conversion::BoundValue {
+                    value,
+                    julian_day_dates: self.julian_day_dates,
+                })
+                .collect();
+
+            let res = u64::try_from(stmt.execute(params_from_iter(bound_params.iter()))?)?;
 
             Ok(res)
         })
-        .await
+        .await;
+
+        match &result {
+            Ok(_) => self.fire_event(SqliteEvent::QueryEnd {
+                sql,
+                duration: start.elapsed(),
+            }),
+            Err(_) => self.fire_event(SqliteEvent::Error {
+                sql,
+                duration: start.elapsed(),
+            }),
+        }
+
+        result
     }
 
     async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
@@ -164,6 +807,7 @@ mod tests {
         connector::Queryable,
         error::{ErrorKind, Name},
     };
+    use chrono::TimeZone;
 
     #[tokio::test]
     async fn unknown_table_should_give_a_good_error() {
@@ -231,4 +875,794 @@ mod tests {
 
         assert_eq!(result.get("txt space").unwrap(), &Value::text("henlo"));
     }
+
+    #[tokio::test]
+    async fn bulk_insert_chunks_rows_to_respect_the_bound_parameter_limit() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (a INTEGER, b INTEGER);")
+            .await
+            .unwrap();
+
+        let rows: Vec<Vec<Value>> = (0..2000)
+            .map(|i| vec![Value::int32(i), Value::int32(i * 2)])
+            .collect();
+
+        let affected_rows = conn.bulk_insert("test", &["a", "b"], rows).await.unwrap();
+        assert_eq!(affected_rows, 2000);
+
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap();
+        let result = result.into_single().unwrap();
+
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 2000);
+    }
+
+    #[tokio::test]
+    async fn bulk_insert_reuses_a_cached_statement_across_same_sized_chunks_and_rebinds_a_partial_last_one() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (a INTEGER);").await.unwrap();
+
+        // Two full chunks of `rows_per_statement` rows followed by a smaller, differently sized
+        // one, so the same cached statement is reused for the first two and a fresh one is
+        // prepared for the last.
+        let rows_per_statement = SQLITE_MAX_BOUND_PARAMETERS;
+        let row_count = rows_per_statement * 2 + 1;
+        let rows: Vec<Vec<Value>> = (0..row_count as i32).map(|i| vec![Value::int32(i)]).collect();
+
+        let affected_rows = conn.bulk_insert("test", &["a"], rows).await.unwrap();
+        assert_eq!(affected_rows, row_count as u64);
+
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap();
+        let result = result.into_single().unwrap();
+
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), row_count as i64);
+    }
+
+    #[tokio::test]
+    async fn registered_callback_fires_for_queries() {
+        let mut conn = Sqlite::new_in_memory().unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let events_for_callback = events.clone();
+
+        conn.set_callback(move |event| {
+            let description = match event {
+                SqliteEvent::Connect => "connect".to_string(),
+                SqliteEvent::QueryStart { sql } => format!("start:{sql}"),
+                SqliteEvent::QueryEnd { sql, .. } => format!("end:{sql}"),
+                SqliteEvent::Error { sql, .. } => format!("error:{sql}"),
+            };
+
+            events_for_callback.lock().unwrap().push(description);
+        });
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        let recorded = events.lock().unwrap().clone();
+        assert_eq!(recorded[0], "connect");
+        assert!(recorded
+            .iter()
+            .any(|e| e.starts_with("start:") && e.contains("CREATE TABLE")));
+        assert!(recorded
+            .iter()
+            .any(|e| e.starts_with("end:") && e.contains("CREATE TABLE")));
+    }
+
+    #[tokio::test]
+    async fn panicking_callback_does_not_poison_the_connection() {
+        let mut conn = Sqlite::new_in_memory().unwrap();
+
+        conn.set_callback(|_| panic!("boom"));
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        let insert = Insert::single_into("test").value("id", 1);
+        conn.insert(insert.into()).await.unwrap();
+
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap();
+        let result = result.into_single().unwrap();
+
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn transaction_commits_on_ok() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        conn.transaction(|tx| {
+            Box::pin(async move {
+                tx.insert(Insert::single_into("test").value("id", 1).into())
+                    .await?;
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn transaction_rolls_back_on_err() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        let result: crate::Result<()> = conn
+            .transaction(|tx| {
+                Box::pin(async move {
+                    tx.insert(Insert::single_into("test").value("id", 1).into())
+                        .await?;
+
+                    let kind = ErrorKind::conversion("rollback me");
+                    Err(Error::builder(kind).build())
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sqlcipher")]
+    async fn opening_an_encrypted_database_with_the_right_key_works() {
+        let path = "db/encrypted_right_key.db";
+        let _ = std::fs::remove_file(path);
+
+        {
+            let conn = Sqlite::try_from(&format!("file:{path}?key=s3cr3t")).unwrap();
+            conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+                .await
+                .unwrap();
+        }
+
+        let conn = Sqlite::try_from(&format!("file:{path}?key=s3cr3t")).unwrap();
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 0);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sqlcipher")]
+    async fn opening_an_encrypted_database_with_the_wrong_key_gives_a_clear_error() {
+        let path = "db/encrypted_wrong_key.db";
+        let _ = std::fs::remove_file(path);
+
+        {
+            let conn = Sqlite::try_from(&format!("file:{path}?key=s3cr3t")).unwrap();
+            conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+                .await
+                .unwrap();
+        }
+
+        let err = Sqlite::try_from(&format!("file:{path}?key=wrong")).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::IncorrectDatabaseEncryptionKey));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn transaction_rolls_back_on_panic() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        let result = std::panic::AssertUnwindSafe(conn.transaction(|tx| {
+            Box::pin(async move {
+                tx.insert(Insert::single_into("test").value("id", 1).into())
+                    .await?;
+                panic!("boom");
+            })
+        }))
+        .catch_unwind()
+        .await;
+
+        assert!(result.is_err());
+
+        let select = Select::from_table("test").value(count(asterisk()));
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn query_with_timeout_interrupts_a_slow_query() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        // No practical end, so it keeps SQLite busy well past the timeout below and gives the
+        // interrupt handle time to fire.
+        let slow_query = Query::from(
+            "WITH RECURSIVE slow(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM slow LIMIT 2000000000) \
+             SELECT count(*) FROM slow",
+        );
+
+        let err = conn
+            .query_with_timeout(slow_query, Duration::from_millis(20))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::SocketTimeout));
+
+        // The connection must still be usable for later queries.
+        let select = Select::from_table("sqlite_master").value(count(asterisk()));
+        conn.select(select).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn table_info_describes_columns_including_a_composite_primary_key() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd(
+            "CREATE TABLE USER (
+                org_id INTEGER NOT NULL,
+                user_id INTEGER NOT NULL,
+                name TEXT DEFAULT 'anonymous',
+                PRIMARY KEY (org_id, user_id)
+            );",
+        )
+        .await
+        .unwrap();
+
+        let columns = conn.table_info("USER").await.unwrap();
+
+        assert_eq!(columns.len(), 3);
+
+        let org_id = columns.iter().find(|c| c.name == "org_id").unwrap();
+        assert_eq!(org_id.declared_type, "INTEGER");
+        assert!(org_id.not_null);
+        assert_eq!(org_id.default_value, None);
+        assert_eq!(org_id.pk, 1);
+
+        let user_id = columns.iter().find(|c| c.name == "user_id").unwrap();
+        assert_eq!(user_id.declared_type, "INTEGER");
+        assert!(user_id.not_null);
+        assert_eq!(user_id.pk, 2);
+
+        let name = columns.iter().find(|c| c.name == "name").unwrap();
+        assert_eq!(name.declared_type, "TEXT");
+        assert!(!name.not_null);
+        assert_eq!(name.default_value, Some("'anonymous'".to_string()));
+        assert_eq!(name.pk, 0);
+    }
+
+    #[tokio::test]
+    async fn index_list_and_index_info_describe_a_composite_unique_index() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd(
+            "CREATE TABLE test (a INTEGER, b INTEGER, c INTEGER);
+             CREATE UNIQUE INDEX test_a_b_idx ON test (a, b DESC);
+             CREATE INDEX test_c_idx ON test (c);",
+        )
+        .await
+        .unwrap();
+
+        let indexes = conn.index_list("test").await.unwrap();
+        assert_eq!(indexes.len(), 2);
+
+        let composite = indexes.iter().find(|i| i.name == "test_a_b_idx").unwrap();
+        assert!(composite.unique);
+        assert_eq!(composite.origin, IndexOrigin::CreateIndex);
+        assert!(!composite.partial);
+
+        let by_c = indexes.iter().find(|i| i.name == "test_c_idx").unwrap();
+        assert!(!by_c.unique);
+        assert_eq!(by_c.origin, IndexOrigin::CreateIndex);
+
+        let columns = conn.index_info("test_a_b_idx").await.unwrap();
+
+        assert_eq!(
+            columns,
+            vec![
+                IndexInfoColumn {
+                    seqno: 0,
+                    name: Some("a".to_string()),
+                    sort_order: IndexColumnSortOrder::Asc,
+                },
+                IndexInfoColumn {
+                    seqno: 1,
+                    name: Some("b".to_string()),
+                    sort_order: IndexColumnSortOrder::Desc,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn index_list_reports_an_inline_unique_constraint_as_an_auto_index() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY, email TEXT UNIQUE);")
+            .await
+            .unwrap();
+
+        let indexes = conn.index_list("test").await.unwrap();
+        let auto_index = indexes
+            .iter()
+            .find(|i| i.name.starts_with("sqlite_autoindex_"))
+            .expect("the UNIQUE column should have created an auto-index");
+
+        assert!(auto_index.unique);
+        assert_eq!(auto_index.origin, IndexOrigin::Unique);
+
+        let columns = conn.index_info(&auto_index.name).await.unwrap();
+        assert_eq!(columns, vec![IndexInfoColumn {
+            seqno: 0,
+            name: Some("email".to_string()),
+            sort_order: IndexColumnSortOrder::Asc,
+        }]);
+    }
+
+    #[tokio::test]
+    async fn selecting_rowid_reads_it_back_as_an_i64() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (txt TEXT NOT NULL);").await.unwrap();
+        conn.raw_cmd("INSERT INTO test (rowid, txt) VALUES (4294967296, 'henlo');")
+            .await
+            .unwrap();
+
+        let result = conn
+            .query_raw("SELECT rowid FROM test", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("rowid").unwrap(), &Value::int64(4294967296));
+    }
+
+    #[tokio::test]
+    async fn binding_a_u64_above_i64_max_round_trips_through_text() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (big TEXT NOT NULL);").await.unwrap();
+
+        let value = u64::MAX;
+        conn.execute_raw("INSERT INTO test (big) VALUES (?)", &[Value::unsigned_int64(value)])
+            .await
+            .unwrap();
+
+        let result = conn
+            .query_raw("SELECT big FROM test", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("big").unwrap().as_str().unwrap(), value.to_string());
+    }
+
+    #[tokio::test]
+    async fn application_id_defaults_to_zero_and_roundtrips_through_set_application_id() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        assert_eq!(conn.application_id().await.unwrap(), 0);
+
+        conn.set_application_id(1_234_567).await.unwrap();
+
+        assert_eq!(conn.application_id().await.unwrap(), 1_234_567);
+    }
+
+    #[tokio::test]
+    async fn foreign_keys_are_enabled_by_default() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        assert!(conn.foreign_keys_enabled().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn foreign_keys_off_connection_string_param_disables_enforcement() {
+        let path = "db/foreign_keys_off_test.db";
+        let _ = std::fs::remove_file(path);
+
+        let conn = Sqlite::try_from(&format!("file:{path}?foreign_keys=false")).unwrap();
+
+        assert!(!conn.foreign_keys_enabled().await.unwrap());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn set_foreign_keys_enabled_toggles_enforcement_at_runtime() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.set_foreign_keys_enabled(false).await.unwrap();
+        assert!(!conn.foreign_keys_enabled().await.unwrap());
+
+        conn.set_foreign_keys_enabled(true).await.unwrap();
+        assert!(conn.foreign_keys_enabled().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn foreign_key_check_finds_violations_introduced_while_enforcement_was_disabled() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE parent (id INTEGER PRIMARY KEY);").await.unwrap();
+        conn.raw_cmd(
+            "CREATE TABLE child (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES parent(id));",
+        )
+        .await
+        .unwrap();
+
+        assert!(conn.foreign_key_check().await.unwrap().is_empty());
+
+        conn.set_foreign_keys_enabled(false).await.unwrap();
+
+        conn.raw_cmd("INSERT INTO child (id, parent_id) VALUES (1, 999);")
+            .await
+            .unwrap();
+
+        conn.set_foreign_keys_enabled(true).await.unwrap();
+
+        let violations = conn.foreign_key_check().await.unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].table, "child");
+        assert_eq!(violations[0].referenced_table, "parent");
+        assert_eq!(violations[0].rowid, Some(1));
+    }
+
+    #[tokio::test]
+    async fn wal_checkpoint_is_a_noop_on_a_non_wal_database() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY);")
+            .await
+            .unwrap();
+
+        let result = conn.wal_checkpoint(CheckpointMode::Passive).await.unwrap();
+
+        assert!(!result.busy);
+        assert_eq!(result.log_frames, -1);
+        assert_eq!(result.checkpointed_frames, -1);
+    }
+
+    #[tokio::test]
+    async fn passive_checkpoint_moves_wal_frames_into_the_database_file() {
+        let path = "db/wal_checkpoint_test.db";
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(format!("{path}-wal"));
+        let _ = std::fs::remove_file(format!("{path}-shm"));
+
+        let conn = Sqlite::try_from(&format!("file:{path}")).unwrap();
+
+        conn.raw_cmd("PRAGMA journal_mode=WAL;").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY, txt TEXT);")
+            .await
+            .unwrap();
+
+        let insert = Insert::single_into("test").value("txt", "henlo");
+        conn.insert(insert.into()).await.unwrap();
+
+        let result = conn.wal_checkpoint(CheckpointMode::Passive).await.unwrap();
+
+        assert!(!result.busy);
+        assert!(result.checkpointed_frames > 0);
+        assert!(result.checkpointed_frames <= result.log_frames);
+
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(format!("{path}-wal"));
+        let _ = std::fs::remove_file(format!("{path}-shm"));
+    }
+
+    #[tokio::test]
+    async fn case_sensitive_like_pragma_changes_like_matching() {
+        let path = "db/case_sensitive_like_test.db";
+        let _ = std::fs::remove_file(path);
+
+        let conn = Sqlite::try_from(&format!("file:{path}?case_sensitive_like=true")).unwrap();
+        conn.raw_cmd("CREATE TABLE test (txt TEXT NOT NULL);").await.unwrap();
+        conn.raw_cmd("INSERT INTO test (txt) VALUES ('Henlo');")
+            .await
+            .unwrap();
+
+        let select = Select::from_table("test")
+            .value(count(asterisk()))
+            .so_that("txt".like("henlo"));
+
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 0);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn case_sensitive_like_pragma_leaves_escape_clauses_working() {
+        let path = "db/case_sensitive_like_escape_test.db";
+        let _ = std::fs::remove_file(path);
+
+        let conn = Sqlite::try_from(&format!("file:{path}?case_sensitive_like=true")).unwrap();
+        conn.raw_cmd("CREATE TABLE test (txt TEXT NOT NULL);").await.unwrap();
+        conn.raw_cmd("INSERT INTO test (txt) VALUES ('100%');")
+            .await
+            .unwrap();
+
+        let result = conn
+            .query_raw("SELECT COUNT(*) AS c FROM test WHERE txt LIKE '100$%' ESCAPE '$'", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("c").unwrap().as_i64().unwrap(), 1);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn without_the_pragma_like_stays_case_insensitive() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (txt TEXT NOT NULL);").await.unwrap();
+        conn.raw_cmd("INSERT INTO test (txt) VALUES ('Henlo');")
+            .await
+            .unwrap();
+
+        let select = Select::from_table("test")
+            .value(count(asterisk()))
+            .so_that("txt".like("henlo"));
+
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+        assert_eq!(result.at(0).unwrap().as_i64().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn selecting_rowid_from_a_without_rowid_table_gives_a_clear_error() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (txt TEXT NOT NULL PRIMARY KEY) WITHOUT ROWID;")
+            .await
+            .unwrap();
+
+        let err = conn.query_raw("SELECT rowid FROM test", &[]).await.unwrap_err();
+
+        match err.kind() {
+            ErrorKind::ColumnNotFound { column } => {
+                assert_eq!(&Name::available("rowid"), column);
+            }
+            e => panic!("Expected error ColumnNotFound, got {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn total_changes_accumulates_across_statements_including_trigger_side_effects() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        assert_eq!(conn.total_changes().await, 0);
+
+        conn.raw_cmd(
+            "CREATE TABLE test (id INTEGER PRIMARY KEY, txt TEXT);
+             CREATE TABLE audit (id INTEGER PRIMARY KEY, message TEXT);
+             CREATE TRIGGER log_insert AFTER INSERT ON test
+             BEGIN
+                 INSERT INTO audit (message) VALUES ('inserted');
+             END;",
+        )
+        .await
+        .unwrap();
+
+        // The trigger fires on the same statement, so one INSERT on `test` produces two changes.
+        let insert = Insert::single_into("test").value("txt", "henlo");
+        conn.insert(insert.into()).await.unwrap();
+
+        assert_eq!(conn.total_changes().await, 2);
+
+        let insert = Insert::single_into("test").value("txt", "henlo again");
+        conn.insert(insert.into()).await.unwrap();
+
+        assert_eq!(conn.total_changes().await, 4);
+    }
+
+    #[tokio::test]
+    async fn prepare_cache_primes_statements_reused_by_later_queries() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY, txt TEXT);")
+            .await
+            .unwrap();
+
+        conn.prepare_cache(&[
+            "INSERT INTO test (txt) VALUES (?)",
+            "SELECT txt FROM test WHERE id = ?",
+        ])
+        .await
+        .unwrap();
+
+        // `query_raw`/`execute_raw` go through the same `prepare_cached` call, so these reuse the
+        // statements primed above instead of recompiling them.
+        conn.query_raw("INSERT INTO test (txt) VALUES (?)", &[Value::text("henlo")])
+            .await
+            .unwrap();
+
+        let result = conn
+            .query_raw("SELECT txt FROM test WHERE id = ?", &[Value::int32(1)])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("txt").unwrap(), &Value::text("henlo"));
+    }
+
+    #[tokio::test]
+    async fn prepare_cache_errors_naming_the_offending_statement() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        let err = conn
+            .prepare_cache(&["SELECT 1", "THIS IS NOT VALID SQL"])
+            .await
+            .unwrap_err();
+
+        assert!(err.original_message().unwrap().contains("THIS IS NOT VALID SQL"));
+    }
+
+    #[tokio::test]
+    async fn julian_day_dates_binds_a_datetime_as_a_real_and_reads_it_back() {
+        let mut conn = Sqlite::new_in_memory().unwrap();
+        conn.set_julian_day_dates(true);
+
+        conn.raw_cmd("CREATE TABLE test (dt DATETIME);").await.unwrap();
+
+        let original = chrono::Utc.with_ymd_and_hms(2024, 3, 15, 13, 45, 30).unwrap();
+        let insert = Insert::single_into("test").value("dt", Value::datetime(original));
+        conn.insert(insert.into()).await.unwrap();
+
+        let storage = conn
+            .query_raw("SELECT typeof(dt) AS ty FROM test", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+        assert_eq!(storage.get("ty").unwrap(), &Value::text("real"));
+
+        let select = Select::from_table("test").value(asterisk());
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+
+        assert_eq!(result.get("dt").unwrap().as_datetime().unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn julian_day_dates_binds_a_date_as_a_real_and_reads_it_back() {
+        let mut conn = Sqlite::new_in_memory().unwrap();
+        conn.set_julian_day_dates(true);
+
+        conn.raw_cmd("CREATE TABLE test (d DATE);").await.unwrap();
+
+        let original = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let insert = Insert::single_into("test").value("d", Value::date(original));
+        conn.insert(insert.into()).await.unwrap();
+
+        let storage = conn
+            .query_raw("SELECT typeof(d) AS ty FROM test", &[])
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+        assert_eq!(storage.get("ty").unwrap(), &Value::text("real"));
+
+        let select = Select::from_table("test").value(asterisk());
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+
+        assert_eq!(result.get("d").unwrap().as_date().unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn a_julian_day_real_written_by_another_tool_is_read_back_without_enabling_the_setting() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (dt DATETIME);").await.unwrap();
+        // 2440588.5 is the Julian day for 1970-01-02T00:00:00Z: one day after the Unix epoch.
+        conn.raw_cmd("INSERT INTO test (dt) VALUES (2440588.5);")
+            .await
+            .unwrap();
+
+        let select = Select::from_table("test").value(asterisk());
+        let result = conn.select(select).await.unwrap().into_single().unwrap();
+
+        let expected = chrono::Utc.with_ymd_and_hms(1970, 1, 2, 0, 0, 0).unwrap();
+        assert_eq!(result.get("dt").unwrap().as_datetime().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn query_raw_map_binds_named_placeholders_with_or_without_the_leading_sigil() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY, txt TEXT);")
+            .await
+            .unwrap();
+        conn.query_raw("INSERT INTO test (id, txt) VALUES (1, 'henlo')", &[])
+            .await
+            .unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("id".to_owned(), Value::int32(1));
+
+        let result = conn
+            .query_raw_map("SELECT txt FROM test WHERE id = :id", &params)
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("txt").unwrap(), &Value::text("henlo"));
+
+        let mut params_with_sigil = HashMap::new();
+        params_with_sigil.insert(":id".to_owned(), Value::int32(1));
+
+        let result = conn
+            .query_raw_map("SELECT txt FROM test WHERE id = :id", &params_with_sigil)
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("txt").unwrap(), &Value::text("henlo"));
+    }
+
+    #[tokio::test]
+    async fn query_raw_map_errors_on_a_missing_placeholder() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY, txt TEXT);")
+            .await
+            .unwrap();
+
+        let params = HashMap::new();
+
+        let err = conn
+            .query_raw_map("SELECT txt FROM test WHERE id = :id", &params)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::QueryInvalidInput(message) if message.contains(":id")));
+    }
+
+    #[tokio::test]
+    async fn query_raw_map_tolerates_unused_entries_in_the_map() {
+        let conn = Sqlite::new_in_memory().unwrap();
+
+        conn.raw_cmd("CREATE TABLE test (id INTEGER PRIMARY KEY, txt TEXT);")
+            .await
+            .unwrap();
+        conn.query_raw("INSERT INTO test (id, txt) VALUES (1, 'henlo')", &[])
+            .await
+            .unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("id".to_owned(), Value::int32(1));
+        params.insert("unused".to_owned(), Value::text("ignored"));
+
+        let result = conn
+            .query_raw_map("SELECT txt FROM test WHERE id = :id", &params)
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(result.get("txt").unwrap(), &Value::text("henlo"));
+    }
 }
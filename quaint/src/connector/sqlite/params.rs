@@ -15,6 +15,29 @@ pub struct SqliteParams {
     pub socket_timeout: Option<Duration>,
     pub max_connection_lifetime: Option<Duration>,
     pub max_idle_connection_lifetime: Option<Duration>,
+    /// The SQLCipher encryption key, issued as `PRAGMA key` right after opening the connection.
+    /// Wrapped so it never leaks into logs through a `{:?}` of `SqliteParams`.
+    pub key: Option<Hidden<String>>,
+    /// Whether `LIKE` should compare case-sensitively, issued as `PRAGMA case_sensitive_like`
+    /// right after opening the connection. SQLite defaults to a case-insensitive `LIKE` (for
+    /// ASCII characters only), unlike most other databases; `None` leaves that default alone.
+    pub case_sensitive_like: Option<bool>,
+    /// Whether foreign key constraint enforcement should be turned on, issued as `PRAGMA
+    /// foreign_keys` right after opening the connection. Unlike SQLite's own default (off), we
+    /// default this to on; pass `foreign_keys=false` for bulk-load scenarios that want to defer
+    /// enforcement.
+    pub foreign_keys: Option<bool>,
+}
+
+/// Wraps a value so that its `Debug` representation never reveals it, for values (such as the
+/// SQLCipher key) that must not end up in logs or traces.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Hidden<T>(pub T);
+
+impl<T> std::fmt::Debug for Hidden<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<HIDDEN>")
+    }
 }
 
 impl TryFrom<&str> for SqliteParams {
@@ -38,6 +61,9 @@ impl TryFrom<&str> for SqliteParams {
             let mut socket_timeout = None;
             let mut max_connection_lifetime = None;
             let mut max_idle_connection_lifetime = None;
+            let mut key = None;
+            let mut case_sensitive_like = None;
+            let mut foreign_keys = None;
 
             if path_parts.len() > 1 {
                 let params = path_parts.last().unwrap().split('&').map(|kv| {
@@ -83,6 +109,23 @@ impl TryFrom<&str> for SqliteParams {
                                 max_idle_connection_lifetime = Some(Duration::from_secs(as_int));
                             }
                         }
+                        "key" => {
+                            key = Some(Hidden(v.to_owned()));
+                        }
+                        "case_sensitive_like" => {
+                            let as_bool = v
+                                .parse::<bool>()
+                                .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+
+                            case_sensitive_like = Some(as_bool);
+                        }
+                        "foreign_keys" => {
+                            let as_bool = v
+                                .parse::<bool>()
+                                .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+
+                            foreign_keys = Some(as_bool);
+                        }
                         _ => {
                             tracing::trace!(message = "Discarding connection string param", param = k);
                         }
@@ -97,6 +140,9 @@ impl TryFrom<&str> for SqliteParams {
                 socket_timeout,
                 max_connection_lifetime,
                 max_idle_connection_lifetime,
+                key,
+                case_sensitive_like,
+                foreign_keys,
             })
         }
     }
@@ -126,4 +172,46 @@ mod tests {
         let params = SqliteParams::try_from(path).unwrap();
         assert_eq!(params.file_path, "dev.db");
     }
+
+    #[test]
+    fn sqlite_params_from_str_defaults_case_sensitive_like_to_none() {
+        let params = SqliteParams::try_from("file:dev.db").unwrap();
+        assert_eq!(params.case_sensitive_like, None);
+    }
+
+    #[test]
+    fn sqlite_params_from_str_parses_case_sensitive_like() {
+        let params = SqliteParams::try_from("file:dev.db?case_sensitive_like=true").unwrap();
+        assert_eq!(params.case_sensitive_like, Some(true));
+
+        let params = SqliteParams::try_from("file:dev.db?case_sensitive_like=false").unwrap();
+        assert_eq!(params.case_sensitive_like, Some(false));
+    }
+
+    #[test]
+    fn sqlite_params_from_str_rejects_an_invalid_case_sensitive_like_value() {
+        let err = SqliteParams::try_from("file:dev.db?case_sensitive_like=nope").unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidConnectionArguments));
+    }
+
+    #[test]
+    fn sqlite_params_from_str_defaults_foreign_keys_to_none() {
+        let params = SqliteParams::try_from("file:dev.db").unwrap();
+        assert_eq!(params.foreign_keys, None);
+    }
+
+    #[test]
+    fn sqlite_params_from_str_parses_foreign_keys() {
+        let params = SqliteParams::try_from("file:dev.db?foreign_keys=false").unwrap();
+        assert_eq!(params.foreign_keys, Some(false));
+
+        let params = SqliteParams::try_from("file:dev.db?foreign_keys=true").unwrap();
+        assert_eq!(params.foreign_keys, Some(true));
+    }
+
+    #[test]
+    fn sqlite_params_from_str_rejects_an_invalid_foreign_keys_value() {
+        let err = SqliteParams::try_from("file:dev.db?foreign_keys=nope").unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidConnectionArguments));
+    }
 }
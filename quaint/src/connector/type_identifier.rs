@@ -11,7 +11,7 @@ pub(crate) trait TypeIdentifier {
     fn is_text(&self) -> bool;
     fn is_bytes(&self) -> bool;
     fn is_bool(&self) -> bool;
-    #[cfg(feature = "mysql")]
+    #[cfg(any(feature = "mysql", feature = "sqlite"))]
     fn is_json(&self) -> bool;
     #[cfg(feature = "mysql")]
     fn is_enum(&self) -> bool;
@@ -0,0 +1,101 @@
+//! A small helper for building parameterized DDL statements.
+//!
+//! DDL (`CREATE TABLE`, ...) cannot use bound parameters, so callers that need to generate it
+//! dynamically — mostly test setup and tooling — are tempted to hand-concatenate identifiers into
+//! raw SQL strings. This module quotes identifiers the way each [`SqlFamily`] expects, so that at
+//! least the identifiers aren't built ad hoc by every caller.
+
+use crate::connector::SqlFamily;
+
+/// A column definition for [`create_table`]: a name and a raw SQL type/constraint fragment.
+///
+/// The type fragment (e.g. `"INTEGER PRIMARY KEY"`) is written verbatim, since SQL types and
+/// constraints are not identifiers and differ too much between databases to validate generically
+/// here.
+pub struct ColumnDef<'a> {
+    pub name: &'a str,
+    pub sql_type: &'a str,
+}
+
+impl<'a> ColumnDef<'a> {
+    pub fn new(name: &'a str, sql_type: &'a str) -> Self {
+        Self { name, sql_type }
+    }
+}
+
+/// Render a `CREATE TABLE` statement for `family`, quoting the table name and every column name.
+pub fn create_table(family: SqlFamily, name: &str, columns: &[ColumnDef<'_>]) -> String {
+    let mut stmt = format!("CREATE TABLE {} (", quote_identifier(family, name));
+
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            stmt.push_str(", ");
+        }
+
+        stmt.push_str(&quote_identifier(family, column.name));
+        stmt.push(' ');
+        stmt.push_str(column.sql_type);
+    }
+
+    stmt.push(')');
+    stmt
+}
+
+/// Quote `name` as an identifier for `family`, escaping any embedded quote character by doubling
+/// it, as each dialect expects.
+pub fn quote_identifier(family: SqlFamily, name: &str) -> String {
+    let (open, close) = match family {
+        #[cfg(feature = "postgresql")]
+        SqlFamily::Postgres => ('"', '"'),
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => ('`', '`'),
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => ('"', '"'),
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => ('[', ']'),
+    };
+
+    let mut escaped = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        if c == close {
+            escaped.push(close);
+        }
+        escaped.push(c);
+    }
+
+    format!("{open}{escaped}{close}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn create_table_quotes_a_reserved_word_column_name() {
+        let columns = [ColumnDef::new("id", "INTEGER PRIMARY KEY"), ColumnDef::new("select", "TEXT")];
+
+        let sql = create_table(SqlFamily::Sqlite, "users", &columns);
+
+        assert_eq!(sql, r#"CREATE TABLE "users" ("id" INTEGER PRIMARY KEY, "select" TEXT)"#);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn quote_identifier_escapes_embedded_double_quotes() {
+        assert_eq!(quote_identifier(SqlFamily::Sqlite, r#"wei"rd"#), r#""wei""rd""#);
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn quote_identifier_escapes_embedded_backticks() {
+        assert_eq!(quote_identifier(SqlFamily::Mysql, "wei`rd"), "`wei``rd`");
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn quote_identifier_escapes_embedded_square_brackets() {
+        assert_eq!(quote_identifier(SqlFamily::Mssql, "wei]rd"), "[wei]]rd]");
+    }
+}
@@ -241,6 +241,9 @@ pub enum ErrorKind {
 
     #[error("External error id#{}", _0)]
     ExternalError(i32),
+
+    #[error("Authentication failed: the provided SQLCipher key is incorrect, or the database is not encrypted")]
+    IncorrectDatabaseEncryptionKey,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -118,6 +118,7 @@ pub extern crate chrono;
 
 pub mod ast;
 pub mod connector;
+pub mod ddl;
 pub mod error;
 #[cfg(feature = "pooled")]
 pub mod pooled;
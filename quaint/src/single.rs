@@ -57,6 +57,8 @@ impl Quaint {
     /// - `socket_timeout` defined in seconds. Acts as the busy timeout in
     ///   SQLite. When set, queries that are waiting for a lock to be released
     ///   will return the `Timeout` error after the defined value.
+    /// - `key` the SQLCipher encryption key (only meaningful with the `sqlcipher`
+    ///   Cargo feature). Issued as `PRAGMA key` right after opening the connection.
     ///
     /// PostgreSQL:
     ///
@@ -134,8 +136,10 @@ impl Quaint {
         let inner = match url_str {
             #[cfg(feature = "sqlite-native")]
             s if s.starts_with("file") => {
-                let params = connector::SqliteParams::try_from(s)?;
-                let sqlite = connector::Sqlite::new(&params.file_path)?;
+                // Pass the full connection string, not just `params.file_path`: `Sqlite::new`
+                // re-parses it into `SqliteParams` itself, and that's the only place query
+                // string params like `key` (the SQLCipher encryption key) get applied.
+                let sqlite = connector::Sqlite::new(s)?;
 
                 Arc::new(sqlite) as Arc<dyn Queryable>
             }
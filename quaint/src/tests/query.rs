@@ -694,6 +694,78 @@ async fn returning_insert(api: &mut dyn TestApi) -> crate::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "postgresql")]
+#[test_each_connector(tags("postgresql"))]
+async fn query_batch_collects_returning_rows_per_statement(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.get_name();
+
+    api.conn()
+        .raw_cmd(&format!("CREATE TABLE {table} (id int primary key, name varchar(255))"))
+        .await?;
+
+    let insert_sql = format!("INSERT INTO {table} (id, name) VALUES ($1, $2) RETURNING id, name");
+    let update_sql = format!("UPDATE {table} SET name = $1 WHERE id = $2");
+
+    let res = api
+        .conn()
+        .query_batch(&[
+            (insert_sql.as_str(), &[Value::from(1), Value::from("Musti")]),
+            (insert_sql.as_str(), &[Value::from(2), Value::from("Naukio")]),
+            (update_sql.as_str(), &[Value::from("Belinda"), Value::from(1)]),
+        ])
+        .await;
+
+    api.conn().raw_cmd(&format!("DROP TABLE {table}")).await?;
+
+    let res = res?;
+
+    assert_eq!(3, res.len());
+
+    assert_eq!(1, res[0].len());
+    assert_eq!(Some("Musti"), res[0].get(0).unwrap()["name"].as_str());
+
+    assert_eq!(1, res[1].len());
+    assert_eq!(Some("Naukio"), res[1].get(0).unwrap()["name"].as_str());
+
+    assert_eq!(0, res[2].len(), "a non-RETURNING statement gets an empty result set");
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+#[test_each_connector(tags("sqlite"))]
+async fn returning_multiple_generated_columns(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.get_name();
+
+    // `id` is a rowid alias (an `INTEGER PRIMARY KEY` column), and `created_at`/`token` are
+    // populated from their defaults, so all three columns are server-generated.
+    api.conn()
+        .raw_cmd(&format!(
+            "CREATE TABLE {table} (id INTEGER PRIMARY KEY, created_at DATETIME NOT NULL DEFAULT '2020-01-01 00:00:00', token TEXT NOT NULL DEFAULT 'generated-token')"
+        ))
+        .await?;
+
+    let insert = Insert::single_into(&table);
+
+    let res = api
+        .conn()
+        .insert(Insert::from(insert).returning(vec!["id", "created_at", "token"]))
+        .await;
+
+    api.conn().raw_cmd(&format!("DROP TABLE {table}")).await?;
+
+    let res = res?;
+
+    assert_eq!(1, res.len());
+
+    let row = res.get(0).unwrap();
+    assert_eq!(Some(1), row["id"].as_i32());
+    assert!(row["created_at"].as_datetime().is_some());
+    assert_eq!(Some("generated-token"), row["token"].as_str());
+
+    Ok(())
+}
+
 #[cfg(any(feature = "postgresql", feature = "sqlite"))]
 #[test_each_connector(tags("postgresql", "sqlite"))]
 async fn returning_update(api: &mut dyn TestApi) -> crate::Result<()> {
@@ -1367,6 +1439,60 @@ async fn float_columns_cast_to_f32(api: &mut dyn TestApi) -> crate::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "sqlite")]
+#[test_each_connector(tags("sqlite"))]
+async fn f32_and_f64_round_trip_through_a_sqlite_real_column(api: &mut dyn TestApi) -> crate::Result<()> {
+    // SQLite stores every REAL as an 8-byte double, so a column declared REAL reads
+    // back as `Value::double` regardless of which variant we bound it with: the f32 -> f64 cast
+    // on bind (see the `ToSql` impl for `ValueType::Float`) is lossless for values that actually
+    // fit in an f32, so no precision is introduced or lost by going through `f64` on the wire.
+    let table = api.create_temp_table("id integer primary key, f real").await?;
+
+    let insert = Insert::single_into(&table).value("f", Value::float(6.412345));
+    api.conn().insert(insert.into()).await?;
+
+    let insert = Insert::single_into(&table).value("f", Value::double(6.412345_f64));
+    api.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(&table).column("f").order_by("id".ascend());
+    let result = api.conn().query(select.into()).await?;
+
+    for row in result.into_iter() {
+        assert_eq!(Some(6.412345), row[0].as_f64());
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+#[test_each_connector(tags("sqlite"))]
+async fn sqlite_stores_bound_nan_as_null_but_keeps_infinity(api: &mut dyn TestApi) -> crate::Result<()> {
+    // `sqlite3_bind_double` converts NaN to SQL NULL internally (NaN can't be represented in
+    // SQLite's on-disk REAL encoding), so a bound NaN comes back as NULL, not as NaN. Infinity
+    // and -Infinity are ordinary finite bit patterns as far as IEEE 754 storage is concerned, so
+    // they round-trip unchanged. This is a property of bound parameters specifically: literal
+    // SQL text renders these as the quoted strings `'NaN'`/`'Infinity'`/`'-Infinity'` instead,
+    // see `Sqlite::visit_raw_value`.
+    let table = api.create_temp_table("id integer primary key, f real").await?;
+
+    let insert = Insert::multi_into(&table, ["f"])
+        .values(vec![Value::double(f64::NAN)])
+        .values(vec![Value::double(f64::INFINITY)])
+        .values(vec![Value::double(f64::NEG_INFINITY)]);
+
+    api.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(&table).column("f").order_by("id".ascend());
+    let result = api.conn().query(select.into()).await?;
+    let mut rows = result.into_iter();
+
+    assert_eq!(None, rows.next().unwrap()[0].as_f64());
+    assert_eq!(Some(f64::INFINITY), rows.next().unwrap()[0].as_f64());
+    assert_eq!(Some(f64::NEG_INFINITY), rows.next().unwrap()[0].as_f64());
+
+    Ok(())
+}
+
 // TODO: Figure out why it doesn't work on MySQL8
 //panicked at 'assertion failed: `(left == right)`
 // left: `Numeric(Some(BigDecimal("1.0")))`,
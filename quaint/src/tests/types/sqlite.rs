@@ -88,6 +88,14 @@ test_type!(datetime(
     Value::datetime(chrono::DateTime::from_str("2020-07-29T09:23:44.458Z").unwrap())
 ));
 
+test_type!(json(
+    sqlite,
+    "JSON",
+    Value::null_json(),
+    Value::json(serde_json::json!({ "name": "Musti", "age": 9 })),
+    Value::json(serde_json::json!(["Musti", "Naukio", 3]))
+));
+
 #[quaint_test_macros::test_each_connector(tags("sqlite"))]
 async fn test_type_text_datetime_rfc3339(api: &mut dyn TestApi) -> crate::Result<()> {
     let table = api.create_type_table("DATETIME").await?;
@@ -152,6 +160,108 @@ async fn test_type_text_datetime_custom(api: &mut dyn TestApi) -> crate::Result<
     Ok(())
 }
 
+// SQLite's INTEGER/REAL/NUMERIC affinity converts a well-formed numeric TEXT literal to the
+// matching storage class on insert, leading zeros included (`'0123'` is stored as the integer
+// 123, `'001.5'` as the real 1.5); the following tests check that such values read back as the
+// column's declared type.
+
+#[quaint_test_macros::test_each_connector(tags("sqlite"))]
+async fn test_numeric_text_in_declared_int_column_coerces_to_int32(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_type_table("INT").await?;
+
+    api.conn()
+        .execute_raw(&format!("INSERT INTO {} (value) VALUES ('0123')", &table), &[])
+        .await?;
+
+    let select = Select::from_table(&table).column("value").order_by("id".descend());
+    let res = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(Some(&Value::int32(123)), res.at(0));
+
+    Ok(())
+}
+
+#[quaint_test_macros::test_each_connector(tags("sqlite"))]
+async fn test_numeric_text_in_declared_bigint_column_coerces_to_int64(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_type_table("BIGINT").await?;
+
+    api.conn()
+        .execute_raw(&format!("INSERT INTO {} (value) VALUES ('09223372036854775807')", &table), &[])
+        .await?;
+
+    let select = Select::from_table(&table).column("value").order_by("id".descend());
+    let res = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(Some(&Value::int64(i64::MAX)), res.at(0));
+
+    Ok(())
+}
+
+#[quaint_test_macros::test_each_connector(tags("sqlite"))]
+async fn test_numeric_text_in_declared_real_column_coerces_to_double(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_type_table("REAL").await?;
+
+    api.conn()
+        .execute_raw(&format!("INSERT INTO {} (value) VALUES ('001.5')", &table), &[])
+        .await?;
+
+    let select = Select::from_table(&table).column("value").order_by("id".descend());
+    let res = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(Some(&Value::double(1.5)), res.at(0));
+
+    Ok(())
+}
+
+#[quaint_test_macros::test_each_connector(tags("sqlite"))]
+async fn test_numeric_text_in_declared_decimal_column_coerces_to_numeric(api: &mut dyn TestApi) -> crate::Result<()> {
+    let table = api.create_type_table("DECIMAL").await?;
+
+    api.conn()
+        .execute_raw(&format!("INSERT INTO {} (value) VALUES ('001.5')", &table), &[])
+        .await?;
+
+    let select = Select::from_table(&table).column("value").order_by("id".descend());
+    let res = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(
+        Some(&Value::numeric(bigdecimal::BigDecimal::from_str("1.5").unwrap())),
+        res.at(0)
+    );
+
+    Ok(())
+}
+
+#[quaint_test_macros::test_each_connector(tags("sqlite"))]
+async fn test_numeric_text_in_expression_column_falls_back_to_text(api: &mut dyn TestApi) -> crate::Result<()> {
+    // An expression result has no declared column type to consult, so it must fall back to
+    // value-based coercion: a TEXT value simply stays text.
+    let res = api.conn().query_raw("SELECT '0123' AS value", &[]).await?.into_single()?;
+
+    assert_eq!(Some(&Value::text("0123")), res.at(0));
+
+    Ok(())
+}
+
+#[quaint_test_macros::test_each_connector(tags("sqlite"))]
+async fn test_invalid_json_text_in_declared_json_column_falls_back_to_text(api: &mut dyn TestApi) -> crate::Result<()> {
+    // SQLite has no native JSON storage class, so nothing stops a column declared JSON from
+    // holding text that isn't valid JSON (e.g. written by a raw statement that bypassed this
+    // binding). Reading it back must not fail the whole query; it falls back to plain text.
+    let table = api.create_type_table("JSON").await?;
+
+    api.conn()
+        .execute_raw(&format!("INSERT INTO {} (value) VALUES ('not json')", &table), &[])
+        .await?;
+
+    let select = Select::from_table(&table).column("value").order_by("id".descend());
+    let res = api.conn().select(select).await?.into_single()?;
+
+    assert_eq!(Some(&Value::text("not json")), res.at(0));
+
+    Ok(())
+}
+
 #[quaint_test_macros::test_each_connector(tags("sqlite"))]
 async fn test_get_int64_from_int32_field_fails(api: &mut dyn TestApi) -> crate::Result<()> {
     let table = api.create_type_table("INT").await?;
@@ -534,6 +534,13 @@ pub trait Visitor<'a> {
             }
         }
 
+        if !ua.ordering.is_empty() {
+            self.write(" ORDER BY ")?;
+            self.visit_ordering(ua.ordering)?;
+        }
+
+        self.visit_limit_and_offset(ua.limit, ua.offset)?;
+
         Ok(())
     }
 
@@ -783,6 +790,24 @@ pub trait Visitor<'a> {
     /// A comparison expression
     fn visit_compare(&mut self, compare: Compare<'a>) -> Result {
         match compare {
+            // `= NULL` and `<> NULL` never match anything, even other nulls, so rewrite them to
+            // the `IS [NOT] NULL` forms that mean what the caller actually wants.
+            Compare::Equals(left, right) if right.is_null_value() => {
+                self.visit_expression(*left)?;
+                self.write(" IS NULL")
+            }
+            Compare::Equals(left, right) if left.is_null_value() => {
+                self.visit_expression(*right)?;
+                self.write(" IS NULL")
+            }
+            Compare::NotEquals(left, right) if right.is_null_value() => {
+                self.visit_expression(*left)?;
+                self.write(" IS NOT NULL")
+            }
+            Compare::NotEquals(left, right) if left.is_null_value() => {
+                self.visit_expression(*right)?;
+                self.write(" IS NOT NULL")
+            }
             Compare::Equals(left, right) => self.visit_equals(*left, *right),
             Compare::NotEquals(left, right) => self.visit_not_equals(*left, *right),
             Compare::LessThan(left, right) => self.visit_less_than(*left, *right),
@@ -210,6 +210,28 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
                     }
                 }
             }
+            Expression {
+                kind: ExpressionKind::Selection(selection),
+                ..
+            } => {
+                let columns = insert.columns.len();
+
+                self.write(" (")?;
+                for (i, c) in insert.columns.into_iter().enumerate() {
+                    self.visit_column(c.name.into_owned().into())?;
+
+                    if i < (columns - 1) {
+                        self.write(", ")?;
+                    }
+                }
+                self.write(")")?;
+
+                // The select can return a different number of columns than the target list above:
+                // we don't validate that here, same as we don't validate `VALUES` row arity against
+                // it. SQLite will raise its own error at execution time if they don't match.
+                self.write(" ")?;
+                self.visit_sub_selection(selection)?;
+            }
             expr => self.visit_expression(expr)?,
         }
 
@@ -626,6 +648,45 @@ mod tests {
         assert_eq!(default_params(expected.1), params);
     }
 
+    #[test]
+    fn test_select_where_between() {
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `age` BETWEEN ? AND ?", vec![420, 666]);
+
+        let query = Select::from_table("naukio").so_that("age".between(420, 666));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
+    #[test]
+    fn test_select_where_not_between() {
+        let expected = expected_values(
+            "SELECT `naukio`.* FROM `naukio` WHERE `age` NOT BETWEEN ? AND ?",
+            vec![420, 666],
+        );
+
+        let query = Select::from_table("naukio").so_that("age".not_between(420, 666));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
+    #[test]
+    fn test_select_where_between_with_string_bounds() {
+        let expected = expected_values(
+            "SELECT `naukio`.* FROM `naukio` WHERE `word` BETWEEN ? AND ?",
+            vec!["cat", "dog"],
+        );
+
+        let query = Select::from_table("naukio").so_that("word".between("cat", "dog"));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(default_params(expected.1), params);
+    }
+
     #[test]
     fn test_select_where_begins_with() {
         let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ?", vec!["%meow"]);
@@ -1009,6 +1070,16 @@ mod tests {
         assert_eq!("INSERT INTO `foo` (`foo`, `baz`) VALUES (?,DEFAULT)", sql);
     }
 
+    #[test]
+    fn test_insert_select() {
+        let select = Select::from_table("bar").column("a").column("b");
+        let insert = Insert::expression_into("foo", vec!["a", "b"], select);
+
+        let (sql, _) = Sqlite::build(insert).unwrap();
+
+        assert_eq!("INSERT INTO `foo` (`a`, `b`) SELECT `bar`.`a`, `bar`.`b` FROM `bar`", sql);
+    }
+
     #[test]
     fn join_is_inserted_positionally() {
         let joined_table = Table::from("User").left_join(
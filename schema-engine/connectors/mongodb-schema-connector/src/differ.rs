@@ -1,6 +1,9 @@
 use crate::migration::{MongoDbMigration, MongoDbMigrationStep};
-use mongodb_schema_describer::{CollectionId, IndexField, IndexId, IndexWalker, MongoSchema};
-use std::collections::BTreeMap;
+use mongodb_schema_describer::{CollectionId, CollectionWalker, IndexField, IndexId, IndexWalker, MongoSchema};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+};
 
 pub(crate) fn diff(previous: Box<MongoSchema>, next: Box<MongoSchema>) -> MongoDbMigration {
     let mut steps = Vec::new();
@@ -27,6 +30,36 @@ pub(crate) fn diff(previous: Box<MongoSchema>, next: Box<MongoSchema>) -> MongoD
     MongoDbMigration { previous, next, steps }
 }
 
+/// A stable, order-independent hash of `schema`. Collections and indexes are hashed sorted by
+/// name, so two schemas that only differ in describer iteration order hash equally.
+pub(crate) fn schema_hash(schema: &MongoSchema) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    let mut collections: Vec<CollectionWalker<'_>> = schema.walk_collections().collect();
+    collections.sort_by_key(|collection| collection.name());
+
+    for collection in collections {
+        collection.name().hash(&mut hasher);
+        collection.has_schema().hash(&mut hasher);
+        collection.is_capped().hash(&mut hasher);
+
+        let mut indexes: Vec<IndexWalker<'_>> = collection.indexes().collect();
+        indexes.sort_by_key(|index| index.name());
+
+        for index in indexes {
+            index.name().hash(&mut hasher);
+            index.is_unique().hash(&mut hasher);
+            index.is_fulltext().hash(&mut hasher);
+
+            for field in index.fields() {
+                field.to_string().hash(&mut hasher);
+            }
+        }
+    }
+
+    hasher.finish()
+}
+
 struct DifferDatabase<'a> {
     collections: BTreeMap<&'a str, (Option<CollectionId>, Option<CollectionId>)>,
     #[allow(clippy::type_complexity)] // respectfully disagree
@@ -130,6 +130,10 @@ impl SchemaConnector for MongoDbSchemaConnector {
         Migration::new(differ::diff(from, to))
     }
 
+    fn schema_hash(&self, schema: &DatabaseSchema) -> u64 {
+        differ::schema_hash(schema.downcast_ref())
+    }
+
     fn drop_database(&mut self) -> BoxFuture<'_, ConnectorResult<()>> {
         Box::pin(async { self.client().await?.drop_database().await })
     }
@@ -13,4 +13,11 @@ impl Migration {
     pub fn downcast_ref<T: 'static>(&self) -> &T {
         self.0.downcast_ref().unwrap()
     }
+
+    /// Should never be used in the core, only in connectors that know what they put there. Takes
+    /// ownership of the underlying migration, for callers that need to consume its fields (e.g.
+    /// to build a new migration out of them) instead of just reading them.
+    pub fn downcast<T: 'static>(self) -> Box<T> {
+        self.0.downcast().unwrap()
+    }
 }
@@ -52,6 +52,11 @@ pub trait SchemaConnector: Send + Sync + 'static {
     /// Create a migration by comparing two database schemas.
     fn diff(&self, from: DatabaseSchema, to: DatabaseSchema) -> Migration;
 
+    /// A stable, order-independent hash of a database schema, following the same equivalence
+    /// rules as `diff`: two schemas that would produce an empty diff hash equally. Callers can
+    /// cache this value to detect when a schema has changed without running a full diff.
+    fn schema_hash(&self, schema: &DatabaseSchema) -> u64;
+
     /// Drop the database referenced by Prisma schema that was used to initialize the connector.
     fn drop_database(&mut self) -> BoxFuture<'_, ConnectorResult<()>>;
 
@@ -79,6 +84,20 @@ pub trait SchemaConnector: Send + Sync + 'static {
         diagnostics: &DestructiveChangeDiagnostics,
     ) -> ConnectorResult<String>;
 
+    /// Like [`Self::render_script`], but lets the caller control whether each step is preceded
+    /// by a `-- <description>` comment identifying the kind of step it came from (e.g. `--
+    /// AlterTable`). Connectors that don't support scripting at all, or that don't distinguish
+    /// the two, can ignore `emit_comments` and defer to `render_script`.
+    fn render_script_with_options(
+        &self,
+        migration: &Migration,
+        diagnostics: &DestructiveChangeDiagnostics,
+        emit_comments: bool,
+    ) -> ConnectorResult<String> {
+        let _ = emit_comments;
+        self.render_script(migration, diagnostics)
+    }
+
     /// Drop all database state.
     ///
     /// Set the `soft` parameter to `true` to force a soft-reset, that is to say a reset that does
@@ -5,25 +5,94 @@ use crate::{
 };
 use schema_connector::{ConnectorResult, DestructiveChangeDiagnostics, Migration};
 use sql_schema_describer::SqlSchema;
+use std::time::{Duration, Instant};
 use tracing_futures::Instrument;
 
+/// A record of one statement applied to the database while applying a migration, kept for tooling
+/// (e.g. an audit log of what a migration actually did) rather than for the engine itself, which
+/// doesn't look at it again once the migration is done. See
+/// [`crate::SqlSchemaConnector::apply_migration_with_changelog`].
+///
+/// A step that renders to more than one statement (e.g. a table redefinition) produces one
+/// `AppliedStep` per statement, all sharing the same `step_index`, rather than a single
+/// aggregated entry: that keeps each entry's `sql` and timing accurate to the one statement it
+/// describes.
+#[derive(Debug, Clone)]
+pub struct AppliedStep {
+    /// The index of the originating step in [`crate::sql_migration::SqlMigration::steps`], the
+    /// same indexing [`crate::apply_migration::RenderedStep::step_index`] uses.
+    pub step_index: usize,
+    /// The statement that was applied.
+    pub sql: String,
+    /// How long the statement took to apply.
+    pub duration: Duration,
+    /// How many rows the statement affected, when the connector can report it. `raw_cmd` doesn't
+    /// surface a row count on any flavour today, so this is always `None` for now; it's kept as
+    /// `Option` so a future flavour that can report it doesn't need a breaking change here.
+    pub rows_affected: Option<u64>,
+}
+
 #[tracing::instrument(skip(flavour, migration))]
 pub(crate) async fn apply_migration(
     migration: &Migration,
     flavour: &mut (dyn SqlFlavour + Send + Sync),
 ) -> ConnectorResult<u32> {
+    let (steps_applied, _changelog) = apply_migration_with_changelog(migration, flavour).await?;
+    Ok(steps_applied)
+}
+
+/// Like [`apply_migration`], but also returns a changelog of every statement that was applied,
+/// for callers that need to audit what a migration did.
+pub(crate) async fn apply_migration_with_changelog(
+    migration: &Migration,
+    flavour: &mut (dyn SqlFlavour + Send + Sync),
+) -> ConnectorResult<(u32, Vec<AppliedStep>)> {
     let migration: &SqlMigration = migration.downcast_ref();
     tracing::debug!("{} steps to execute", migration.steps.len());
 
-    for step in &migration.steps {
-        for sql_string in render_raw_sql(step, flavour, MigrationPair::new(&migration.before, &migration.after)) {
+    let chunk_size = flavour.migration_statement_chunk_size();
+    let mut statements_since_commit = 0;
+    let mut changelog = Vec::new();
+
+    if chunk_size.is_some() {
+        flavour.raw_cmd("BEGIN").await?;
+    }
+
+    for (step_index, step) in migration.steps.iter().enumerate() {
+        let statements = render_raw_sql(step, flavour, MigrationPair::new(&migration.before, &migration.after));
+
+        for sql_string in &statements {
             assert!(!sql_string.is_empty());
             let span = tracing::info_span!("migration_step", ?step);
-            flavour.raw_cmd(&sql_string).instrument(span).await?;
+            let started_at = Instant::now();
+            flavour.raw_cmd(sql_string).instrument(span).await?;
+
+            changelog.push(AppliedStep {
+                step_index,
+                sql: sql_string.clone(),
+                duration: started_at.elapsed(),
+                rows_affected: None,
+            });
+        }
+
+        // A step's own statements (e.g. a table redefinition) are never split across a commit:
+        // the chunk boundary is only ever checked once the whole step has been applied.
+        if let Some(chunk_size) = chunk_size {
+            statements_since_commit += statements.len();
+
+            if statements_since_commit >= chunk_size {
+                flavour.raw_cmd("COMMIT").await?;
+                flavour.raw_cmd("BEGIN").await?;
+                statements_since_commit = 0;
+            }
         }
     }
 
-    Ok(migration.steps.len() as u32)
+    if chunk_size.is_some() {
+        flavour.raw_cmd("COMMIT").await?;
+    }
+
+    Ok((migration.steps.len() as u32, changelog))
 }
 
 #[tracing::instrument(skip(migration, flavour))]
@@ -31,6 +100,18 @@ pub(crate) fn render_script(
     migration: &Migration,
     diagnostics: &DestructiveChangeDiagnostics,
     flavour: &(dyn SqlFlavour + Send + Sync),
+) -> ConnectorResult<String> {
+    render_script_with_options(migration, diagnostics, flavour, true)
+}
+
+/// Like [`render_script`], but lets the caller omit the `-- <description>` comment that would
+/// otherwise precede each step. Useful for production scripts that want to stay terse.
+#[tracing::instrument(skip(migration, flavour))]
+pub(crate) fn render_script_with_options(
+    migration: &Migration,
+    diagnostics: &DestructiveChangeDiagnostics,
+    flavour: &(dyn SqlFlavour + Send + Sync),
+    emit_comments: bool,
 ) -> ConnectorResult<String> {
     let migration: &SqlMigration = migration.downcast_ref();
     if migration.steps.is_empty() {
@@ -84,9 +165,11 @@ pub(crate) fn render_script(
             // because we do not want two newlines at the end of the file:
             // many editors will remove trailing newlines, and automatically
             // edit the migration.
-            script.push_str("-- ");
-            script.push_str(step.description());
-            script.push('\n');
+            if emit_comments {
+                script.push_str("-- ");
+                script.push_str(step.description());
+                script.push('\n');
+            }
 
             for statement in statements {
                 script.push_str(&statement);
@@ -103,6 +186,100 @@ pub(crate) fn render_script(
     Ok(script)
 }
 
+/// One migration step rendered to its constituent SQL statements, for callers (e.g. an external
+/// migration orchestrator) that need to apply statements with the transaction semantics the step
+/// actually requires, instead of parsing a flat SQL script back apart. See [`render_steps`].
+#[derive(Debug, PartialEq)]
+pub struct RenderedStep {
+    /// The index of the originating step in [`SqlMigration::steps`].
+    pub step_index: usize,
+    /// The statements needed to apply this step, in order. A step that renders to more than one
+    /// statement (e.g. a SQLite table redefinition) must have all of them applied together.
+    pub statements: Vec<String>,
+    /// Whether these statements can run inside the migration's transaction, or must run on their
+    /// own outside of it (e.g. `CREATE INDEX CONCURRENTLY` on PostgreSQL). Mirrors
+    /// [`SqlMigrationStep::requires_separate_transaction`].
+    pub runs_in_transaction: bool,
+}
+
+/// Renders every step of `migration` to a [`RenderedStep`], skipping steps that don't render any
+/// statement. Unlike [`render_script`], this keeps each step's statements grouped and exposes the
+/// transaction semantics the caller needs to apply them correctly.
+#[tracing::instrument(skip(migration, flavour))]
+pub fn render_steps(migration: &Migration, flavour: &(dyn SqlFlavour + Send + Sync)) -> Vec<RenderedStep> {
+    let migration: &SqlMigration = migration.downcast_ref();
+    let schemas = MigrationPair::new(&migration.before, &migration.after);
+
+    migration
+        .steps
+        .iter()
+        .enumerate()
+        .filter_map(|(step_index, step)| {
+            let statements = render_raw_sql(step, flavour, schemas);
+
+            if statements.is_empty() {
+                return None;
+            }
+
+            Some(RenderedStep {
+                step_index,
+                statements,
+                runs_in_transaction: !step.requires_separate_transaction(),
+            })
+        })
+        .collect()
+}
+
+/// Applies `steps` to the database one at a time as they're produced, instead of collecting them
+/// into a [`crate::sql_migration::SqlMigration`] first. See
+/// [`crate::SqlSchemaConnector::apply_diff_streaming`].
+///
+/// Unlike [`apply_migration_with_changelog`], this never holds the applied steps' rendered SQL or
+/// timings anywhere, since there's no `Migration` for a changelog entry's `step_index` to point
+/// into — a caller that needs an audit trail should use `apply_migration_with_changelog` instead.
+#[tracing::instrument(skip(steps, schemas, flavour))]
+pub(crate) async fn apply_steps_streaming(
+    steps: impl Iterator<Item = SqlMigrationStep>,
+    schemas: MigrationPair<&SqlSchema>,
+    flavour: &mut (dyn SqlFlavour + Send + Sync),
+) -> ConnectorResult<u32> {
+    let chunk_size = flavour.migration_statement_chunk_size();
+    let mut statements_since_commit = 0;
+    let mut steps_applied = 0;
+
+    if chunk_size.is_some() {
+        flavour.raw_cmd("BEGIN").await?;
+    }
+
+    for step in steps {
+        let statements = render_raw_sql(&step, flavour, schemas);
+
+        for sql_string in &statements {
+            assert!(!sql_string.is_empty());
+            let span = tracing::info_span!("migration_step", ?step);
+            flavour.raw_cmd(sql_string).instrument(span).await?;
+        }
+
+        steps_applied += 1;
+
+        if let Some(chunk_size) = chunk_size {
+            statements_since_commit += statements.len();
+
+            if statements_since_commit >= chunk_size {
+                flavour.raw_cmd("COMMIT").await?;
+                flavour.raw_cmd("BEGIN").await?;
+                statements_since_commit = 0;
+            }
+        }
+    }
+
+    if chunk_size.is_some() {
+        flavour.raw_cmd("COMMIT").await?;
+    }
+
+    Ok(steps_applied)
+}
+
 #[tracing::instrument(skip(script, connector))]
 pub(crate) async fn apply_script(
     migration_name: &str,
@@ -117,6 +294,242 @@ pub(crate) async fn apply_script(
     connector.flavour.apply_migration_script(migration_name, script).await
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flavour::SqliteFlavour;
+    use psl::SourceFile;
+    use schema_connector::ConnectorParams;
+    use sql_schema_describer::SqlSchema;
+
+    #[tokio::test]
+    async fn changelog_has_one_entry_per_applied_statement() {
+        let datamodel = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id   Int    @id
+              name String
+            }
+        "#;
+
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+        let mut flavour = SqliteFlavour::default();
+        flavour
+            .set_params(ConnectorParams {
+                connection_string: "file::memory:".to_owned(),
+                preview_features: Default::default(),
+                shadow_database_connection_string: None,
+            })
+            .unwrap();
+
+        let next = crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &flavour);
+        let steps = crate::sql_schema_differ::calculate_initial_steps(&next, &flavour);
+
+        assert_eq!(steps.len(), 1, "creating a single table should be a single step");
+
+        let migration = Migration::new(SqlMigration {
+            before: SqlSchema::default(),
+            after: next.describer_schema,
+            steps,
+        });
+
+        let (steps_applied, changelog) = apply_migration_with_changelog(&migration, &mut flavour).await.unwrap();
+
+        assert_eq!(steps_applied, 1);
+        assert_eq!(changelog.len(), 1, "the single CreateTable step renders to a single statement");
+
+        let applied = &changelog[0];
+        let migration: &SqlMigration = migration.downcast_ref();
+        assert!(matches!(
+            migration.steps[applied.step_index],
+            SqlMigrationStep::CreateTable { .. }
+        ));
+        assert!(applied.sql.contains("CREATE TABLE"));
+        assert_eq!(applied.rows_affected, None);
+    }
+
+    #[tokio::test]
+    async fn apply_steps_streaming_applies_every_step_without_a_migration() {
+        let datamodel = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id   Int    @id
+              name String
+            }
+        "#;
+
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+        let mut flavour = SqliteFlavour::default();
+        flavour
+            .set_params(ConnectorParams {
+                connection_string: "file::memory:".to_owned(),
+                preview_features: Default::default(),
+                shadow_database_connection_string: None,
+            })
+            .unwrap();
+
+        let next = crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &flavour);
+        let steps = crate::sql_schema_differ::calculate_initial_steps(&next, &flavour);
+
+        assert_eq!(steps.len(), 1, "creating a single table should be a single step");
+
+        let before = SqlSchema::default();
+        let schemas = MigrationPair::new(&before, &next.describer_schema);
+        let steps_applied = apply_steps_streaming(steps.into_iter(), schemas, &mut flavour)
+            .await
+            .unwrap();
+
+        assert_eq!(steps_applied, 1);
+    }
+
+    #[test]
+    fn render_steps_marks_a_concurrently_created_index_as_requiring_its_own_transaction() {
+        use crate::{flavour::PostgresFlavour, sql_schema_differ::DifferSettings};
+
+        fn schema(datamodel: &str) -> crate::database_schema::SqlDatabaseSchema {
+            let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+            let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+            crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &PostgresFlavour::default())
+        }
+
+        let previous = schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String
+                }
+            "#,
+        );
+        let next = schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String @unique
+                }
+            "#,
+        );
+
+        let steps = crate::sql_schema_differ::calculate_steps_with_settings(
+            MigrationPair::new(&previous, &next),
+            &PostgresFlavour::default(),
+            DifferSettings {
+                concurrent_index_creation: true,
+                ..Default::default()
+            },
+        );
+
+        let migration = Migration::new(SqlMigration {
+            before: previous.describer_schema,
+            after: next.describer_schema,
+            steps,
+        });
+
+        let rendered = render_steps(&migration, &PostgresFlavour::default());
+
+        assert_eq!(
+            rendered.len(),
+            1,
+            "the added unique constraint should render to a single CreateIndex step"
+        );
+
+        let step = &rendered[0];
+        assert_eq!(step.step_index, 0);
+        assert_eq!(step.statements.len(), 1);
+        assert!(step.statements[0].contains("CONCURRENTLY"));
+        assert!(
+            !step.runs_in_transaction,
+            "a concurrently created index must not run inside the migration's transaction"
+        );
+    }
+
+    #[test]
+    fn render_steps_keeps_a_sqlite_redefines_statements_together_in_one_step() {
+        fn schema(datamodel: &str) -> crate::database_schema::SqlDatabaseSchema {
+            let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+            let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+            crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &SqliteFlavour::default())
+        }
+
+        // Adding a required column without a default forces SQLite to redefine the table, which
+        // renders as several statements (CREATE TABLE, INSERT, DROP, ALTER ... RENAME, ...) that
+        // must all be applied together.
+        let previous = schema(
+            r#"
+                datasource db {
+                  provider = "sqlite"
+                  url      = "file:dev.db"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String
+                }
+            "#,
+        );
+        let next = schema(
+            r#"
+                datasource db {
+                  provider = "sqlite"
+                  url      = "file:dev.db"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String
+                  age  Int
+                }
+            "#,
+        );
+
+        let steps = crate::sql_schema_differ::calculate_steps(MigrationPair::new(&previous, &next), &SqliteFlavour::default());
+
+        let migration = Migration::new(SqlMigration {
+            before: previous.describer_schema,
+            after: next.describer_schema,
+            steps,
+        });
+
+        let rendered = render_steps(&migration, &SqliteFlavour::default());
+
+        assert_eq!(rendered.len(), 1, "the redefine should be a single RenderedStep");
+
+        let step = &rendered[0];
+        assert_eq!(step.step_index, 0);
+        assert!(
+            step.statements.len() > 1,
+            "a redefine renders to several statements that must be applied together"
+        );
+        assert!(
+            step.runs_in_transaction,
+            "unlike a concurrently created index, a SQLite redefine can run inside the migration's transaction"
+        );
+    }
+}
+
 fn render_raw_sql(
     step: &SqlMigrationStep,
     renderer: &(dyn SqlFlavour + Send + Sync),
@@ -133,6 +546,9 @@ fn render_raw_sql(
         SqlMigrationStep::CreateSchema(namespace_id) => {
             vec![renderer.render_create_namespace(schemas.next.walk(*namespace_id))]
         }
+        SqlMigrationStep::DropSchema(namespace_id) => {
+            vec![renderer.render_drop_namespace(schemas.previous.walk(*namespace_id))]
+        }
         SqlMigrationStep::DropEnum(enum_id) => renderer.render_drop_enum(schemas.previous.walk(*enum_id)),
         SqlMigrationStep::CreateTable { table_id } => {
             let table = schemas.next.walk(*table_id);
@@ -145,9 +561,9 @@ fn render_raw_sql(
             renderer.render_drop_table(table.namespace(), table.name())
         }
         SqlMigrationStep::RedefineIndex { index } => renderer.render_drop_and_recreate_index(schemas.walk(*index)),
-        SqlMigrationStep::AddForeignKey { foreign_key_id } => {
+        SqlMigrationStep::AddForeignKey { foreign_key_id, deferred } => {
             let foreign_key = schemas.next.walk(*foreign_key_id);
-            vec![renderer.render_add_foreign_key(foreign_key)]
+            vec![renderer.render_add_foreign_key(foreign_key, *deferred)]
         }
         SqlMigrationStep::DropForeignKey { foreign_key_id } => {
             let foreign_key = schemas.previous.walk(*foreign_key_id);
@@ -158,11 +574,37 @@ fn render_raw_sql(
             table_id: _,
             index_id,
             from_drop_and_recreate: _,
-        } => vec![renderer.render_create_index(schemas.next.walk(*index_id))],
+            concurrently,
+        } => {
+            let index = schemas.next.walk(*index_id);
+
+            vec![if *concurrently {
+                renderer.render_create_index_concurrently(index)
+            } else {
+                renderer.render_create_index(index)
+            }]
+        }
         SqlMigrationStep::DropIndex { index_id } => {
             vec![renderer.render_drop_index(schemas.previous.walk(*index_id))]
         }
         SqlMigrationStep::RenameIndex { index } => renderer.render_rename_index(schemas.walk(*index)),
+        SqlMigrationStep::AlterIndex { index } => {
+            let indexes = schemas.walk(*index);
+            let mut statements = Vec::new();
+
+            if indexes.previous.tablespace() != indexes.next.tablespace() {
+                statements.push(renderer.render_alter_index_tablespace(indexes));
+            }
+
+            if indexes.previous.description() != indexes.next.description() {
+                statements.push(renderer.render_comment_on_index(indexes));
+            }
+
+            statements
+        }
+        SqlMigrationStep::AlterForeignKey { foreign_key_id } => {
+            vec![renderer.render_comment_on_constraint(schemas.walk(*foreign_key_id))]
+        }
         SqlMigrationStep::DropView(drop_view) => {
             let view = schemas.previous.walk(drop_view.view_id);
 
@@ -186,5 +628,39 @@ fn render_raw_sql(
         SqlMigrationStep::DropExtension(drop_extension) => {
             renderer.render_drop_extension(drop_extension, schemas.previous)
         }
+        SqlMigrationStep::CreateDomain(create_domain) => renderer.render_create_domain(create_domain, schemas.next),
+        SqlMigrationStep::AlterDomain(alter_domain) => {
+            renderer.render_alter_domain(alter_domain, MigrationPair::new(schemas.previous, schemas.next))
+        }
+        SqlMigrationStep::DropDomain(drop_domain) => renderer.render_drop_domain(drop_domain, schemas.previous),
+        SqlMigrationStep::CreatePolicy(create_policy) => renderer.render_create_policy(create_policy, schemas.next),
+        SqlMigrationStep::AlterPolicy(alter_policy) => {
+            renderer.render_alter_policy(alter_policy, MigrationPair::new(schemas.previous, schemas.next))
+        }
+        SqlMigrationStep::DropPolicy(drop_policy) => renderer.render_drop_policy(drop_policy, schemas.previous),
+        SqlMigrationStep::EnableRowLevelSecurity { table_id } => {
+            renderer.render_enable_row_level_security(schemas.next.walk(*table_id))
+        }
+        SqlMigrationStep::DisableRowLevelSecurity { table_id } => {
+            renderer.render_disable_row_level_security(schemas.previous.walk(*table_id))
+        }
+        SqlMigrationStep::TruncateTable { table_id, cascade } => {
+            renderer.render_truncate_table(schemas.next.walk(*table_id), *cascade)
+        }
+        SqlMigrationStep::AddTableInheritance {
+            table_id,
+            parent_table_id,
+        } => renderer.render_add_table_inheritance(schemas.next.walk(*table_id), schemas.next.walk(*parent_table_id)),
+        SqlMigrationStep::DropTableInheritance {
+            table_id,
+            parent_table_id,
+        } => renderer.render_drop_table_inheritance(
+            schemas.previous.walk(*table_id),
+            schemas.previous.walk(*parent_table_id),
+        ),
+        SqlMigrationStep::CreateTrigger(create_trigger) => {
+            renderer.render_create_trigger(create_trigger, schemas.next)
+        }
+        SqlMigrationStep::DropTrigger(drop_trigger) => renderer.render_drop_trigger(drop_trigger, schemas.previous),
     }
 }
@@ -116,6 +116,28 @@ pub(crate) trait SqlFlavour:
         script: &'a str,
     ) -> BoxFuture<'a, ConnectorResult<()>>;
 
+    /// The maximum number of migration statements to apply in a single transaction before
+    /// committing it and opening a new one, when applying a migration step by step (as opposed
+    /// to [`Self::apply_migration_script`], which applies an already-rendered script as-is).
+    /// `None` (the default) applies the whole migration without an explicit chunk boundary.
+    ///
+    /// This exists for flavours where holding one very large transaction open for an entire
+    /// migration is undesirable, e.g. SQLite holding a writer lock or growing its rollback
+    /// journal for the whole migration's duration. A single migration step's statements are
+    /// never split across a chunk boundary, since some steps (e.g. a table redefinition) only
+    /// work correctly when applied together.
+    fn migration_statement_chunk_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// A raw SQL expression to substitute for existing `NULL` values when a column is made
+    /// required and has no default, so the data copy in a table redefinition does not fail on
+    /// rows that still hold `NULL` for that column. `None` (the default) performs no
+    /// substitution, leaving the redefinition to fail against such rows.
+    fn not_null_coalesce_sentinel(&self) -> Option<&str> {
+        None
+    }
+
     fn check_database_version_compatibility(
         &self,
         _datamodel: &ValidatedSchema,
@@ -128,6 +150,33 @@ pub(crate) trait SqlFlavour:
         Ok(())
     }
 
+    /// Whether `name` is a word reserved by this database, case-insensitively. This is advisory:
+    /// every identifier we render is already quoted, so a reserved word works fine as a table or
+    /// column name in the SQL we generate. It exists so callers that want to catch this ahead of
+    /// time (e.g. because some other part of their stack writes unquoted SQL against the same
+    /// database) can opt into [`crate::SqlSchemaConnector::check_reserved_identifiers`].
+    fn is_reserved(&self, _name: &str) -> bool {
+        false
+    }
+
+    /// The maximum length, in bytes, of a table, column, index or constraint name on this
+    /// database. A name we generate (e.g. for an index or a foreign key) that goes over this is
+    /// silently truncated by the database, which risks colliding with another generated name
+    /// truncated to the same prefix — see
+    /// [`crate::identifier_length::check_identifier_lengths`].
+    fn max_identifier_length(&self) -> usize {
+        63 // the Postgres default; most other flavours override this.
+    }
+
+    /// Whether this database supports more than one schema (namespace) per database, with
+    /// `CREATE SCHEMA`/`DROP SCHEMA` DDL. Gates whether the differ emits schema create/drop steps
+    /// at all — connectors without multi-schema support never see more than one namespace
+    /// described in the first place, so this mostly matters for documentation and for callers
+    /// probing capabilities ahead of time.
+    fn supports_multi_schema(&self) -> bool {
+        false
+    }
+
     /// The connection string received in set_params().
     fn connection_string(&self) -> Option<&str>;
 
@@ -102,6 +102,32 @@ impl SqlFlavour for MssqlFlavour {
         "mssql"
     }
 
+    fn max_identifier_length(&self) -> usize {
+        // https://learn.microsoft.com/en-us/sql/sql-server/maximum-capacity-specifications-for-sql-server
+        128
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        // https://learn.microsoft.com/en-us/sql/t-sql/language-elements/reserved-keywords-transact-sql
+        const RESERVED_WORDS: &[&str] = &[
+            "add", "all", "alter", "and", "any", "as", "asc", "backup", "begin", "between", "by", "case", "check",
+            "column", "constraint", "create", "cross", "current", "current_date", "current_time",
+            "current_timestamp", "current_user", "database", "default", "delete", "desc", "distinct", "drop",
+            "else", "end", "exec", "execute", "exists", "for", "foreign", "from", "full", "function", "goto",
+            "group", "having", "identity", "in", "index", "inner", "insert", "intersect", "into", "is", "join",
+            "key", "left", "like", "not", "null", "of", "on", "open", "option", "or", "order", "outer",
+            "primary", "procedure", "references", "right", "rollback", "schema", "select", "session_user",
+            "set", "some", "system_user", "table", "then", "to", "transaction", "trigger", "union",
+            "unique", "update", "user", "using", "values", "view", "when", "where", "with",
+        ];
+
+        RESERVED_WORDS.iter().any(|word| word.eq_ignore_ascii_case(name))
+    }
+
+    fn supports_multi_schema(&self) -> bool {
+        true
+    }
+
     fn create_database(&mut self) -> BoxFuture<'_, ConnectorResult<String>> {
         Box::pin(async {
             let params = self.state.get_unwrapped_params();
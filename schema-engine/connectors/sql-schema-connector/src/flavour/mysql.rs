@@ -183,6 +183,28 @@ impl SqlFlavour for MysqlFlavour {
             .map(|p| p.connector_params.connection_string.as_str())
     }
 
+    fn max_identifier_length(&self) -> usize {
+        // https://dev.mysql.com/doc/refman/8.0/en/identifier-length.html
+        64
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        // https://dev.mysql.com/doc/refman/8.0/en/keywords.html (reserved words only).
+        const RESERVED_WORDS: &[&str] = &[
+            "add", "all", "alter", "and", "as", "asc", "between", "both", "by", "case", "change", "check",
+            "column", "condition", "constraint", "create", "cross", "current_date", "current_time",
+            "current_timestamp", "current_user", "database", "default", "delete", "desc", "describe", "distinct",
+            "drop", "else", "exists", "explain", "false", "for", "foreign", "from", "group", "having", "if", "in",
+            "index", "insert", "interval", "into", "is", "join", "key", "leading", "left", "like", "limit",
+            "localtime", "localtimestamp", "lock", "match", "not", "null", "on", "or", "order", "outer", "over",
+            "primary", "references", "rename", "replace", "right", "rlike", "schema", "select", "set", "show",
+            "table", "then", "to", "trailing", "true", "union", "unique", "update", "use", "using", "values",
+            "when", "where", "window", "with",
+        ];
+
+        RESERVED_WORDS.iter().any(|word| word.eq_ignore_ascii_case(name))
+    }
+
     fn create_database(&mut self) -> BoxFuture<'_, ConnectorResult<String>> {
         Box::pin(async {
             let params = self.state.get_unwrapped_params();
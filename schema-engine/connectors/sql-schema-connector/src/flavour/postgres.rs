@@ -237,6 +237,27 @@ impl SqlFlavour for PostgresFlavour {
             .map(|p| p.connector_params.connection_string.as_str())
     }
 
+    fn is_reserved(&self, name: &str) -> bool {
+        // https://www.postgresql.org/docs/current/sql-keywords-appendix.html (reserved and
+        // reserved-for-future-use words only; non-reserved keywords can be used as identifiers).
+        const RESERVED_WORDS: &[&str] = &[
+            "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric", "both", "case", "cast",
+            "check", "collate", "column", "constraint", "create", "current_catalog", "current_date",
+            "current_role", "current_time", "current_timestamp", "current_user", "default", "deferrable", "desc",
+            "distinct", "do", "else", "end", "except", "false", "fetch", "for", "foreign", "from", "grant", "group",
+            "having", "in", "initially", "intersect", "into", "lateral", "leading", "limit", "localtime",
+            "localtimestamp", "not", "null", "offset", "on", "only", "or", "order", "primary", "references",
+            "returning", "select", "session_user", "some", "symmetric", "table", "then", "to", "trailing", "true",
+            "union", "unique", "user", "using", "variadic", "when", "where", "window", "with",
+        ];
+
+        RESERVED_WORDS.iter().any(|word| word.eq_ignore_ascii_case(name))
+    }
+
+    fn supports_multi_schema(&self) -> bool {
+        true
+    }
+
     fn create_database(&mut self) -> BoxFuture<'_, ConnectorResult<String>> {
         Box::pin(async {
             let params = self.state.get_unwrapped_params();
@@ -14,6 +14,12 @@ type State = super::State<Params, Connection>;
 struct Params {
     connector_params: ConnectorParams,
     file_path: String,
+    /// Parsed from the `migration_statement_chunk_size` connection string parameter. See
+    /// [`SqlFlavour::migration_statement_chunk_size`].
+    migration_statement_chunk_size: Option<usize>,
+    /// Parsed from the `not_null_coalesce_sentinel` connection string parameter. See
+    /// [`SqlFlavour::not_null_coalesce_sentinel`].
+    not_null_coalesce_sentinel: Option<String>,
 }
 
 pub(crate) struct SqliteFlavour {
@@ -57,6 +63,26 @@ impl SqlFlavour for SqliteFlavour {
             .map(|p| p.connector_params.connection_string.as_str())
     }
 
+    fn max_identifier_length(&self) -> usize {
+        // SQLite identifiers are only bounded by SQLITE_MAX_LENGTH (1,000,000,000 bytes by
+        // default), which nothing we generate could realistically reach.
+        usize::MAX
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        // https://www.sqlite.org/lang_keywords.html
+        const RESERVED_WORDS: &[&str] = &[
+            "add", "all", "alter", "and", "as", "asc", "between", "by", "case", "check", "collate", "column",
+            "constraint", "create", "cross", "current_date", "current_time", "current_timestamp", "default",
+            "delete", "desc", "distinct", "drop", "else", "end", "exists", "foreign", "from", "group", "having",
+            "in", "index", "insert", "intersect", "into", "is", "join", "key", "left", "like", "limit", "not",
+            "null", "on", "or", "order", "outer", "primary", "references", "right", "select", "set", "table",
+            "then", "to", "transaction", "union", "unique", "update", "using", "values", "when", "where",
+        ];
+
+        RESERVED_WORDS.iter().any(|word| word.eq_ignore_ascii_case(name))
+    }
+
     fn table_names(&mut self, _namespaces: Option<Namespaces>) -> BoxFuture<'_, ConnectorResult<Vec<String>>> {
         Box::pin(async move {
             let select = r#"SELECT name AS table_name FROM sqlite_master WHERE type='table' ORDER BY name ASC"#;
@@ -311,13 +337,26 @@ impl SqlFlavour for SqliteFlavour {
             quaint::connector::SqliteParams::try_from(params.connection_string.as_str())
                 .map_err(ConnectorError::url_parse_error)?;
 
+        let migration_statement_chunk_size = migration_statement_chunk_size_param(&params.connection_string);
+        let not_null_coalesce_sentinel = not_null_coalesce_sentinel_param(&params.connection_string);
+
         self.state.set_params(Params {
             connector_params: params,
             file_path,
+            migration_statement_chunk_size,
+            not_null_coalesce_sentinel,
         });
         Ok(())
     }
 
+    fn migration_statement_chunk_size(&self) -> Option<usize> {
+        self.state.params().and_then(|params| params.migration_statement_chunk_size)
+    }
+
+    fn not_null_coalesce_sentinel(&self) -> Option<&str> {
+        self.state.params().and_then(|params| params.not_null_coalesce_sentinel.as_deref())
+    }
+
     fn set_preview_features(&mut self, preview_features: enumflags2::BitFlags<psl::PreviewFeature>) {
         match &mut self.state {
             super::State::Initial => {
@@ -371,6 +410,39 @@ fn acquire_lock(connection: &mut Connection) -> ConnectorResult<()> {
     connection.raw_cmd("PRAGMA main.locking_mode=EXCLUSIVE")
 }
 
+/// Parses the `migration_statement_chunk_size` parameter off a SQLite connection string, the
+/// same way [`quaint::connector::SqliteParams`] parses its own query parameters.
+fn migration_statement_chunk_size_param(connection_string: &str) -> Option<usize> {
+    let query = connection_string.split_once('?')?.1;
+
+    query.split('&').find_map(|kv| {
+        let (key, value) = kv.split_once('=')?;
+
+        if key == "migration_statement_chunk_size" {
+            value.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Parses the `not_null_coalesce_sentinel` parameter off a SQLite connection string, the same way
+/// [`migration_statement_chunk_size_param`] does. The value is used verbatim as a raw SQL
+/// expression, the same way a `DbGenerated` default is.
+fn not_null_coalesce_sentinel_param(connection_string: &str) -> Option<String> {
+    let query = connection_string.split_once('?')?.1;
+
+    query.split('&').find_map(|kv| {
+        let (key, value) = kv.split_once('=')?;
+
+        if key == "not_null_coalesce_sentinel" {
+            Some(value.to_owned())
+        } else {
+            None
+        }
+    })
+}
+
 fn with_connection<'a, O, C>(state: &'a mut State, f: C) -> ConnectorResult<O>
 where
     O: 'a + Send,
@@ -0,0 +1,164 @@
+//! An opt-in check for table, column, index and foreign key constraint names, introduced by a
+//! migration, that are longer than the target database allows. A name that goes over the limit
+//! is silently truncated by the database itself, which risks colliding with another generated
+//! name that gets truncated to the same prefix. We don't truncate names ourselves when rendering
+//! — the database's own truncation rule is opaque to us in general, and two names we generated
+//! distinctly could very well collide after it — so this exists for callers who want to catch the
+//! problem ahead of time, and [`truncate_with_hash_suffix`] for producing a replacement name that
+//! is guaranteed not to collide with a different over-long name sharing the same prefix.
+
+use crate::{
+    flavour::SqlFlavour,
+    sql_migration::{SqlMigration, SqlMigrationStep, TableChange},
+    sql_schema_differ::SqlSchemaDifferFlavour,
+};
+use schema_connector::{ConnectorError, ConnectorResult};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// Check every table, column, index and foreign key constraint name introduced by `migration`
+/// against `flavour.max_identifier_length()`, and return a single blocking error naming all the
+/// offenders if any are found.
+pub(crate) fn check_identifier_lengths(migration: &SqlMigration, flavour: &dyn SqlFlavour) -> ConnectorResult<()> {
+    let schemas = migration.schemas();
+    let max_length = flavour.max_identifier_length();
+    let mut offenders = Vec::new();
+
+    let mut check = |kind: &str, name: &str| {
+        if name.len() > max_length {
+            offenders.push(format!(
+                "{kind} `{name}` ({} characters, max {max_length}; for example, `{}` would avoid the limit)",
+                name.len(),
+                truncate_with_hash_suffix(name, max_length),
+            ));
+        }
+    };
+
+    for step in &migration.steps {
+        match step {
+            SqlMigrationStep::CreateTable { table_id } => {
+                let table = schemas.next.walk(*table_id);
+                check("table", table.name());
+
+                for column in table.columns() {
+                    check("column", column.name());
+                }
+
+                // Flavours that render a created table's indexes inline emit no CreateIndex step.
+                if !flavour.should_create_indexes_from_created_tables() {
+                    for index in table.indexes() {
+                        check("index", index.name());
+                    }
+                }
+            }
+            SqlMigrationStep::AddForeignKey { foreign_key_id, .. } => {
+                if let Some(name) = schemas.next.walk(*foreign_key_id).constraint_name() {
+                    check("foreign key", name);
+                }
+            }
+            SqlMigrationStep::AlterTable(alter_table) => {
+                for change in &alter_table.changes {
+                    match change {
+                        TableChange::AddColumn { column_id, .. } => {
+                            check("column", schemas.next.walk(*column_id).name());
+                        }
+                        TableChange::AddForeignKey { foreign_key_id, .. } => {
+                            if let Some(name) = schemas.next.walk(*foreign_key_id).constraint_name() {
+                                check("foreign key", name);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            SqlMigrationStep::CreateIndex { index_id, .. } => {
+                check("index", schemas.next.walk(*index_id).name());
+            }
+            SqlMigrationStep::RenameForeignKey { foreign_key_id } => {
+                if let Some(name) = schemas.next.walk(foreign_key_id.next).constraint_name() {
+                    check("foreign key", name);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if offenders.is_empty() {
+        return Ok(());
+    }
+
+    Err(ConnectorError::from_msg(format!(
+        "The following names are longer than {max_length} characters, the limit for the target database, and would be truncated: {}.",
+        offenders.join(", ")
+    )))
+}
+
+/// If `name` is no longer than `max_length`, return it unchanged. Otherwise, truncate it to make
+/// room for an 8-character suffix derived from hashing the full, untruncated name, so that two
+/// different over-long names that happen to share the same `max_length`-byte prefix still end up
+/// with different results instead of silently colliding.
+pub(crate) fn truncate_with_hash_suffix(name: &str, max_length: usize) -> String {
+    if name.len() <= max_length {
+        return name.to_owned();
+    }
+
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let suffix = format!("{:08x}", hasher.finish() as u32);
+
+    // `saturating_sub` covers a `max_length` too small to even fit the suffix; the result is
+    // then just `_` and the suffix, which is longer than `max_length` and no longer useful as a
+    // name, but at least doesn't panic.
+    let prefix_len = max_length.saturating_sub(suffix.len() + 1).min(name.len());
+    let prefix = &name[..floor_char_boundary(name, prefix_len)];
+
+    format!("{prefix}_{suffix}")
+}
+
+/// The largest byte index `<= index` that lands on a UTF-8 character boundary in `s`, so slicing
+/// `&s[..that_index]` can't panic or split a multi-byte character.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    (0..=index).rev().find(|&i| s.is_char_boundary(i)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_with_hash_suffix_leaves_short_names_untouched() {
+        assert_eq!(truncate_with_hash_suffix("short_name", 63), "short_name");
+    }
+
+    #[test]
+    fn truncate_with_hash_suffix_shortens_long_names_to_the_limit() {
+        let name = "a".repeat(100);
+        let truncated = truncate_with_hash_suffix(&name, 63);
+
+        assert_eq!(truncated.len(), 63);
+        assert!(truncated.starts_with(&"a".repeat(54)));
+    }
+
+    #[test]
+    fn truncate_with_hash_suffix_disambiguates_names_that_collide_after_a_naive_truncation() {
+        // These two names share their first 70 characters, so a naive truncation to 63 bytes
+        // would make them collide.
+        let common_prefix = "a".repeat(70);
+        let first = format!("{common_prefix}_foo");
+        let second = format!("{common_prefix}_bar");
+
+        assert_eq!(&first[..63], &second[..63], "the two names should collide under naive truncation");
+
+        let truncated_first = truncate_with_hash_suffix(&first, 63);
+        let truncated_second = truncate_with_hash_suffix(&second, 63);
+
+        assert_eq!(truncated_first.len(), 63);
+        assert_eq!(truncated_second.len(), 63);
+        assert_ne!(
+            truncated_first, truncated_second,
+            "two over-long names sharing the same truncated prefix must not collide"
+        );
+    }
+}
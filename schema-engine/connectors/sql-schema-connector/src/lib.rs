@@ -6,11 +6,17 @@ mod apply_migration;
 mod database_schema;
 mod error;
 mod flavour;
+mod identifier_length;
 mod introspection;
+mod migrate_sql;
 mod migration_pair;
+mod reserved_identifiers;
+mod schema_hash;
 mod sql_destructive_change_checker;
 mod sql_migration;
+mod sql_migration_inverter;
 mod sql_migration_persistence;
+mod sql_migration_risk;
 mod sql_renderer;
 mod sql_schema_calculator;
 mod sql_schema_differ;
@@ -23,7 +29,10 @@ use psl::ValidatedSchema;
 use schema_connector::{migrations_directory::MigrationDirectory, *};
 use sql_migration::{DropUserDefinedType, DropView, SqlMigration, SqlMigrationStep};
 use sql_schema_describer as sql;
-use std::{future, sync::Arc};
+use std::{collections::BTreeMap, future, sync::Arc};
+
+pub use migrate_sql::MigrationScript;
+pub use sql_migration_risk::{MigrationRisk, RiskLevel};
 
 const MIGRATIONS_TABLE_NAME: &str = "_prisma_migrations";
 
@@ -124,6 +133,188 @@ impl SqlSchemaConnector {
         self.flavour.set_params(params)
     }
 
+    /// Opt-in check for tables and columns created by `migration` whose name is a word reserved
+    /// by the target database. Not run automatically as part of `diff`, since every identifier
+    /// we render is already quoted and thus unaffected by this; callers that also need their
+    /// identifiers to be safe unquoted (e.g. for raw queries elsewhere in their stack) can call
+    /// this explicitly and surface the resulting error before applying the migration.
+    pub fn check_reserved_identifiers(&self, migration: &Migration) -> ConnectorResult<()> {
+        reserved_identifiers::check_reserved_identifiers(migration.downcast_ref(), self.flavour.as_ref())
+    }
+
+    /// Check that every table, column, index and foreign key constraint name introduced by
+    /// `migration` fits within [`crate::flavour::SqlFlavour::max_identifier_length`]. Not run
+    /// automatically as part of `diff`, since a name that goes over the limit doesn't fail the
+    /// migration outright — the database truncates it instead, which only becomes a problem if
+    /// that truncation collides with another name. Callers that want to catch that ahead of time
+    /// can call this explicitly before applying the migration.
+    pub fn check_identifier_lengths(&self, migration: &Migration) -> ConnectorResult<()> {
+        identifier_length::check_identifier_lengths(migration.downcast_ref(), self.flavour.as_ref())
+    }
+
+    /// Summarize how risky `migration` is, for surfacing in dashboards. Unlike
+    /// [`DestructiveChangeChecker`], this never touches the database, so it can't account for row
+    /// counts on a flavour that would otherwise inspect them.
+    pub fn migration_risk(&self, migration: &Migration) -> MigrationRisk {
+        let migration: &SqlMigration = migration.downcast_ref();
+        sql_migration_risk::score_migration(&migration.steps, migration.schemas(), self.flavour())
+    }
+
+    /// Like [`SchemaConnector::apply_migration`], but also returns a changelog of every
+    /// statement that was applied, for tooling that needs to audit what a migration actually did
+    /// (e.g. an external audit log) rather than only how many steps ran.
+    pub async fn apply_migration_with_changelog(
+        &mut self,
+        migration: &Migration,
+    ) -> ConnectorResult<(u32, Vec<apply_migration::AppliedStep>)> {
+        apply_migration::apply_migration_with_changelog(migration, self.flavour.as_mut()).await
+    }
+
+    /// Compute the down-migration for `migration`: the steps that, applied on top of it, bring
+    /// the database back to the schema `migration` started from. Down-migrations reverse the
+    /// schema, not the data, so e.g. a dropped column comes back empty. Returns the descriptions
+    /// of any forward steps that have no safe down-migration equivalent instead of a partial
+    /// result — for example, dropping a required column with no default is never inverted, since
+    /// the data needed to recreate it is already gone.
+    pub fn invert(&self, migration: Migration) -> Result<Migration, Vec<String>> {
+        let migration: Box<SqlMigration> = migration.downcast();
+        let steps = migration.invert()?;
+
+        Ok(Migration::new(SqlMigration {
+            before: migration.after,
+            after: migration.before,
+            steps,
+        }))
+    }
+
+    /// Diffs `previous` against `next` and renders the result to a SQL script in one call,
+    /// instead of separately wiring up [`Self::diff`]/[`SchemaConnector::diff`] and
+    /// [`SchemaConnector::render_script`]. For callers (e.g. simple CLIs) that don't need the
+    /// rest of the diff/apply lifecycle this type exposes to the schema engine core.
+    pub fn migrate_sql(&self, previous: sql::SqlSchema, next: sql::SqlSchema) -> ConnectorResult<MigrationScript> {
+        migrate_sql::migrate_sql(previous, next, self.flavour.as_ref(), Default::default())
+    }
+
+    /// Build a migration that truncates `table_names`, for callers that need to empty tables
+    /// outside of a schema diff (e.g. before reseeding). `cascade` additionally truncates tables
+    /// with foreign keys to any of `table_names`, where the flavour supports it (currently
+    /// PostgreSQL only); on flavours without native cascading truncation, list the referencing
+    /// tables explicitly, in the order they must be truncated.
+    ///
+    /// `schema` is needed twice — mirroring [`Self::diff_foreign_keys`]'s `from`/`to` — because
+    /// the returned [`Migration`] doesn't change the schema itself, only the data; pass two
+    /// [`DatabaseSchema`]s describing the same database (e.g. from two calls to
+    /// [`Self::describe_schema`]).
+    pub fn truncate_tables(
+        &self,
+        schema: MigrationPair<DatabaseSchema>,
+        table_names: &[&str],
+        cascade: bool,
+    ) -> ConnectorResult<Migration> {
+        let schema = schema.map(SqlDatabaseSchema::from_erased);
+
+        let steps = table_names
+            .iter()
+            .map(|table_name| {
+                let table = schema.previous.describer_schema.table_walker(table_name).ok_or_else(|| {
+                    ConnectorError::from_msg(format!("Table `{table_name}` does not exist in the given schema."))
+                })?;
+
+                Ok(SqlMigrationStep::truncate_table(table.id, cascade))
+            })
+            .collect::<ConnectorResult<Vec<_>>>()?;
+
+        Ok(Migration::new(SqlMigration {
+            before: schema.previous.describer_schema,
+            after: schema.next.describer_schema,
+            steps,
+        }))
+    }
+
+    /// Like [`SchemaConnector::diff`], but only considers foreign keys, ignoring every other
+    /// kind of change. Useful to repair foreign key drift on its own — for example re-adding the
+    /// constraints a bulk load disabled — without the column/index churn a full `diff` of the
+    /// same two schemas would include. See [`sql_schema_differ::calculate_fk_steps`] for the
+    /// cases this intentionally leaves out.
+    pub fn diff_foreign_keys(&self, from: DatabaseSchema, to: DatabaseSchema) -> Migration {
+        let previous = SqlDatabaseSchema::from_erased(from);
+        let next = SqlDatabaseSchema::from_erased(to);
+        let steps = sql_schema_differ::calculate_fk_steps(MigrationPair::new(&previous, &next), self.flavour.as_ref());
+
+        Migration::new(SqlMigration {
+            before: previous.describer_schema,
+            after: next.describer_schema,
+            steps,
+        })
+    }
+
+    /// Returns true iff [`SchemaConnector::diff`] on the same two schemas would produce no steps
+    /// at all, i.e. they are identical as far as the diffing rules are concerned. Cheaper than
+    /// calling `diff` and checking the result is empty when the caller doesn't need the steps
+    /// themselves — for example a watcher that only needs to know whether to re-diff at all.
+    pub fn schemas_equivalent(&self, from: DatabaseSchema, to: DatabaseSchema) -> bool {
+        let previous = SqlDatabaseSchema::from_erased(from);
+        let next = SqlDatabaseSchema::from_erased(to);
+
+        sql_schema_differ::schemas_equivalent(MigrationPair::new(&previous, &next), self.flavour.as_ref())
+    }
+
+    /// Group `migration`'s steps by the table they primarily affect, for callers (e.g. a UI) that
+    /// want to present a diff per model rather than as a flat list. Returns step indices into
+    /// [`SqlMigration::steps`] (the same indexing [`Self::render_steps`] uses via
+    /// [`apply_migration::RenderedStep::step_index`]) rather than the steps themselves, since
+    /// [`sql_migration::SqlMigrationStep`] isn't part of this crate's public API. Steps that are
+    /// not about a single table are grouped under the empty-string key.
+    pub fn steps_by_table(&self, migration: &Migration) -> BTreeMap<String, Vec<usize>> {
+        let migration: &SqlMigration = migration.downcast_ref();
+        let grouped = sql_migration::steps_by_table(&migration.steps, migration.schemas());
+
+        grouped
+            .into_iter()
+            .map(|(name, steps)| (name, step_indices(&migration.steps, steps)))
+            .collect()
+    }
+
+    /// Split `migration`'s steps into a safe set and a set containing only the steps classified
+    /// as destructive by `SqlMigrationStep::is_destructive` — dropped tables, truncations,
+    /// dropped columns, not-castable column recreates, and narrowing column type changes.
+    /// Intended for review-gating pipelines that want to require extra sign-off before applying
+    /// anything that could delete or corrupt data, without blocking the rest of a migration on
+    /// it. Returns step indices into [`SqlMigration::steps`], as `(safe, destructive)`, for the
+    /// same reason [`Self::steps_by_table`] does.
+    pub fn partition_steps(&self, migration: &Migration) -> (Vec<usize>, Vec<usize>) {
+        let migration: &SqlMigration = migration.downcast_ref();
+        let (safe, destructive) = sql_migration::partition_steps(&migration.steps);
+
+        (
+            step_indices(&migration.steps, safe),
+            step_indices(&migration.steps, destructive),
+        )
+    }
+
+    /// Diffs `from` against `to` and applies the resulting steps to the database one at a time as
+    /// they're produced, instead of collecting the whole diff into a [`Migration`] first via
+    /// [`Self::diff`]/[`SchemaConnector::apply_migration`]. Useful for a caller applying a large
+    /// diff that doesn't need the `Migration` afterwards (e.g. to render a script or check its
+    /// risk) and would rather not hold the fully materialized step list, its rendered SQL, and a
+    /// changelog all in memory together. Returns the number of steps applied.
+    pub async fn apply_diff_streaming(&mut self, from: DatabaseSchema, to: DatabaseSchema) -> ConnectorResult<u32> {
+        let previous = SqlDatabaseSchema::from_erased(from);
+        let next = SqlDatabaseSchema::from_erased(to);
+        let steps = sql_schema_differ::calculate_steps_streaming(MigrationPair::new(&previous, &next), self.flavour());
+        let schemas = MigrationPair::new(&previous.describer_schema, &next.describer_schema);
+
+        apply_migration::apply_steps_streaming(steps, schemas, self.flavour.as_mut()).await
+    }
+
+    /// Like [`SchemaConnector::render_script`], but keeps each step's statements grouped and
+    /// reports whether they can run inside the migration's transaction, for callers (e.g. an
+    /// external migration orchestrator) that apply statements themselves instead of handing a
+    /// flat SQL script to a `psql`-like client.
+    pub fn render_steps(&self, migration: &Migration) -> Vec<apply_migration::RenderedStep> {
+        apply_migration::render_steps(migration, self.flavour())
+    }
+
     async fn db_schema_from_diff_target(
         &mut self,
         target: DiffTarget<'_>,
@@ -252,6 +443,11 @@ impl SchemaConnector for SqlSchemaConnector {
         })
     }
 
+    fn schema_hash(&self, schema: &DatabaseSchema) -> u64 {
+        let schema: &SqlDatabaseSchema = schema.downcast_ref();
+        schema_hash::hash_schema(&schema.describer_schema, self.flavour.as_ref())
+    }
+
     fn drop_database(&mut self) -> BoxFuture<'_, ConnectorResult<()>> {
         self.flavour.drop_database()
     }
@@ -291,6 +487,15 @@ impl SchemaConnector for SqlSchemaConnector {
         apply_migration::render_script(migration, diagnostics, self.flavour())
     }
 
+    fn render_script_with_options(
+        &self,
+        migration: &Migration,
+        diagnostics: &DestructiveChangeDiagnostics,
+        emit_comments: bool,
+    ) -> ConnectorResult<String> {
+        apply_migration::render_script_with_options(migration, diagnostics, self.flavour(), emit_comments)
+    }
+
     fn reset(&mut self, soft: bool, namespaces: Option<Namespaces>) -> BoxFuture<'_, ConnectorResult<()>> {
         Box::pin(async move {
             if soft || self.flavour.reset(namespaces.clone()).await.is_err() {
@@ -352,6 +557,20 @@ fn new_shadow_database_name() -> String {
     format!("prisma_migrate_shadow_db_{}", uuid::Uuid::new_v4())
 }
 
+/// Maps each of `steps` (borrowed from `all_steps`) to its index in `all_steps`, for public APIs
+/// that report steps by index rather than exposing [`sql_migration::SqlMigrationStep`] itself.
+fn step_indices(all_steps: &[SqlMigrationStep], steps: Vec<&SqlMigrationStep>) -> Vec<usize> {
+    steps
+        .into_iter()
+        .map(|step| {
+            all_steps
+                .iter()
+                .position(|s| std::ptr::eq(s, step))
+                .expect("step must be borrowed from all_steps")
+        })
+        .collect()
+}
+
 /// Try to reset the database to an empty state. This should only be used
 /// when we don't have the permissions to do a full reset.
 #[tracing::instrument(skip(flavour))]
@@ -0,0 +1,118 @@
+//! A one-shot convenience API that runs the differ and the renderer together, for callers (e.g.
+//! simple CLIs) that don't need to drive the full diff/apply lifecycle [`crate::SqlSchemaConnector`]
+//! exposes to the schema engine core. Exposed publicly as [`crate::SqlSchemaConnector::migrate_sql`].
+
+use crate::{
+    apply_migration, database_schema::SqlDatabaseSchema, migration_pair::MigrationPair,
+    sql_destructive_change_checker, sql_migration::SqlMigration, sql_schema_differ, SqlFlavour,
+};
+use schema_connector::{ConnectorResult, DestructiveChangeDiagnostics, Migration};
+use sql_schema_describer::SqlSchema;
+
+/// The result of [`migrate_sql`]: a rendered SQL script, together with the destructive-change
+/// warnings (if any) found while producing it.
+#[derive(Debug)]
+pub struct MigrationScript {
+    /// The rendered SQL script. Empty diffs still render a (harmless) script, rather than an
+    /// empty string, so it's always safe to run.
+    pub script: String,
+    /// The destructive-change warnings found while producing `script`, if any.
+    pub diagnostics: DestructiveChangeDiagnostics,
+}
+
+/// Diffs `previous` against `next` and renders the result to a SQL script in one call, instead of
+/// separately wiring up [`sql_schema_differ::calculate_steps_with_settings`], the destructive
+/// change checker, and the renderer. An empty diff renders to a script holding a single comment.
+pub(crate) fn migrate_sql(
+    previous: SqlSchema,
+    next: SqlSchema,
+    flavour: &(dyn SqlFlavour + Send + Sync),
+    settings: sql_schema_differ::DifferSettings,
+) -> ConnectorResult<MigrationScript> {
+    let schemas = MigrationPair::new(SqlDatabaseSchema::from(previous), SqlDatabaseSchema::from(next));
+    let steps = sql_schema_differ::calculate_steps_with_settings(schemas.as_ref(), flavour, settings);
+    let schemas = schemas.map(|schema| schema.describer_schema);
+
+    let migration = Migration::new(SqlMigration {
+        before: schemas.previous,
+        after: schemas.next,
+        steps,
+    });
+
+    let diagnostics = sql_destructive_change_checker::plan(migration.downcast_ref(), flavour).pure_check();
+    let script = apply_migration::render_script(&migration, &diagnostics, flavour)?;
+
+    Ok(MigrationScript { script, diagnostics })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flavour::SqliteFlavour;
+    use psl::SourceFile;
+
+    fn sql_schema(datamodel: &str) -> SqlSchema {
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+        let flavour = SqliteFlavour::default();
+
+        crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &flavour).describer_schema
+    }
+
+    #[test]
+    fn an_empty_diff_renders_to_the_empty_migration_comment() {
+        let datamodel = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id Int @id
+            }
+        "#;
+
+        let flavour = SqliteFlavour::default();
+
+        let result = migrate_sql(sql_schema(datamodel), sql_schema(datamodel), &flavour, Default::default()).unwrap();
+
+        assert_eq!(result.script, "-- This is an empty migration.");
+        assert!(!result.diagnostics.has_warnings());
+    }
+
+    #[test]
+    fn an_added_column_renders_sql_without_warnings() {
+        let previous_datamodel = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id Int @id
+            }
+        "#;
+
+        let next_datamodel = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id   Int    @id
+              name String?
+            }
+        "#;
+
+        let previous = sql_schema(previous_datamodel);
+        let next = sql_schema(next_datamodel);
+        let flavour = SqliteFlavour::default();
+
+        let result = migrate_sql(previous, next, &flavour, Default::default()).unwrap();
+
+        assert!(result.script.contains("ALTER TABLE"));
+        assert!(result.script.contains("\"name\""));
+        assert!(!result.diagnostics.has_warnings());
+    }
+}
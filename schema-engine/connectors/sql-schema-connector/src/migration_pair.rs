@@ -47,6 +47,15 @@ impl<T> MigrationPair<T> {
     pub(crate) fn zip<U>(self, other: MigrationPair<U>) -> MigrationPair<(T, U)> {
         MigrationPair::new((self.previous, other.previous), (self.next, other.next))
     }
+
+    /// Swap `previous` and `next`, e.g. to reinterpret a pair of ids in the context of a
+    /// down-migration, where what used to be `next` becomes `previous`.
+    pub(crate) fn swapped(self) -> MigrationPair<T> {
+        MigrationPair {
+            previous: self.next,
+            next: self.previous,
+        }
+    }
 }
 
 impl<T> MigrationPair<Option<T>> {
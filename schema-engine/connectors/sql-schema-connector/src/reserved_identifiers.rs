@@ -0,0 +1,60 @@
+//! An opt-in check for table and column names, introduced by a migration, that collide with a
+//! word reserved by the target database. We always quote the identifiers we render, so a
+//! reserved word works fine in the SQL we generate; this check exists for callers who want to
+//! catch the collision ahead of time anyway (for example because some other part of their stack
+//! — a raw query, a different tool reading the same database — writes unquoted SQL and would
+//! choke on it).
+
+use crate::{
+    flavour::SqlFlavour,
+    sql_migration::{SqlMigration, SqlMigrationStep, TableChange},
+};
+use schema_connector::{ConnectorError, ConnectorResult};
+
+/// Check every table and column created by `migration` against `flavour.is_reserved`, and return
+/// a single blocking error naming all of them if any are found.
+pub(crate) fn check_reserved_identifiers(migration: &SqlMigration, flavour: &dyn SqlFlavour) -> ConnectorResult<()> {
+    let schemas = migration.schemas();
+    let mut offenders = Vec::new();
+
+    for step in &migration.steps {
+        match step {
+            SqlMigrationStep::CreateTable { table_id } => {
+                let table = schemas.next.walk(*table_id);
+
+                if flavour.is_reserved(table.name()) {
+                    offenders.push(format!("table `{}`", table.name()));
+                }
+
+                for column in table.columns() {
+                    if flavour.is_reserved(column.name()) {
+                        offenders.push(format!("column `{}` on table `{}`", column.name(), table.name()));
+                    }
+                }
+            }
+            SqlMigrationStep::AlterTable(alter_table) => {
+                let table = schemas.next.walk(alter_table.table_ids.next);
+
+                for change in &alter_table.changes {
+                    if let TableChange::AddColumn { column_id, .. } = change {
+                        let column = schemas.next.walk(*column_id);
+
+                        if flavour.is_reserved(column.name()) {
+                            offenders.push(format!("column `{}` on table `{}`", column.name(), table.name()));
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if offenders.is_empty() {
+        return Ok(());
+    }
+
+    Err(ConnectorError::from_msg(format!(
+        "The following identifiers are reserved words in the target database: {}. Rename them, or quote them explicitly wherever they are used outside of migrations, before migrating.",
+        offenders.join(", ")
+    )))
+}
@@ -0,0 +1,181 @@
+//! A stable, order-independent content hash of a [`SqlSchema`], for callers that want to cache
+//! diffing results and skip re-diffing when a schema hasn't materially changed.
+//!
+//! The hash follows the same rules the differ itself uses to decide whether two schemas are
+//! equivalent: tables and views the flavour ignores (e.g. PostGIS system tables) are skipped, and
+//! unordered collections (columns, indexes, foreign keys, ...) are sorted by name first so that
+//! two schemas that only differ in describer iteration order hash equally.
+
+use crate::flavour::SqlFlavour;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use sql_schema_describer::{
+    walkers::{ForeignKeyWalker, IndexWalker, TableColumnWalker, TableWalker},
+    SqlSchema,
+};
+
+/// Compute a stable hash of `schema`, consistent with the rules `flavour` uses for diffing.
+pub(crate) fn hash_schema(schema: &SqlSchema, flavour: &dyn SqlFlavour) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    let mut tables: Vec<TableWalker<'_>> = schema
+        .table_walkers()
+        .filter(|table| !flavour.table_should_be_ignored(table.name()))
+        .collect();
+    tables.sort_by_key(|table| (table.namespace(), table.name()));
+
+    for table in tables {
+        hash_table(table, &mut hasher);
+    }
+
+    let mut enums: Vec<_> = schema.enum_walkers().collect();
+    enums.sort_by_key(|enm| (enm.namespace(), enm.name()));
+
+    for enm in enums {
+        enm.namespace().hash(&mut hasher);
+        enm.name().hash(&mut hasher);
+        // Variant order is meaningful (e.g. for ordering comparisons), unlike the other
+        // collections here, so we don't sort it.
+        enm.values().for_each(|variant| variant.hash(&mut hasher));
+    }
+
+    hasher.finish()
+}
+
+fn hash_table(table: TableWalker<'_>, hasher: &mut DefaultHasher) {
+    table.namespace().hash(hasher);
+    table.name().hash(hasher);
+    table.has_row_level_security().hash(hasher);
+    table.is_unlogged().hash(hasher);
+
+    let mut columns: Vec<TableColumnWalker<'_>> = table.columns().collect();
+    columns.sort_by_key(|column| column.name());
+
+    for column in columns {
+        hash_column(column, hasher);
+    }
+
+    let mut indexes: Vec<IndexWalker<'_>> = table.indexes().collect();
+    indexes.sort_by_key(|index| index.name());
+
+    for index in indexes {
+        hash_index(index, hasher);
+    }
+
+    let mut foreign_keys: Vec<ForeignKeyWalker<'_>> = table.foreign_keys().collect();
+    foreign_keys.sort_by_key(|fk| {
+        (
+            fk.constraint_name().unwrap_or_default().to_owned(),
+            fk.referenced_table_name().to_owned(),
+        )
+    });
+
+    for foreign_key in foreign_keys {
+        hash_foreign_key(foreign_key, hasher);
+    }
+}
+
+fn hash_column(column: TableColumnWalker<'_>, hasher: &mut DefaultHasher) {
+    // `ColumnArity`, `ColumnTypeFamily` and `DefaultKind` don't implement `Hash`, but they do
+    // implement `Debug` deterministically (no unordered collections inside), so formatting them
+    // is a safe stand-in.
+    column.name().hash(hasher);
+    format!("{:?}", column.arity()).hash(hasher);
+    column.is_autoincrement().hash(hasher);
+    column.column_type().full_data_type.hash(hasher);
+
+    match column.column_type_family_as_enum() {
+        Some(enum_walker) => {
+            enum_walker.namespace().hash(hasher);
+            enum_walker.name().hash(hasher);
+        }
+        None => format!("{:?}", column.column_type_family()).hash(hasher),
+    }
+
+    format!("{:?}", column.default().map(|d| d.kind())).hash(hasher);
+}
+
+fn hash_index(index: IndexWalker<'_>, hasher: &mut DefaultHasher) {
+    index.name().hash(hasher);
+    index.is_unique().hash(hasher);
+    index.is_primary_key().hash(hasher);
+    index.column_names().for_each(|name| name.hash(hasher));
+}
+
+fn hash_foreign_key(foreign_key: ForeignKeyWalker<'_>, hasher: &mut DefaultHasher) {
+    foreign_key.constraint_name().hash(hasher);
+    foreign_key.referenced_table_name().hash(hasher);
+    format!("{:?}", foreign_key.on_delete_action()).hash(hasher);
+    format!("{:?}", foreign_key.on_update_action()).hash(hasher);
+
+    foreign_key
+        .constrained_columns()
+        .for_each(|column| column.name().hash(hasher));
+    foreign_key
+        .referenced_columns()
+        .for_each(|column| column.name().hash(hasher));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database_schema::SqlDatabaseSchema, flavour::SqliteFlavour};
+    use psl::SourceFile;
+
+    fn sql_schema(datamodel: &str) -> SqlDatabaseSchema {
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+        crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &SqliteFlavour::default())
+    }
+
+    const BASE_DATAMODEL: &str = r#"
+        datasource db {
+          provider = "sqlite"
+          url = "file:dev.db"
+        }
+
+        model Cat {
+          id   Int    @id
+          name String
+        }
+    "#;
+
+    #[test]
+    fn equal_schemas_hash_equally() {
+        let a = sql_schema(BASE_DATAMODEL);
+        let b = sql_schema(BASE_DATAMODEL);
+
+        let hash_a = hash_schema(&a.describer_schema, &SqliteFlavour::default());
+        let hash_b = hash_schema(&b.describer_schema, &SqliteFlavour::default());
+
+        assert_eq!(hash_a, hash_b);
+    }
+
+    #[test]
+    fn adding_a_column_changes_the_hash() {
+        let before = sql_schema(BASE_DATAMODEL);
+
+        let after_datamodel = r#"
+            datasource db {
+              provider = "sqlite"
+              url = "file:dev.db"
+            }
+
+            model Cat {
+              id   Int    @id
+              name String
+              age  Int?
+            }
+        "#;
+        let after = sql_schema(after_datamodel);
+
+        let hash_before = hash_schema(&before.describer_schema, &SqliteFlavour::default());
+        let hash_after = hash_schema(&after.describer_schema, &SqliteFlavour::default());
+
+        assert_ne!(hash_before, hash_after);
+    }
+}
@@ -22,6 +22,14 @@ pub struct Column {
     pub column: String,
 }
 
+/// A single enum value to look for in a specific column, for checking whether a removed enum
+/// variant is still in use somewhere before dropping it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnValue {
+    pub column: Column,
+    pub value: String,
+}
+
 /// This trait should be implemented by warning and unexecutable migration types. It lets them
 /// describe what data they need from the current state of the database to be as accurate and
 /// informative as possible.
@@ -36,6 +44,12 @@ pub(super) trait Check {
         None
     }
 
+    /// Indicates that, for each returned `(column, value)` pair, the number of rows where
+    /// `column` currently holds `value` should be inspected.
+    fn needed_enum_value_usage_counts(&self) -> Vec<ColumnValue> {
+        Vec::new()
+    }
+
     /// This function will always be called for every check in a migration. Each change must check
     /// for the data it needs in the database inspection results. If there is no data, it should
     /// assume the current state of the database could not be inspected and warn with a best effort
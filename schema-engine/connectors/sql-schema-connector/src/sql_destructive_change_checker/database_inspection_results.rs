@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use super::check::{Column, Table};
+use super::check::{Column, ColumnValue, Table};
 
 /// The information about the current state of the database gathered by the destructive change checker.
 #[derive(Debug, Default)]
@@ -9,6 +9,9 @@ pub(super) struct DatabaseInspectionResults {
     row_counts: HashMap<Table, i64>,
     /// HashMap from (table name, column name) to non-null values count.
     value_counts: HashMap<Column, i64>,
+    /// HashMap from (column, enum value) to the number of rows where that column currently holds
+    /// that value.
+    enum_value_usage_counts: HashMap<ColumnValue, i64>,
 }
 
 impl DatabaseInspectionResults {
@@ -31,4 +34,12 @@ impl DatabaseInspectionResults {
     pub(super) fn set_value_count(&mut self, column: Column, count: i64) {
         self.value_counts.insert(column, count);
     }
+
+    pub(super) fn get_enum_value_usage_count(&self, column_value: &ColumnValue) -> Option<i64> {
+        self.enum_value_usage_counts.get(column_value).copied()
+    }
+
+    pub(super) fn set_enum_value_usage_count(&mut self, column_value: ColumnValue, count: i64) {
+        self.enum_value_usage_counts.insert(column_value, count);
+    }
 }
@@ -4,7 +4,7 @@ mod postgres;
 mod sqlite;
 
 use super::{
-    check::{Column, Table},
+    check::{Column, ColumnValue, Table},
     DestructiveCheckPlan,
 };
 use crate::{migration_pair::MigrationPair, sql_migration::AlterColumn, sql_schema_differ::ColumnChanges};
@@ -34,6 +34,17 @@ pub(crate) trait DestructiveChangeCheckerFlavour {
     fn count_rows_in_table<'a>(&'a mut self, table: &'a Table) -> BoxFuture<'a, ConnectorResult<i64>>;
 
     fn count_values_in_column<'a>(&'a mut self, column: &'a Column) -> BoxFuture<'a, ConnectorResult<i64>>;
+
+    /// Count the rows where `column_value.column` currently holds `column_value.value`, for
+    /// checking whether a removed enum variant is still in use. Only ever called when an
+    /// [`crate::sql_migration::SqlMigrationStep::AlterEnum`] step or an enum-narrowing
+    /// `AlterColumn` step removes a variant from an enum that at least one column uses (on
+    /// PostgreSQL and MySQL respectively — MSSQL and SQLite have no enum type and never reach
+    /// this method).
+    fn count_rows_with_enum_value<'a>(
+        &'a mut self,
+        column_value: &'a ColumnValue,
+    ) -> BoxFuture<'a, ConnectorResult<i64>>;
 }
 
 /// Display a column type for warnings/errors.
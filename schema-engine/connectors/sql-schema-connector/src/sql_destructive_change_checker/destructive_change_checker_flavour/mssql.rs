@@ -3,7 +3,7 @@ use crate::{
     flavour::{MssqlFlavour, SqlFlavour},
     migration_pair::MigrationPair,
     sql_destructive_change_checker::{
-        check::{Column, Table},
+        check::{Column, ColumnValue, Table},
         destructive_check_plan::DestructiveCheckPlan,
         unexecutable_step_check::UnexecutableStepCheck,
         warning_check::SqlMigrationWarningCheck,
@@ -26,6 +26,7 @@ impl DestructiveChangeCheckerFlavour for MssqlFlavour {
             column_id: _,
             changes,
             type_change,
+            type_override: _,
         } = alter_column;
 
         if changes.only_default_changed() {
@@ -143,4 +144,11 @@ impl DestructiveChangeCheckerFlavour for MssqlFlavour {
             super::extract_column_values_count(result_set)
         })
     }
+
+    fn count_rows_with_enum_value<'a>(
+        &'a mut self,
+        _column_value: &'a ColumnValue,
+    ) -> BoxFuture<'a, ConnectorResult<i64>> {
+        unreachable!("count_rows_with_enum_value on MSSQL: AlterEnum is never emitted for this connector")
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     flavour::{MysqlFlavour, SqlFlavour},
     migration_pair::MigrationPair,
     sql_destructive_change_checker::{
-        check::{Column, Table},
+        check::{Column, ColumnValue, Table},
         destructive_check_plan::DestructiveCheckPlan,
         unexecutable_step_check::UnexecutableStepCheck,
         warning_check::SqlMigrationWarningCheck,
@@ -11,6 +11,7 @@ use crate::{
     sql_migration::{AlterColumn, ColumnTypeChange},
     sql_schema_differ::ColumnChanges,
 };
+use quaint::prelude::Value;
 use schema_connector::{BoxFuture, ConnectorResult};
 use sql_schema_describer::walkers::TableColumnWalker;
 
@@ -26,6 +27,7 @@ impl DestructiveChangeCheckerFlavour for MysqlFlavour {
             column_id: _,
             changes,
             type_change,
+            type_override: _,
         } = alter_column;
 
         // If only the default changed, the migration is safe.
@@ -133,7 +135,7 @@ impl DestructiveChangeCheckerFlavour for MysqlFlavour {
         let query = format!("SELECT COUNT(*) FROM `{}`", table.table);
 
         Box::pin(async move {
-            query_with_backoff(self, &query)
+            query_with_backoff(self, &query, &[])
                 .await
                 .and_then(|result_set| super::extract_table_rows_count(table, result_set))
         })
@@ -147,7 +149,24 @@ impl DestructiveChangeCheckerFlavour for MysqlFlavour {
         );
 
         Box::pin(async move {
-            query_with_backoff(self, &query)
+            query_with_backoff(self, &query, &[])
+                .await
+                .and_then(super::extract_column_values_count)
+        })
+    }
+
+    fn count_rows_with_enum_value<'a>(
+        &'a mut self,
+        column_value: &'a ColumnValue,
+    ) -> BoxFuture<'a, ConnectorResult<i64>> {
+        let query = format!(
+            "SELECT COUNT(*) FROM `{}` WHERE `{}` = ?",
+            column_value.column.table, column_value.column.column
+        );
+
+        Box::pin(async move {
+            let params = [Value::text(column_value.value.clone())];
+            query_with_backoff(self, &query, &params)
                 .await
                 .and_then(super::extract_column_values_count)
         })
@@ -159,9 +178,13 @@ impl DestructiveChangeCheckerFlavour for MysqlFlavour {
 /// This is necessary because destructive change checks can come after a migration, and _on
 /// Vitess_, schema changes are asynchronous, they can take time to take effect. That causes
 /// failures in destructive change checks. Trying again later, in this case, works.
-async fn query_with_backoff(flavour: &mut MysqlFlavour, query: &str) -> ConnectorResult<quaint::prelude::ResultSet> {
+async fn query_with_backoff<'a>(
+    flavour: &mut MysqlFlavour,
+    query: &str,
+    params: &[Value<'a>],
+) -> ConnectorResult<quaint::prelude::ResultSet> {
     let delay = std::time::Duration::from_millis(400);
-    let mut result = flavour.query_raw(query, &[]).await;
+    let mut result = flavour.query_raw(query, params).await;
 
     for i in 0..6 {
         match &result {
@@ -169,7 +192,7 @@ async fn query_with_backoff(flavour: &mut MysqlFlavour, query: &str) -> Connecto
             Err(_) => tokio::time::sleep(delay.saturating_mul(2 ^ i)).await,
         }
 
-        result = flavour.query_raw(query, &[]).await
+        result = flavour.query_raw(query, params).await
     }
 
     result
@@ -196,6 +219,11 @@ fn is_safe_enum_change(
                 SqlMigrationWarningCheck::EnumValueRemoval {
                     enm: next_enum.name().to_owned(),
                     values: removed_values,
+                    columns: vec![Column {
+                        table: columns.previous.table().name().to_owned(),
+                        namespace: columns.previous.table().namespace().map(str::to_owned),
+                        column: columns.previous.name().to_owned(),
+                    }],
                 },
                 step_index,
             );
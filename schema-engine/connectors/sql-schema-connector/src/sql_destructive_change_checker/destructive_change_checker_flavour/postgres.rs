@@ -3,7 +3,7 @@ use crate::{
     flavour::{PostgresFlavour, SqlFlavour},
     migration_pair::MigrationPair,
     sql_destructive_change_checker::{
-        check::{Column, Table},
+        check::{Column, ColumnValue, Table},
         destructive_check_plan::DestructiveCheckPlan,
         unexecutable_step_check::UnexecutableStepCheck,
         warning_check::SqlMigrationWarningCheck,
@@ -11,6 +11,7 @@ use crate::{
     sql_migration::{AlterColumn, ColumnTypeChange},
     sql_schema_differ::ColumnChanges,
 };
+use quaint::prelude::Value;
 use schema_connector::{BoxFuture, ConnectorResult};
 use sql_schema_describer::walkers::TableColumnWalker;
 
@@ -26,6 +27,7 @@ impl DestructiveChangeCheckerFlavour for PostgresFlavour {
             column_id: _,
             changes,
             type_change,
+            type_override: _,
         } = alter_column;
 
         if changes.arity_changed() && columns.previous.arity().is_nullable() && columns.next.arity().is_required() {
@@ -148,4 +150,20 @@ impl DestructiveChangeCheckerFlavour for PostgresFlavour {
             super::extract_column_values_count(result_set)
         })
     }
+
+    fn count_rows_with_enum_value<'a>(
+        &'a mut self,
+        column_value: &'a ColumnValue,
+    ) -> BoxFuture<'a, ConnectorResult<i64>> {
+        Box::pin(async move {
+            let ColumnValue { column, value } = column_value;
+            let from = match &column.namespace {
+                Some(namespace) => format!("\"{}\".\"{}\"", namespace, column.table),
+                None => format!("\"{}\"", column.table),
+            };
+            let query = format!("SELECT COUNT(*) FROM {} WHERE \"{}\"::text = $1", from, column.column);
+            let result_set = self.query_raw(&query, &[Value::text(value.clone())]).await?;
+            super::extract_column_values_count(result_set)
+        })
+    }
 }
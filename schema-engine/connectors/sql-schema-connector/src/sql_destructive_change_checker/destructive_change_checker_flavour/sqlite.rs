@@ -3,7 +3,7 @@ use crate::{
     flavour::{SqlFlavour, SqliteFlavour},
     migration_pair::MigrationPair,
     sql_destructive_change_checker::{
-        check::{Column, Table},
+        check::{Column, ColumnValue, Table},
         destructive_check_plan::DestructiveCheckPlan,
         unexecutable_step_check::UnexecutableStepCheck,
         warning_check::SqlMigrationWarningCheck,
@@ -97,4 +97,11 @@ impl DestructiveChangeCheckerFlavour for SqliteFlavour {
             super::extract_column_values_count(result_set)
         })
     }
+
+    fn count_rows_with_enum_value<'a>(
+        &'a mut self,
+        _column_value: &'a ColumnValue,
+    ) -> BoxFuture<'a, ConnectorResult<i64>> {
+        unreachable!("count_rows_with_enum_value on SQLite: AlterEnum is never emitted for this connector")
+    }
 }
@@ -3,11 +3,9 @@ use super::{
     unexecutable_step_check::UnexecutableStepCheck, warning_check::SqlMigrationWarningCheck,
 };
 use crate::flavour::SqlFlavour;
-use schema_connector::{
-    ConnectorError, ConnectorResult, DestructiveChangeDiagnostics, MigrationWarning, UnexecutableMigration,
-};
+use schema_connector::{ConnectorResult, DestructiveChangeDiagnostics, MigrationWarning, UnexecutableMigration};
 use std::time::Duration;
-use tokio::time::{error::Elapsed, timeout};
+use tokio::time::timeout;
 
 const DESTRUCTIVE_TIMEOUT_DURATION: Duration = Duration::from_secs(60);
 
@@ -49,21 +47,20 @@ impl DestructiveCheckPlan {
 
         let inspection = async {
             for (unexecutable, _idx) in &self.unexecutable_migrations {
-                self.inspect_for_check(unexecutable, flavour, &mut results).await?;
+                // A table or column a check needs may not exist yet on the inspected database
+                // (e.g. a migration that hasn't been applied there yet). When that happens, we
+                // skip the count rather than failing the whole plan: the check will fall back to
+                // its generic, count-less warning message.
+                self.inspect_for_check(unexecutable, flavour, &mut results).await.ok();
             }
 
             for (warning, _idx) in &self.warnings {
-                self.inspect_for_check(warning, flavour, &mut results).await?;
+                self.inspect_for_check(warning, flavour, &mut results).await.ok();
             }
-
-            Ok::<(), ConnectorError>(())
         };
 
         // Ignore the timeout error, we will still return useful warnings.
-        match timeout(DESTRUCTIVE_TIMEOUT_DURATION, inspection).await {
-            Ok(Ok(())) | Err(Elapsed { .. }) => (),
-            Ok(Err(err)) => return Err(err),
-        };
+        timeout(DESTRUCTIVE_TIMEOUT_DURATION, inspection).await.ok();
 
         let mut diagnostics = DestructiveChangeDiagnostics::new();
 
@@ -109,6 +106,13 @@ impl DestructiveCheckPlan {
             }
         }
 
+        for column_value in check.needed_enum_value_usage_counts() {
+            if results.get_enum_value_usage_count(&column_value).is_none() {
+                let count = flavour.count_rows_with_enum_value(&column_value).await?;
+                results.set_enum_value_usage_count(column_value, count);
+            }
+        }
+
         Ok(())
     }
 
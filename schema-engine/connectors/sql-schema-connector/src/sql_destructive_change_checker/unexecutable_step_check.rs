@@ -10,6 +10,7 @@ pub(crate) enum UnexecutableStepCheck {
     MadeOptionalFieldRequired(Column),
     MadeScalarFieldIntoArrayField(Column),
     DropAndRecreateRequiredColumn(Column),
+    DroppedAllColumnsFromTable(Table),
 }
 
 impl Check for UnexecutableStepCheck {
@@ -20,6 +21,7 @@ impl Check for UnexecutableStepCheck {
             | UnexecutableStepCheck::MadeScalarFieldIntoArrayField(column)
             | UnexecutableStepCheck::AddedRequiredFieldToTable(column)
             | UnexecutableStepCheck::DropAndRecreateRequiredColumn(column) => Some(Table::from_column(column)),
+            UnexecutableStepCheck::DroppedAllColumnsFromTable(_) => None,
         }
     }
 
@@ -29,7 +31,8 @@ impl Check for UnexecutableStepCheck {
             | UnexecutableStepCheck::MadeScalarFieldIntoArrayField(column) => Some(column.clone()),
             UnexecutableStepCheck::AddedRequiredFieldToTable { .. }
             | UnexecutableStepCheck::AddedRequiredFieldToTableWithPrismaLevelDefault { .. }
-            | UnexecutableStepCheck::DropAndRecreateRequiredColumn { .. } => None,
+            | UnexecutableStepCheck::DropAndRecreateRequiredColumn { .. }
+            | UnexecutableStepCheck::DroppedAllColumnsFromTable { .. } => None,
         }
     }
 
@@ -125,6 +128,31 @@ impl Check for UnexecutableStepCheck {
                     Some(_) => Some(format!("Changed the type of `{column}` on the `{table}` table. No cast exists, the column would be dropped and recreated, which cannot be done since the column is required and there is data in the table.", column = column.column, table = column.table)),
                 }
             }
+            UnexecutableStepCheck::DroppedAllColumnsFromTable(table) => Some(format!(
+                "The migration would drop every column of the `{table}` table. SQLite does not support tables without columns, add at least one column to the table or drop the table itself instead.",
+                table = table.table,
+            )),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropped_all_columns_from_table_is_always_unexecutable() {
+        let check = UnexecutableStepCheck::DroppedAllColumnsFromTable(Table {
+            table: "Cat".to_owned(),
+            namespace: None,
+        });
+
+        assert!(check.needed_table_row_count().is_none());
+        assert!(check.needed_column_value_count().is_none());
+
+        let message = check
+            .evaluate(&DatabaseInspectionResults::default())
+            .expect("dropping all columns is never executable on SQLite");
+        assert!(message.contains("Cat"));
+    }
+}
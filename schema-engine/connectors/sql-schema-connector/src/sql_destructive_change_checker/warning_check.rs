@@ -1,5 +1,5 @@
 use super::{
-    check::{Check, Column, Table},
+    check::{Check, Column, ColumnValue, Table},
     database_inspection_results::DatabaseInspectionResults,
 };
 
@@ -41,9 +41,19 @@ pub(super) enum SqlMigrationWarningCheck {
         table: String,
         columns: Vec<String>,
     },
+    CheckConstraintChange {
+        table: String,
+        namespace: Option<String>,
+    },
     EnumValueRemoval {
         enm: String,
         values: Vec<String>,
+        /// The columns using this enum, as it was before the migration. Used to check whether any
+        /// of `values` is still in use before warning.
+        columns: Vec<Column>,
+    },
+    DomainNotNullAddition {
+        domain: String,
     },
 }
 
@@ -95,6 +105,21 @@ impl Check for SqlMigrationWarningCheck {
         }
     }
 
+    fn needed_enum_value_usage_counts(&self) -> Vec<ColumnValue> {
+        match self {
+            SqlMigrationWarningCheck::EnumValueRemoval { values, columns, .. } => columns
+                .iter()
+                .flat_map(|column| {
+                    values.iter().map(|value| ColumnValue {
+                        column: column.clone(),
+                        value: value.clone(),
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     fn evaluate(&self, database_check_results: &DatabaseInspectionResults) -> Option<String> {
         match self {
             SqlMigrationWarningCheck::DropAndRecreateColumn { table, column, namespace } => {
@@ -160,7 +185,33 @@ impl Check for SqlMigrationWarningCheck {
             },
             SqlMigrationWarningCheck::UniqueConstraintAddition { table, columns } =>
                 Some(format!("A unique constraint covering the columns `[{columns}]` on the table `{table}` will be added. If there are existing duplicate values, this will fail.", table = table, columns = columns.join(","))),
-            SqlMigrationWarningCheck::EnumValueRemoval { enm, values } =>  Some(format!("The values [{values}] on the enum `{enm}` will be removed. If these variants are still used in the database, this will fail.", enm = enm, values = values.join(","))),
+            SqlMigrationWarningCheck::CheckConstraintChange { table, .. } =>
+                Some(format!("The `{table}` table is recreated to change its check constraints. If any existing row violates the new checks, this will fail.")),
+            SqlMigrationWarningCheck::EnumValueRemoval { enm, values, columns } => {
+                let mut still_used = Vec::new();
+                let mut all_checked = true;
+
+                for column in columns {
+                    for value in values {
+                        let column_value = ColumnValue { column: column.clone(), value: value.clone() };
+                        match database_check_results.get_enum_value_usage_count(&column_value) {
+                            Some(0) => (),
+                            Some(count) => still_used.push(format!("{}.{} ({count} row(s))", column.table, value)),
+                            None => all_checked = false,
+                        }
+                    }
+                }
+
+                if still_used.is_empty() && all_checked {
+                    // None of the removed values are currently used anywhere: safe to drop.
+                    None
+                } else if still_used.is_empty() {
+                    Some(format!("The values [{values}] on the enum `{enm}` will be removed. If these variants are still used in the database, this will fail.", enm = enm, values = values.join(",")))
+                } else {
+                    Some(format!("The values [{values}] on the enum `{enm}` will be removed, but are still used by: {usages}. This will fail unless those rows are migrated off the removed values first.", enm = enm, values = values.join(","), usages = still_used.join(", ")))
+                }
+            },
+            SqlMigrationWarningCheck::DomainNotNullAddition { domain } => Some(format!("The domain `{domain}` will be made `NOT NULL`. If any column using this domain contains a `NULL` value, this will fail.")),
 
         }
     }
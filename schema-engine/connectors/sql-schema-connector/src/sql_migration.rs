@@ -7,9 +7,12 @@ use enumflags2::BitFlags;
 use sql_schema_describer::{
     postgres::{self, PostgresSchemaExt},
     walkers::{TableColumnWalker, TableWalker},
-    EnumId, ForeignKeyId, IndexId, SqlSchema, TableColumnId, TableId, UdtId, ViewId,
+    EnumId, ForeignKeyId, IndexId, SqlSchema, TableColumnId, TableId, TriggerId, UdtId, ViewId,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
 };
-use std::{collections::BTreeSet, fmt::Write as _};
 
 /// The database migration type for SqlMigrationConnector.
 #[derive(Debug)]
@@ -24,6 +27,38 @@ impl SqlMigration {
         MigrationPair::new(&self.before, &self.after)
     }
 
+    /// Compute the down-migration: the steps that, applied on top of this migration, bring the
+    /// database back from `self.after` to `self.before`.
+    ///
+    /// Returns the descriptions of any forward steps that have no safe down-migration equivalent
+    /// instead of a partial result.
+    pub(crate) fn invert(&self) -> Result<Vec<SqlMigrationStep>, Vec<String>> {
+        crate::sql_migration_inverter::invert_steps(&self.steps, self.schemas())
+            .map_err(|irreversible| irreversible.into_iter().map(|step| step.description).collect())
+    }
+
+    /// The previous/next table id for every table present in either schema, paired by namespace
+    /// and name. A pair with both ids set was matched as the same table across the diff; one with
+    /// only `previous` set was dropped, and one with only `next` set was created. A renamed table
+    /// shows up as one dropped and one created table, not as a pair.
+    ///
+    /// This is a read-only view for diagnostics tooling: it pairs tables by namespace and name
+    /// directly off the two schemas, rather than reusing the differ's own `DifferDatabase`, so
+    /// flavour-specific quirks like case folding aren't applied here.
+    pub fn table_id_mapping(&self) -> Vec<(Option<TableId>, Option<TableId>)> {
+        let mut mapping: BTreeMap<(Option<&str>, &str), (Option<TableId>, Option<TableId>)> = BTreeMap::new();
+
+        for table in self.before.table_walkers() {
+            mapping.entry((table.namespace(), table.name())).or_default().0 = Some(table.id);
+        }
+
+        for table in self.after.table_walkers() {
+            mapping.entry((table.namespace(), table.name())).or_default().1 = Some(table.id);
+        }
+
+        mapping.into_values().collect()
+    }
+
     /// Exposed for tests.
     ///
     /// Rendering of the drift summary proceeds in two steps:
@@ -46,6 +81,16 @@ impl SqlMigration {
             AlteredExtension,
             DroppedExtension,
             CreatedExtension,
+            AlteredDomain,
+            DroppedDomain,
+            CreatedDomain,
+            AlteredPolicy,
+            DroppedPolicy,
+            CreatedPolicy,
+            ChangedRowLevelSecurity,
+            ChangedTableInheritance,
+            DroppedTrigger,
+            CreatedTrigger,
             AddedEnum,
             AddedTable,
             RemovedEnum,
@@ -65,6 +110,8 @@ impl SqlMigration {
             match step {
                 SqlMigrationStep::AlterSequence(_, _) => (),
                 SqlMigrationStep::CreateSchema(_) => (), // todo
+                SqlMigrationStep::DropSchema(_) => (), // todo
+                SqlMigrationStep::TruncateTable { .. } => (), // todo
                 SqlMigrationStep::DropView(drop_view) => {
                     drift_items.insert((
                         DriftType::RemovedView,
@@ -138,20 +185,29 @@ impl SqlMigration {
                         idx,
                     ));
                 }
+                SqlMigrationStep::AlterForeignKey { foreign_key_id } => {
+                    drift_items.insert((
+                        DriftType::ChangedTable,
+                        self.schemas().walk(*foreign_key_id).next.table().name(),
+                        idx,
+                    ));
+                }
                 SqlMigrationStep::CreateIndex {
                     table_id: (_, table_id),
                     ..
                 } => {
                     drift_items.insert((DriftType::ChangedTable, self.schemas().next.walk(*table_id).name(), idx));
                 }
-                SqlMigrationStep::AddForeignKey { foreign_key_id: id } => {
+                SqlMigrationStep::AddForeignKey { foreign_key_id: id, .. } => {
                     drift_items.insert((
                         DriftType::ChangedTable,
                         self.schemas().next.walk(*id).table().name(),
                         idx,
                     ));
                 }
-                SqlMigrationStep::RenameIndex { index } | SqlMigrationStep::RedefineIndex { index } => {
+                SqlMigrationStep::RenameIndex { index }
+                | SqlMigrationStep::RedefineIndex { index }
+                | SqlMigrationStep::AlterIndex { index } => {
                     drift_items.insert((
                         DriftType::ChangedTable,
                         self.schemas().walk(*index).previous.table().name(),
@@ -176,6 +232,80 @@ impl SqlMigration {
 
                     drift_items.insert((DriftType::DroppedExtension, &extension.name, idx));
                 }
+                SqlMigrationStep::CreateDomain(create_domain) => {
+                    let ext: &PostgresSchemaExt = self.schemas().next.downcast_connector_data();
+                    let domain = ext.get_domain(create_domain.id);
+
+                    drift_items.insert((DriftType::CreatedDomain, &domain.name, idx));
+                }
+                SqlMigrationStep::AlterDomain(alter_domain) => {
+                    let ext: &PostgresSchemaExt = self.schemas().previous.downcast_connector_data();
+                    let domain = ext.get_domain(alter_domain.ids.previous);
+
+                    drift_items.insert((DriftType::AlteredDomain, &domain.name, idx));
+                }
+                SqlMigrationStep::DropDomain(drop_domain) => {
+                    let ext: &PostgresSchemaExt = self.schemas().previous.downcast_connector_data();
+                    let domain = ext.get_domain(drop_domain.id);
+
+                    drift_items.insert((DriftType::DroppedDomain, &domain.name, idx));
+                }
+                SqlMigrationStep::CreatePolicy(create_policy) => {
+                    let ext: &PostgresSchemaExt = self.schemas().next.downcast_connector_data();
+                    let policy = ext.get_policy(create_policy.id);
+
+                    drift_items.insert((DriftType::CreatedPolicy, &policy.name, idx));
+                }
+                SqlMigrationStep::AlterPolicy(alter_policy) => {
+                    let ext: &PostgresSchemaExt = self.schemas().previous.downcast_connector_data();
+                    let policy = ext.get_policy(alter_policy.ids.previous);
+
+                    drift_items.insert((DriftType::AlteredPolicy, &policy.name, idx));
+                }
+                SqlMigrationStep::DropPolicy(drop_policy) => {
+                    let ext: &PostgresSchemaExt = self.schemas().previous.downcast_connector_data();
+                    let policy = ext.get_policy(drop_policy.id);
+
+                    drift_items.insert((DriftType::DroppedPolicy, &policy.name, idx));
+                }
+                SqlMigrationStep::EnableRowLevelSecurity { table_id } => {
+                    drift_items.insert((
+                        DriftType::ChangedRowLevelSecurity,
+                        self.schemas().next.walk(*table_id).name(),
+                        idx,
+                    ));
+                }
+                SqlMigrationStep::DisableRowLevelSecurity { table_id } => {
+                    drift_items.insert((
+                        DriftType::ChangedRowLevelSecurity,
+                        self.schemas().previous.walk(*table_id).name(),
+                        idx,
+                    ));
+                }
+                SqlMigrationStep::AddTableInheritance { table_id, .. } => {
+                    drift_items.insert((
+                        DriftType::ChangedTableInheritance,
+                        self.schemas().next.walk(*table_id).name(),
+                        idx,
+                    ));
+                }
+                SqlMigrationStep::DropTableInheritance { table_id, .. } => {
+                    drift_items.insert((
+                        DriftType::ChangedTableInheritance,
+                        self.schemas().previous.walk(*table_id).name(),
+                        idx,
+                    ));
+                }
+                SqlMigrationStep::CreateTrigger(create_trigger) => {
+                    let trigger = self.schemas().next.walk(create_trigger.id);
+
+                    drift_items.insert((DriftType::CreatedTrigger, trigger.name(), idx));
+                }
+                SqlMigrationStep::DropTrigger(drop_trigger) => {
+                    let trigger = self.schemas().previous.walk(drop_trigger.id);
+
+                    drift_items.insert((DriftType::DroppedTrigger, trigger.name(), idx));
+                }
             };
         }
 
@@ -221,6 +351,33 @@ impl SqlMigration {
                     DriftType::DroppedExtension => {
                         out.push_str("\n[-] Removed extensions\n`");
                     }
+                    DriftType::CreatedPolicy => {
+                        out.push_str("\n[+] Added policies\n");
+                    }
+                    DriftType::AlteredPolicy => {
+                        out.push_str("\n[*] Changed the `");
+                        out.push_str(item_name);
+                        out.push_str("` policy\n");
+                    }
+                    DriftType::DroppedPolicy => {
+                        out.push_str("\n[-] Removed policies\n");
+                    }
+                    DriftType::ChangedRowLevelSecurity => {
+                        out.push_str("\n[*] Changed row level security on the `");
+                        out.push_str(item_name);
+                        out.push_str("` table\n");
+                    }
+                    DriftType::ChangedTableInheritance => {
+                        out.push_str("\n[*] Changed table inheritance on the `");
+                        out.push_str(item_name);
+                        out.push_str("` table\n");
+                    }
+                    DriftType::CreatedTrigger => {
+                        out.push_str("\n[+] Added triggers\n");
+                    }
+                    DriftType::DroppedTrigger => {
+                        out.push_str("\n[-] Removed triggers\n");
+                    }
                 }
             }
 
@@ -236,6 +393,7 @@ impl SqlMigration {
                     out.push('\n');
                 }
                 SqlMigrationStep::CreateSchema(_) => {} // todo
+                SqlMigrationStep::DropSchema(_) => {} // todo
                 SqlMigrationStep::AlterEnum(alter_enum) => {
                     for added in &alter_enum.created_variants {
                         out.push_str("  [+] Added variant `");
@@ -283,6 +441,7 @@ impl SqlMigration {
                             TableChange::AddColumn {
                                 column_id,
                                 has_virtual_default: _,
+                                preceding_column: _,
                             } => {
                                 out.push_str("  [+] Added column `");
                                 out.push_str(self.schemas().next.walk(*column_id).name());
@@ -331,6 +490,53 @@ impl SqlMigration {
                                 out.push_str(")\n");
                                 out.push_str(")\n");
                             }
+                            TableChange::AlterTablePersistence { unlogged } => {
+                                out.push_str("  [*] Set table to ");
+                                out.push_str(if *unlogged { "UNLOGGED" } else { "LOGGED" });
+                                out.push('\n');
+                            }
+                            TableChange::AlterTableCollation { collation } => {
+                                out.push_str("  [*] Changed table collation to `");
+                                out.push_str(collation);
+                                out.push_str("`\n");
+                            }
+                            TableChange::AlterTableTablespace { tablespace } => {
+                                out.push_str("  [*] Changed table tablespace to `");
+                                out.push_str(tablespace);
+                                out.push_str("`\n");
+                            }
+                            TableChange::AddExclusionConstraint { constraint_name, .. } => {
+                                out.push_str("  [+] Added exclusion constraint `");
+                                out.push_str(constraint_name);
+                                out.push_str("`\n");
+                            }
+                            TableChange::DropExclusionConstraint { constraint_name } => {
+                                out.push_str("  [-] Removed exclusion constraint `");
+                                out.push_str(constraint_name);
+                                out.push_str("`\n");
+                            }
+                            TableChange::AddCheckConstraint { constraint_name, .. } => {
+                                out.push_str("  [+] Added check constraint `");
+                                out.push_str(constraint_name);
+                                out.push_str("`\n");
+                            }
+                            TableChange::DropCheckConstraint { constraint_name } => {
+                                out.push_str("  [-] Removed check constraint `");
+                                out.push_str(constraint_name);
+                                out.push_str("`\n");
+                            }
+                            TableChange::AddForeignKey { foreign_key_id, .. } => {
+                                let foreign_key = self.schemas().next.walk(*foreign_key_id);
+                                out.push_str("  [+] Added foreign key on columns (");
+                                out.push_str(&foreign_key.constrained_columns().map(|c| c.name()).join(", "));
+                                out.push_str(")\n");
+                            }
+                            TableChange::DropForeignKey { foreign_key_id } => {
+                                let foreign_key = self.schemas().previous.walk(*foreign_key_id);
+                                out.push_str("  [-] Removed foreign key on columns (");
+                                out.push_str(&foreign_key.constrained_columns().map(|c| c.name()).join(", "));
+                                out.push_str(")\n");
+                            }
                         }
                     }
                 }
@@ -362,6 +568,7 @@ impl SqlMigration {
                     table_id: _,
                     index_id,
                     from_drop_and_recreate: _,
+                    concurrently: _,
                 } => {
                     let index = self.schemas().next.walk(*index_id);
 
@@ -375,7 +582,7 @@ impl SqlMigration {
                     out.push_str(&index.column_names().join(", "));
                     out.push_str(")\n");
                 }
-                SqlMigrationStep::AddForeignKey { foreign_key_id } => {
+                SqlMigrationStep::AddForeignKey { foreign_key_id, .. } => {
                     let foreign_key = self.schemas().next.walk(*foreign_key_id);
                     out.push_str("  [+] Added foreign key on columns (");
                     out.push_str(&foreign_key.constrained_columns().map(|c| c.name()).join(", "));
@@ -397,6 +604,20 @@ impl SqlMigration {
                     out.push_str(index.previous.name());
                     out.push_str("`\n");
                 }
+                SqlMigrationStep::AlterIndex { index } => {
+                    let index = self.schemas().walk(*index);
+
+                    out.push_str("  [*] Changed the comment and/or tablespace on index `");
+                    out.push_str(index.previous.name());
+                    out.push_str("`\n");
+                }
+                SqlMigrationStep::AlterForeignKey { foreign_key_id } => {
+                    let fks = self.schemas().walk(*foreign_key_id);
+
+                    out.push_str("  [*] Changed the comment on the foreign key \"");
+                    out.push_str(fks.previous.constraint_name().unwrap());
+                    out.push_str("\"\n");
+                }
                 SqlMigrationStep::CreateExtension(create_extension) => {
                     let ext: &PostgresSchemaExt = self.schemas().next.downcast_connector_data();
                     out.push_str("  - ");
@@ -405,6 +626,57 @@ impl SqlMigration {
                 }
                 SqlMigrationStep::AlterExtension(_) => {}
                 SqlMigrationStep::DropExtension(_) => {}
+                SqlMigrationStep::CreateDomain(create_domain) => {
+                    let ext: &PostgresSchemaExt = self.schemas().next.downcast_connector_data();
+                    out.push_str("  - ");
+                    out.push_str(&ext.get_domain(create_domain.id).name);
+                    out.push('\n');
+                }
+                SqlMigrationStep::AlterDomain(_) => {}
+                SqlMigrationStep::DropDomain(_) => {}
+                SqlMigrationStep::CreatePolicy(create_policy) => {
+                    let ext: &PostgresSchemaExt = self.schemas().next.downcast_connector_data();
+                    out.push_str("  - ");
+                    out.push_str(&ext.get_policy(create_policy.id).name);
+                    out.push('\n');
+                }
+                SqlMigrationStep::AlterPolicy(_) => {}
+                SqlMigrationStep::DropPolicy(_) => {}
+                SqlMigrationStep::EnableRowLevelSecurity { .. } => {
+                    out.push_str("  [*] Enabled row level security\n");
+                }
+                SqlMigrationStep::DisableRowLevelSecurity { .. } => {
+                    out.push_str("  [*] Disabled row level security\n");
+                }
+                SqlMigrationStep::TruncateTable { table_id, cascade } => {
+                    out.push_str("  [*] Truncated table `");
+                    out.push_str(self.schemas().previous.walk(*table_id).name());
+                    out.push('`');
+                    if *cascade {
+                        out.push_str(" (cascading to referencing tables)");
+                    }
+                    out.push('\n');
+                }
+                SqlMigrationStep::AddTableInheritance { parent_table_id, .. } => {
+                    out.push_str("  [*] Now inherits from `");
+                    out.push_str(self.schemas().next.walk(*parent_table_id).name());
+                    out.push_str("`\n");
+                }
+                SqlMigrationStep::DropTableInheritance { parent_table_id, .. } => {
+                    out.push_str("  [*] No longer inherits from `");
+                    out.push_str(self.schemas().previous.walk(*parent_table_id).name());
+                    out.push_str("`\n");
+                }
+                SqlMigrationStep::CreateTrigger(create_trigger) => {
+                    out.push_str("  - ");
+                    out.push_str(self.schemas().next.walk(create_trigger.id).name());
+                    out.push('\n');
+                }
+                SqlMigrationStep::DropTrigger(drop_trigger) => {
+                    out.push_str("  - ");
+                    out.push_str(self.schemas().previous.walk(drop_trigger.id).name());
+                    out.push('\n');
+                }
             }
         }
 
@@ -434,6 +706,16 @@ fn render_column_changes(columns: MigrationPair<TableColumnWalker<'_>>, changes:
                     "column became autoincrementing".to_owned()
                 }
             }
+            ColumnChange::Storage => format!(
+                "storage changed from {:?} to {:?}",
+                columns.previous.toast_storage(),
+                columns.next.toast_storage()
+            ),
+            ColumnChange::NotNullConstraintName => format!(
+                "NOT NULL constraint name changed from {:?} to {:?}",
+                columns.previous.not_null_constraint_name(),
+                columns.next.not_null_constraint_name()
+            ),
         })
         .join(", ");
 
@@ -446,12 +728,20 @@ fn render_column_changes(columns: MigrationPair<TableColumnWalker<'_>>, changes:
 // by variant, then by the contents. Since the contents are mostly indexes in a
 // SqlSchema struct, the natural ordering of the indexes matches well with what
 // you would intuitively expect.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum SqlMigrationStep {
     CreateSchema(sql_schema_describer::NamespaceId),
     DropExtension(DropExtension),
     CreateExtension(CreateExtension),
     AlterExtension(AlterExtension),
+    DropDomain(DropDomain),
+    CreateDomain(CreateDomain),
+    AlterDomain(AlterDomain),
+    DropPolicy(DropPolicy),
+    AlterPolicy(AlterPolicy),
+    DisableRowLevelSecurity {
+        table_id: TableId,
+    },
     AlterSequence(MigrationPair<u32>, SequenceChanges),
     DropView(DropView),
     DropUserDefinedType(DropUserDefinedType),
@@ -463,6 +753,9 @@ pub(crate) enum SqlMigrationStep {
     DropIndex {
         index_id: IndexId,
     },
+    // Order matters: a trigger's body can reference a column, so it must be dropped before the
+    // table/column structure that might remove that column changes underneath it.
+    DropTrigger(DropTrigger),
     AlterTable(AlterTable),
     AlterPrimaryKey(MigrationPair<TableId>),
     // Order matters: we must drop tables before we create indexes,
@@ -478,24 +771,54 @@ pub(crate) enum SqlMigrationStep {
     // - We must drop enums after we drop tables, or dropping the enum will
     //   fail on postgres because objects (=tables) still depend on them.
     DropEnum(sql_schema_describer::EnumId),
+    // Order matters: this must come after we drop tables, since the tables contained in the
+    // schema (namespace) have to be gone before the schema itself can be dropped, and before we
+    // create tables, since the new tables might live in a schema we have not created yet.
+    DropSchema(sql_schema_describer::NamespaceId),
     CreateTable {
         table_id: TableId,
     },
+    // Order matters: enabling row-level security and creating a policy both reference the
+    // table by name, so they must come after CreateTable.
+    EnableRowLevelSecurity {
+        table_id: TableId,
+    },
+    CreatePolicy(CreatePolicy),
     RedefineTables(Vec<RedefineTable>),
+    // Order matters: Postgres only lets a table INHERIT a parent once their shared columns
+    // already have matching types, so this must come after the table and column structure (
+    // CreateTable, AlterTable, RedefineTables) is in place.
+    AddTableInheritance {
+        table_id: TableId,
+        parent_table_id: TableId,
+    },
+    DropTableInheritance {
+        table_id: TableId,
+        parent_table_id: TableId,
+    },
     // Order matters: we must create indexes after ALTER TABLEs because the indexes can be
     // on fields that are dropped/created there.
     CreateIndex {
         table_id: (Option<TableId>, TableId),
         index_id: IndexId,
         from_drop_and_recreate: bool,
+        /// Render with `CREATE INDEX CONCURRENTLY`, where the flavour supports it. Set from
+        /// `DifferSettings::concurrent_index_creation` at diff time. A concurrently created index
+        /// can't run inside the migration's transaction; see
+        /// [`SqlMigrationStep::requires_separate_transaction`].
+        concurrently: bool,
     },
     RenameForeignKey {
         foreign_key_id: MigrationPair<ForeignKeyId>,
     },
     // Order matters: this needs to come after create_indexes, because the foreign keys can depend on unique
-    // indexes created there.
+    // indexes created there. `DifferSettings::defer_foreign_keys` additionally moves every
+    // `AddForeignKey` step, wherever it originally sorted, to the very end of the step list.
     AddForeignKey {
         foreign_key_id: ForeignKeyId,
+        /// Whether this foreign key should only be checked at transaction commit, where the
+        /// flavour supports it. Set from `DifferSettings::defer_foreign_keys` at diff time.
+        deferred: bool,
     },
     RenameIndex {
         index: MigrationPair<IndexId>,
@@ -503,9 +826,37 @@ pub(crate) enum SqlMigrationStep {
     RedefineIndex {
         index: MigrationPair<IndexId>,
     },
+    /// Change the comment and/or the tablespace of an index, where the flavour supports object
+    /// comments (currently PostgreSQL only) or tablespaces (currently PostgreSQL only).
+    AlterIndex {
+        index: MigrationPair<IndexId>,
+    },
+    /// Change the comment on a foreign key constraint, where the flavour supports object
+    /// comments (currently PostgreSQL only).
+    AlterForeignKey {
+        foreign_key_id: MigrationPair<ForeignKeyId>,
+    },
+    // Order matters: a created trigger's body can reference the final table/column/index
+    // structure, so it must come after that structure is in place.
+    CreateTrigger(CreateTrigger),
+    /// Empty a table. Never produced by the differ: truncation is always intentional, so it is
+    /// only ever added to a migration through [`SqlMigrationStep::truncate_table`].
+    TruncateTable { table_id: TableId, cascade: bool },
 }
 
 impl SqlMigrationStep {
+    /// Build a step that empties `table_id` when applied. This is the only way to get a
+    /// `TruncateTable` step: the differ never infers truncation from a diff, since it is
+    /// destructive in a way that must be requested explicitly.
+    ///
+    /// `cascade` additionally truncates tables with foreign keys to `table_id`, where the
+    /// flavour supports it (currently PostgreSQL only); on flavours without native cascading
+    /// truncation, the caller is responsible for ordering truncations so that referencing tables
+    /// are truncated first.
+    pub(crate) fn truncate_table(table_id: TableId, cascade: bool) -> Self {
+        SqlMigrationStep::TruncateTable { table_id, cascade }
+    }
+
     pub(crate) fn description(&self) -> &'static str {
         match self {
             SqlMigrationStep::AddForeignKey { .. } => "AddForeignKey",
@@ -520,6 +871,7 @@ impl SqlMigrationStep {
             SqlMigrationStep::DropEnum(_) => "DropEnum",
             SqlMigrationStep::DropForeignKey { .. } => "DropForeignKey",
             SqlMigrationStep::DropIndex { .. } => "DropIndex",
+            SqlMigrationStep::DropSchema(_) => "DropSchema",
             SqlMigrationStep::DropTable { .. } => "DropTable",
             SqlMigrationStep::DropUserDefinedType(_) => "DropUserDefinedType",
             SqlMigrationStep::DropView(_) => "DropView",
@@ -527,46 +879,331 @@ impl SqlMigrationStep {
             SqlMigrationStep::RedefineTables { .. } => "RedefineTables",
             SqlMigrationStep::RenameForeignKey { .. } => "RenameForeignKey",
             SqlMigrationStep::RenameIndex { .. } => "RenameIndex",
+            SqlMigrationStep::AlterIndex { .. } => "AlterIndex",
+            SqlMigrationStep::AlterForeignKey { .. } => "AlterForeignKey",
             SqlMigrationStep::CreateExtension(_) => "CreateExtension",
             SqlMigrationStep::AlterExtension(_) => "AlterExtension",
             SqlMigrationStep::DropExtension(_) => "DropExtension",
+            SqlMigrationStep::CreateDomain(_) => "CreateDomain",
+            SqlMigrationStep::AlterDomain(_) => "AlterDomain",
+            SqlMigrationStep::DropDomain(_) => "DropDomain",
+            SqlMigrationStep::CreatePolicy(_) => "CreatePolicy",
+            SqlMigrationStep::AlterPolicy(_) => "AlterPolicy",
+            SqlMigrationStep::DropPolicy(_) => "DropPolicy",
+            SqlMigrationStep::EnableRowLevelSecurity { .. } => "EnableRowLevelSecurity",
+            SqlMigrationStep::DisableRowLevelSecurity { .. } => "DisableRowLevelSecurity",
+            SqlMigrationStep::TruncateTable { .. } => "TruncateTable",
+            SqlMigrationStep::AddTableInheritance { .. } => "AddTableInheritance",
+            SqlMigrationStep::DropTableInheritance { .. } => "DropTableInheritance",
+            SqlMigrationStep::CreateTrigger(_) => "CreateTrigger",
+            SqlMigrationStep::DropTrigger(_) => "DropTrigger",
+        }
+    }
+
+    /// Whether this step must be applied outside of the migration's transaction, for callers
+    /// (e.g. an external migration orchestrator) that wrap steps in a transaction by default. A
+    /// `CREATE INDEX CONCURRENTLY` cannot run inside a transaction block on PostgreSQL.
+    pub(crate) fn requires_separate_transaction(&self) -> bool {
+        matches!(self, SqlMigrationStep::CreateIndex { concurrently: true, .. })
+    }
+
+    /// Whether applying this step could delete or corrupt existing data: dropped tables,
+    /// truncations, dropped columns, dropped primary keys, not-castable column recreates, narrowing
+    /// (risky or not-castable) column type changes, removed enum variants, and the equivalents of
+    /// those inside a `RedefineTables`, where changed check constraints also count.
+    /// Used by [`partition_steps`] to split a migration into a safe set and a set that needs
+    /// extra review before it is applied.
+    pub(crate) fn is_destructive(&self) -> bool {
+        match self {
+            SqlMigrationStep::DropTable { .. } | SqlMigrationStep::TruncateTable { .. } => true,
+            SqlMigrationStep::AlterTable(AlterTable { changes, .. }) => {
+                changes.iter().any(TableChange::is_destructive)
+            }
+            SqlMigrationStep::RedefineTables(redefines) => redefines.iter().any(RedefineTable::is_destructive),
+            SqlMigrationStep::AlterEnum(alter_enum) => !alter_enum.dropped_variants.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// The tables this step reads or modifies, for callers (e.g. an external migration
+    /// orchestrator) that need to acquire the right locks, or check grants, before applying it.
+    /// `schemas` must be the same before/after pair the step was generated from. Each returned
+    /// `TableId` is scoped to whichever of the two schemas the step actually addresses that table
+    /// through — the same schema `render_raw_sql` would walk it in to render the step's SQL — so a
+    /// step that only exists on one side (e.g. `DropTable`, `CreateTable`) returns an id scoped to
+    /// that side only.
+    pub(crate) fn affected_tables(&self, schemas: MigrationPair<&SqlSchema>) -> Vec<TableId> {
+        match self {
+            SqlMigrationStep::CreateSchema(_)
+            | SqlMigrationStep::AlterSequence(_, _)
+            | SqlMigrationStep::CreateEnum(_)
+            | SqlMigrationStep::DropEnum(_)
+            | SqlMigrationStep::DropSchema(_)
+            | SqlMigrationStep::DropView(_)
+            | SqlMigrationStep::DropUserDefinedType(_)
+            | SqlMigrationStep::CreateExtension(_)
+            | SqlMigrationStep::AlterExtension(_)
+            | SqlMigrationStep::DropExtension(_)
+            | SqlMigrationStep::CreateDomain(_)
+            | SqlMigrationStep::AlterDomain(_)
+            | SqlMigrationStep::DropDomain(_)
+            | SqlMigrationStep::CreatePolicy(_)
+            | SqlMigrationStep::AlterPolicy(_)
+            | SqlMigrationStep::DropPolicy(_) => Vec::new(),
+            SqlMigrationStep::EnableRowLevelSecurity { table_id } => vec![*table_id],
+            SqlMigrationStep::DisableRowLevelSecurity { table_id } => vec![*table_id],
+            SqlMigrationStep::TruncateTable { table_id, .. } => vec![*table_id],
+            SqlMigrationStep::AddTableInheritance {
+                table_id,
+                parent_table_id,
+            }
+            | SqlMigrationStep::DropTableInheritance {
+                table_id,
+                parent_table_id,
+            } => vec![*table_id, *parent_table_id],
+            // Every column that had the enum as its default has that default dropped, and
+            // reinstalled where the next column still has one: the next column's table is
+            // returned where there is one, the previous column's table otherwise.
+            SqlMigrationStep::AlterEnum(alter_enum) => alter_enum
+                .previous_usages_as_default
+                .iter()
+                .map(|(previous_column_id, next_column_id)| match next_column_id {
+                    Some(next_column_id) => schemas.next.walk(*next_column_id).table().id,
+                    None => schemas.previous.walk(*previous_column_id).table().id,
+                })
+                .collect(),
+            SqlMigrationStep::DropForeignKey { foreign_key_id } => {
+                let fk = schemas.previous.walk(*foreign_key_id);
+                vec![fk.table().id, fk.referenced_table().id]
+            }
+            SqlMigrationStep::DropIndex { index_id } => vec![schemas.previous.walk(*index_id).table().id],
+            SqlMigrationStep::AlterTable(alter_table) => vec![alter_table.table_ids.next],
+            SqlMigrationStep::AlterPrimaryKey(table_ids) => vec![table_ids.next],
+            SqlMigrationStep::DropTable { table_id } => vec![*table_id],
+            SqlMigrationStep::CreateTable { table_id } => vec![*table_id],
+            SqlMigrationStep::RedefineTables(redefine_tables) => {
+                redefine_tables.iter().map(|table| table.table_ids.next).collect()
+            }
+            SqlMigrationStep::CreateIndex { index_id, .. } => vec![schemas.next.walk(*index_id).table().id],
+            SqlMigrationStep::RenameForeignKey { foreign_key_id } => {
+                let fk = schemas.next.walk(foreign_key_id.next);
+                vec![fk.table().id, fk.referenced_table().id]
+            }
+            SqlMigrationStep::AddForeignKey { foreign_key_id, .. } => {
+                let fk = schemas.next.walk(*foreign_key_id);
+                vec![fk.table().id, fk.referenced_table().id]
+            }
+            SqlMigrationStep::RenameIndex { index } => vec![schemas.walk(*index).next.table().id],
+            SqlMigrationStep::RedefineIndex { index } => vec![schemas.walk(*index).next.table().id],
+            SqlMigrationStep::AlterIndex { index } => vec![schemas.walk(*index).next.table().id],
+            SqlMigrationStep::AlterForeignKey { foreign_key_id } => {
+                let fk = schemas.next.walk(foreign_key_id.next);
+                vec![fk.table().id, fk.referenced_table().id]
+            }
+            SqlMigrationStep::CreateTrigger(create_trigger) => {
+                vec![schemas.next.walk(create_trigger.id).table().id]
+            }
+            SqlMigrationStep::DropTrigger(drop_trigger) => {
+                vec![schemas.previous.walk(drop_trigger.id).table().id]
+            }
         }
     }
+
+    /// The name of the single table this step is primarily about, for [`steps_by_table`].
+    /// Returns `None` for steps that are not naturally about one table — enum, extension,
+    /// policy and schema changes — and for `RedefineTables`, which can batch several tables
+    /// into one step and so has no single primary table.
+    fn primary_table_name<'a>(&self, schemas: MigrationPair<&'a SqlSchema>) -> Option<&'a str> {
+        Some(match self {
+            SqlMigrationStep::CreateSchema(_)
+            | SqlMigrationStep::DropSchema(_)
+            | SqlMigrationStep::AlterSequence(_, _)
+            | SqlMigrationStep::DropView(_)
+            | SqlMigrationStep::DropUserDefinedType(_)
+            | SqlMigrationStep::CreateEnum(_)
+            | SqlMigrationStep::AlterEnum(_)
+            | SqlMigrationStep::DropEnum(_)
+            | SqlMigrationStep::CreateExtension(_)
+            | SqlMigrationStep::AlterExtension(_)
+            | SqlMigrationStep::DropExtension(_)
+            | SqlMigrationStep::CreateDomain(_)
+            | SqlMigrationStep::AlterDomain(_)
+            | SqlMigrationStep::DropDomain(_)
+            | SqlMigrationStep::CreatePolicy(_)
+            | SqlMigrationStep::AlterPolicy(_)
+            | SqlMigrationStep::DropPolicy(_)
+            | SqlMigrationStep::RedefineTables(_) => return None,
+            SqlMigrationStep::EnableRowLevelSecurity { table_id } => schemas.next.walk(*table_id).name(),
+            SqlMigrationStep::DisableRowLevelSecurity { table_id } => schemas.previous.walk(*table_id).name(),
+            SqlMigrationStep::TruncateTable { table_id, .. } => schemas.next.walk(*table_id).name(),
+            SqlMigrationStep::AddTableInheritance { table_id, .. } => schemas.next.walk(*table_id).name(),
+            SqlMigrationStep::DropTableInheritance { table_id, .. } => schemas.previous.walk(*table_id).name(),
+            SqlMigrationStep::DropForeignKey { foreign_key_id } => {
+                schemas.previous.walk(*foreign_key_id).table().name()
+            }
+            SqlMigrationStep::DropIndex { index_id } => schemas.previous.walk(*index_id).table().name(),
+            SqlMigrationStep::AlterTable(alter_table) => schemas.previous.walk(alter_table.table_ids.previous).name(),
+            SqlMigrationStep::AlterPrimaryKey(table_ids) => schemas.previous.walk(table_ids.previous).name(),
+            SqlMigrationStep::DropTable { table_id } => schemas.previous.walk(*table_id).name(),
+            SqlMigrationStep::CreateTable { table_id } => schemas.next.walk(*table_id).name(),
+            SqlMigrationStep::CreateIndex { index_id, .. } => schemas.next.walk(*index_id).table().name(),
+            SqlMigrationStep::RenameForeignKey { foreign_key_id } => {
+                schemas.next.walk(foreign_key_id.next).table().name()
+            }
+            SqlMigrationStep::AddForeignKey { foreign_key_id, .. } => schemas.next.walk(*foreign_key_id).table().name(),
+            SqlMigrationStep::RenameIndex { index } => schemas.walk(*index).next.table().name(),
+            SqlMigrationStep::RedefineIndex { index } => schemas.walk(*index).next.table().name(),
+            SqlMigrationStep::AlterIndex { index } => schemas.walk(*index).next.table().name(),
+            SqlMigrationStep::AlterForeignKey { foreign_key_id } => {
+                schemas.next.walk(foreign_key_id.next).table().name()
+            }
+            SqlMigrationStep::CreateTrigger(create_trigger) => schemas.next.walk(create_trigger.id).table().name(),
+            SqlMigrationStep::DropTrigger(drop_trigger) => schemas.previous.walk(drop_trigger.id).table().name(),
+        })
+    }
+}
+
+/// Group migration steps by the table they primarily affect, for callers (e.g. a UI) that want
+/// to present a diff per model rather than as a flat list. Steps that are not about a single
+/// table — enum, extension, policy and schema changes — are grouped under the empty-string key.
+///
+/// A `RedefineTables` step can batch several tables into one step; it is listed under each of
+/// the tables it touches, so that a per-table view shows it regardless of which table led the
+/// caller there. A step that merely touches a second table in passing (e.g. `AddForeignKey`
+/// also locks the referenced table) is listed only under its primary table; see
+/// [`SqlMigrationStep::affected_tables`] for the full set of tables a step touches.
+pub(crate) fn steps_by_table<'a>(
+    steps: &'a [SqlMigrationStep],
+    schemas: MigrationPair<&SqlSchema>,
+) -> BTreeMap<String, Vec<&'a SqlMigrationStep>> {
+    let mut grouped: BTreeMap<String, Vec<&'a SqlMigrationStep>> = BTreeMap::new();
+
+    for step in steps {
+        match step {
+            SqlMigrationStep::RedefineTables(redefines) => {
+                for redefine in redefines {
+                    let name = schemas.next.walk(redefine.table_ids.next).name().to_owned();
+                    grouped.entry(name).or_default().push(step);
+                }
+            }
+            _ => {
+                let name = step.primary_table_name(schemas).unwrap_or("").to_owned();
+                grouped.entry(name).or_default().push(step);
+            }
+        }
+    }
+
+    grouped
+}
+
+/// Split `steps` into a safe set and a set containing only the steps classified as destructive
+/// by [`SqlMigrationStep::is_destructive`] — dropped tables, truncations, dropped columns and
+/// primary keys, not-castable column recreates, narrowing column type changes, and removed enum
+/// variants. Intended for review-gating
+/// pipelines that want to require extra sign-off before applying anything that could delete or
+/// corrupt data, without blocking the rest of a migration on it.
+pub(crate) fn partition_steps(steps: &[SqlMigrationStep]) -> (Vec<&SqlMigrationStep>, Vec<&SqlMigrationStep>) {
+    let (destructive, safe): (Vec<_>, Vec<_>) = steps.iter().partition(|step| step.is_destructive());
+    (safe, destructive)
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct AlterExtension {
     pub ids: MigrationPair<postgres::ExtensionId>,
     pub changes: Vec<ExtensionChange>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct CreateExtension {
     pub id: postgres::ExtensionId,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct DropExtension {
     pub id: postgres::ExtensionId,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum ExtensionChange {
     AlterVersion,
     AlterSchema,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct AlterDomain {
+    pub ids: MigrationPair<postgres::DomainId>,
+    pub changes: Vec<DomainChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct CreateDomain {
+    pub id: postgres::DomainId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct DropDomain {
+    pub id: postgres::DomainId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum DomainChange {
+    AlterBaseType,
+    AlterNotNull,
+    AlterDefault,
+    AlterCheck,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct CreateTrigger {
+    pub id: TriggerId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct DropTrigger {
+    pub id: TriggerId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct CreatePolicy {
+    pub id: postgres::PolicyId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct DropPolicy {
+    pub id: postgres::PolicyId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct AlterPolicy {
+    pub ids: MigrationPair<postgres::PolicyId>,
+    pub changes: Vec<PolicyChange>,
+}
+
+/// A change to a policy that can be expressed with `ALTER POLICY`, without dropping and
+/// recreating it. A changed `permissive` or `command` has no `ALTER POLICY` equivalent, so those
+/// are modeled as a [`DropPolicy`] followed by a [`CreatePolicy`] instead.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum PolicyChange {
+    Roles,
+    Using,
+    WithCheck,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct AlterTable {
     pub table_ids: MigrationPair<TableId>,
     pub changes: Vec<TableChange>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum TableChange {
     AddColumn {
         column_id: TableColumnId,
         has_virtual_default: bool,
+        /// The column that should immediately precede this one once added. Only honored by
+        /// connectors that support positioning newly added columns (currently MySQL, via
+        /// `ADD COLUMN ... AFTER`/`FIRST`); other connectors always append at the end.
+        preceding_column: Option<TableColumnId>,
     },
     AlterColumn(AlterColumn),
     DropColumn {
@@ -580,6 +1217,88 @@ pub(crate) enum TableChange {
     DropPrimaryKey,
     AddPrimaryKey,
     RenamePrimaryKey,
+    /// Postgres-only: add an `EXCLUDE` constraint. Gated behind
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::push_exclusion_constraint_changes`].
+    AddExclusionConstraint {
+        constraint_name: String,
+        /// The full constraint definition, e.g. `EXCLUDE USING gist (...)`, as returned by
+        /// `pg_get_constraintdef`.
+        definition: String,
+    },
+    /// Postgres-only: drop an `EXCLUDE` constraint.
+    DropExclusionConstraint {
+        constraint_name: String,
+    },
+    /// Add a `CHECK` constraint restricting a column to an enum's variants. Only emitted by
+    /// flavours where
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::emulates_enums_as_check_constraints`]
+    /// is true.
+    AddCheckConstraint {
+        constraint_name: String,
+        /// The full constraint definition, e.g. `CHECK ("status" IN ('ACTIVE', 'DONE'))`.
+        definition: String,
+    },
+    /// Drop a `CHECK` constraint previously added for an enum column. See
+    /// [`Self::AddCheckConstraint`].
+    DropCheckConstraint {
+        constraint_name: String,
+    },
+    /// Postgres-only: `ALTER TABLE ... SET LOGGED/UNLOGGED`. Gated behind
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::push_table_persistence_changes`].
+    AlterTablePersistence {
+        unlogged: bool,
+    },
+    /// MySQL-only: `ALTER TABLE ... COLLATE ...`, changing the table's default collation. Gated
+    /// behind [`crate::sql_schema_differ::SqlSchemaDifferFlavour::push_table_collation_changes`].
+    AlterTableCollation {
+        collation: String,
+    },
+    /// Postgres-only: `ALTER TABLE ... SET TABLESPACE ...`. Gated behind
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::push_table_tablespace_changes`].
+    AlterTableTablespace {
+        tablespace: String,
+    },
+    /// Add a foreign key inline as part of the table's own `AlterTable` step, rather than as a
+    /// separate [`SqlMigrationStep::AddForeignKey`]. Only emitted when
+    /// [`crate::sql_schema_differ::differ_database::DifferSettings::coalesce_foreign_keys_into_alter_table`]
+    /// is set and the flavour supports rendering multiple `ALTER TABLE` clauses at once.
+    AddForeignKey {
+        foreign_key_id: ForeignKeyId,
+        deferred: bool,
+    },
+    /// Drop a foreign key inline as part of the table's own `AlterTable` step. See
+    /// [`Self::AddForeignKey`].
+    DropForeignKey {
+        foreign_key_id: ForeignKeyId,
+    },
+}
+
+impl TableChange {
+    /// Whether applying this change could delete or corrupt existing data. See
+    /// [`SqlMigrationStep::is_destructive`].
+    fn is_destructive(&self) -> bool {
+        match self {
+            TableChange::DropColumn { .. }
+            | TableChange::DropAndRecreateColumn { .. }
+            | TableChange::DropPrimaryKey => true,
+            TableChange::AlterColumn(AlterColumn { type_change, .. }) => matches!(
+                type_change,
+                Some(ColumnTypeChange::RiskyCast) | Some(ColumnTypeChange::NotCastable)
+            ),
+            TableChange::AddColumn { .. }
+            | TableChange::AddPrimaryKey
+            | TableChange::RenamePrimaryKey
+            | TableChange::AddExclusionConstraint { .. }
+            | TableChange::DropExclusionConstraint { .. }
+            | TableChange::AddCheckConstraint { .. }
+            | TableChange::DropCheckConstraint { .. }
+            | TableChange::AlterTablePersistence { .. }
+            | TableChange::AlterTableCollation { .. }
+            | TableChange::AlterTableTablespace { .. }
+            | TableChange::AddForeignKey { .. }
+            | TableChange::DropForeignKey { .. } => false,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -604,21 +1323,25 @@ impl DropUserDefinedType {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct AlterColumn {
     pub column_id: MigrationPair<TableColumnId>,
     pub changes: ColumnChanges,
     pub type_change: Option<ColumnTypeChange>,
+    /// A type to render instead of the next column's own native type, set from
+    /// [`crate::sql_schema_differ::DifferSettings::type_overrides`] at diff time. Only honored by
+    /// the PostgreSQL renderer so far.
+    pub type_override: Option<String>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum ColumnTypeChange {
     RiskyCast,
     SafeCast,
     NotCastable,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct AlterEnum {
     pub id: MigrationPair<EnumId>,
     pub created_variants: Vec<String>,
@@ -636,7 +1359,7 @@ impl AlterEnum {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct RedefineTable {
     pub added_columns: Vec<TableColumnId>,
     pub added_columns_with_virtual_defaults: Vec<TableColumnId>,
@@ -644,6 +1367,27 @@ pub(crate) struct RedefineTable {
     pub dropped_primary_key: bool,
     pub column_pairs: Vec<(MigrationPair<TableColumnId>, ColumnChanges, Option<ColumnTypeChange>)>,
     pub table_ids: MigrationPair<TableId>,
+    /// Whether the table's named check constraints changed, forcing the redefine to carry the new
+    /// checks over. Existing rows could already violate a check they previously didn't have to
+    /// satisfy, which is why this is surfaced as a destructive-change warning rather than applied
+    /// silently.
+    pub checks_changed: bool,
+}
+
+impl RedefineTable {
+    /// Whether this redefine could delete or corrupt existing data. See
+    /// [`SqlMigrationStep::is_destructive`].
+    fn is_destructive(&self) -> bool {
+        !self.dropped_columns.is_empty()
+            || self.dropped_primary_key
+            || self.checks_changed
+            || self.column_pairs.iter().any(|(_, _, type_change)| {
+                matches!(
+                    type_change,
+                    Some(ColumnTypeChange::RiskyCast) | Some(ColumnTypeChange::NotCastable)
+                )
+            })
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -681,3 +1425,223 @@ fn render_primary_key_column_names(table: TableWalker<'_>, out: &mut String) {
         .join(", ");
     out.push_str(&cols);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sql_schema_describer::ForeignKeyAction;
+
+    #[test]
+    fn table_id_mapping_covers_a_mixed_diff() {
+        let mut previous = SqlSchema::default();
+        let dropped = previous.push_table("dropped".to_owned(), Default::default(), None);
+        let kept_before = previous.push_table("kept".to_owned(), Default::default(), None);
+
+        let mut next = SqlSchema::default();
+        let kept_after = next.push_table("kept".to_owned(), Default::default(), None);
+        let created = next.push_table("created".to_owned(), Default::default(), None);
+
+        let migration = SqlMigration {
+            before: previous,
+            after: next,
+            steps: Vec::new(),
+        };
+
+        let mut mapping = migration.table_id_mapping();
+        mapping.sort();
+
+        assert_eq!(
+            mapping,
+            vec![
+                (None, Some(created)),
+                (Some(dropped), None),
+                (Some(kept_before), Some(kept_after)),
+            ]
+        );
+    }
+
+    #[test]
+    fn affected_tables_for_create_and_drop_table() {
+        let mut next = SqlSchema::default();
+        let table_id = next.push_table("a".to_owned(), Default::default(), None);
+        let previous = SqlSchema::default();
+        let schemas = MigrationPair::new(&previous, &next);
+
+        assert_eq!(
+            vec![table_id],
+            SqlMigrationStep::CreateTable { table_id }.affected_tables(schemas)
+        );
+
+        let mut previous = SqlSchema::default();
+        let table_id = previous.push_table("a".to_owned(), Default::default(), None);
+        let next = SqlSchema::default();
+        let schemas = MigrationPair::new(&previous, &next);
+
+        assert_eq!(
+            vec![table_id],
+            SqlMigrationStep::DropTable { table_id }.affected_tables(schemas)
+        );
+    }
+
+    #[test]
+    fn affected_tables_for_add_foreign_key_touching_two_tables() {
+        let mut next = SqlSchema::default();
+        let a = next.push_table("a".to_owned(), Default::default(), None);
+        let b = next.push_table("b".to_owned(), Default::default(), None);
+        let foreign_key_id =
+            next.push_foreign_key(None, [a, b], [ForeignKeyAction::NoAction, ForeignKeyAction::NoAction]);
+        let previous = SqlSchema::default();
+        let schemas = MigrationPair::new(&previous, &next);
+
+        assert_eq!(
+            vec![a, b],
+            SqlMigrationStep::AddForeignKey {
+                foreign_key_id,
+                deferred: false,
+            }
+            .affected_tables(schemas)
+        );
+    }
+
+    #[test]
+    fn affected_tables_for_a_self_referencing_foreign_key() {
+        let mut next = SqlSchema::default();
+        let a = next.push_table("a".to_owned(), Default::default(), None);
+        let foreign_key_id =
+            next.push_foreign_key(None, [a, a], [ForeignKeyAction::NoAction, ForeignKeyAction::NoAction]);
+        let previous = SqlSchema::default();
+        let schemas = MigrationPair::new(&previous, &next);
+
+        // Both the owning and the referenced table are the same table here, but we still report
+        // it once per role rather than silently deduplicating: a caller that wants to lock it
+        // once can dedupe on its end, but collapsing it here would hide the fact that the step
+        // treats the table in two distinct roles.
+        assert_eq!(
+            vec![a, a],
+            SqlMigrationStep::AddForeignKey {
+                foreign_key_id,
+                deferred: false,
+            }
+            .affected_tables(schemas)
+        );
+    }
+
+    #[test]
+    fn steps_by_table_groups_a_mixed_diff() {
+        let mut previous = SqlSchema::default();
+        let prev_b = previous.push_table("b".to_owned(), Default::default(), None);
+        let prev_c = previous.push_table("c".to_owned(), Default::default(), None);
+
+        let mut next = SqlSchema::default();
+        let a = next.push_table("a".to_owned(), Default::default(), None);
+        let b = next.push_table("b".to_owned(), Default::default(), None);
+        let c = next.push_table("c".to_owned(), Default::default(), None);
+        let enum_id = next.push_enum(Default::default(), "Color".to_owned(), None);
+
+        let redefine_table = |table_ids| RedefineTable {
+            added_columns: Vec::new(),
+            added_columns_with_virtual_defaults: Vec::new(),
+            dropped_columns: Vec::new(),
+            dropped_primary_key: false,
+            column_pairs: Vec::new(),
+            table_ids,
+            checks_changed: false,
+        };
+
+        let steps = vec![
+            SqlMigrationStep::CreateTable { table_id: a },
+            SqlMigrationStep::CreateEnum(enum_id),
+            // A single RedefineTables step spanning two tables at once: it must show up under
+            // both, not just the first.
+            SqlMigrationStep::RedefineTables(vec![
+                redefine_table(MigrationPair::new(prev_b, b)),
+                redefine_table(MigrationPair::new(prev_c, c)),
+            ]),
+        ];
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let grouped = steps_by_table(&steps, schemas);
+
+        assert_eq!(grouped.keys().collect::<Vec<_>>(), vec!["", "a", "b", "c"]);
+        assert_eq!(grouped[""].len(), 1);
+        assert_eq!(grouped["a"].len(), 1);
+        assert_eq!(grouped["b"].len(), 1);
+        assert_eq!(grouped["c"].len(), 1);
+        assert!(matches!(grouped["b"][0], SqlMigrationStep::RedefineTables(_)));
+        assert!(std::ptr::eq(grouped["b"][0], grouped["c"][0]));
+    }
+
+    #[test]
+    fn partition_steps_classifies_a_mixed_set() {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("a".to_owned(), Default::default(), None);
+        let column_id = schema.push_table_column(
+            table_id,
+            sql_schema_describer::Column {
+                name: "name".to_owned(),
+                tpe: sql_schema_describer::ColumnType {
+                    full_data_type: "TEXT".to_owned(),
+                    family: sql_schema_describer::ColumnTypeFamily::String,
+                    arity: sql_schema_describer::ColumnArity::Nullable,
+                    native_type: None,
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        let safe_steps = vec![
+            SqlMigrationStep::CreateTable { table_id },
+            SqlMigrationStep::AlterTable(AlterTable {
+                table_ids: MigrationPair::new(table_id, table_id),
+                changes: vec![TableChange::AddPrimaryKey],
+            }),
+            // A RedefineTables step that is internally safe: no dropped columns, no narrowing
+            // casts, no changed checks.
+            SqlMigrationStep::RedefineTables(vec![RedefineTable {
+                added_columns: Vec::new(),
+                added_columns_with_virtual_defaults: Vec::new(),
+                dropped_columns: Vec::new(),
+                dropped_primary_key: false,
+                column_pairs: Vec::new(),
+                table_ids: MigrationPair::new(table_id, table_id),
+                checks_changed: false,
+            }]),
+        ];
+
+        let destructive_steps = vec![
+            SqlMigrationStep::DropTable { table_id },
+            SqlMigrationStep::TruncateTable { table_id, cascade: false },
+            SqlMigrationStep::AlterTable(AlterTable {
+                table_ids: MigrationPair::new(table_id, table_id),
+                changes: vec![TableChange::DropPrimaryKey],
+            }),
+            // A RedefineTables step that drops a column: destructive despite looking similar to
+            // the safe one above.
+            SqlMigrationStep::RedefineTables(vec![RedefineTable {
+                added_columns: Vec::new(),
+                added_columns_with_virtual_defaults: Vec::new(),
+                dropped_columns: vec![column_id],
+                dropped_primary_key: false,
+                column_pairs: Vec::new(),
+                table_ids: MigrationPair::new(table_id, table_id),
+                checks_changed: false,
+            }]),
+        ];
+
+        let steps: Vec<SqlMigrationStep> = safe_steps
+            .iter()
+            .cloned()
+            .chain(destructive_steps.iter().cloned())
+            .collect();
+
+        let (safe, destructive) = partition_steps(&steps);
+
+        assert_eq!(safe, safe_steps.iter().collect::<Vec<_>>());
+        assert_eq!(destructive, destructive_steps.iter().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,284 @@
+//! Inversion of forward [`SqlMigrationStep`]s into the steps of a down-migration.
+//!
+//! Down-migrations reverse the *schema*, not the data: recreating a dropped table produces an
+//! empty table, and re-adding a dropped column produces a column with no prior values. A step is
+//! only treated as irreversible here when even that schema-level reversal is unsafe to execute,
+//! e.g. because it would recreate a `NOT NULL` column with no default on a table that may already
+//! have rows.
+//!
+//! Exposed publicly as [`crate::SqlSchemaConnector::invert`].
+
+use crate::{
+    database_schema::SqlDatabaseSchema,
+    migration_pair::MigrationPair,
+    sql_migration::{AlterEnum, AlterTable, SqlMigrationStep, TableChange},
+    SqlFlavour,
+};
+use sql_schema_describer::SqlSchema;
+
+/// A forward step for which [`invert_steps`] could not produce a safe down-migration equivalent.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct IrreversibleStep {
+    pub(crate) description: String,
+}
+
+/// A migration diffed in both directions in one pass: the forward steps, and — where every step
+/// could be safely inverted — the steps that undo them. See [`invert_steps`] for what makes a
+/// step irreversible.
+///
+/// Unlike [`invert_steps`] (exposed as [`crate::SqlSchemaConnector::invert`]), this has no public
+/// entry point: doing so would need to hand back both directions as owned [`crate::SqlMigration`]s,
+/// which in turn would need `before`/`after` to be cheaply duplicated, and
+/// [`sql_schema_describer::SqlSchema`] isn't `Clone`. Exempted from the dead code lint until
+/// there's a caller that only needs the cheaper steps-only view this returns.
+#[allow(dead_code)]
+pub(crate) struct ReversibleMigration {
+    pub(crate) up: Vec<SqlMigrationStep>,
+    pub(crate) down: Result<Vec<SqlMigrationStep>, Vec<String>>,
+}
+
+/// Diff `schemas` into a forward migration and, where possible, its down-migration.
+#[allow(dead_code)]
+pub(crate) fn calculate_reversible_migration(
+    schemas: MigrationPair<&SqlDatabaseSchema>,
+    flavour: &dyn SqlFlavour,
+) -> ReversibleMigration {
+    let up = crate::sql_schema_differ::calculate_steps(schemas, flavour);
+    let down = invert_steps(&up, schemas.map(|schema| &schema.describer_schema))
+        .map_err(|irreversible| irreversible.into_iter().map(|step| step.description).collect());
+
+    ReversibleMigration { up, down }
+}
+
+/// Invert the steps of a forward migration into the steps that undo it.
+///
+/// The returned steps apply in the reverse order of the forward migration. If any step cannot be
+/// safely inverted, the whole migration is rejected: a down-migration that silently drops some of
+/// the steps it was asked to reverse is more dangerous than one that refuses to generate.
+pub(crate) fn invert_steps(
+    steps: &[SqlMigrationStep],
+    schemas: MigrationPair<&SqlSchema>,
+) -> Result<Vec<SqlMigrationStep>, Vec<IrreversibleStep>> {
+    let mut inverted = Vec::with_capacity(steps.len());
+    let mut irreversible = Vec::new();
+
+    for step in steps.iter().rev() {
+        match invert_step(step, schemas) {
+            Ok(step) => inverted.push(step),
+            Err(description) => irreversible.push(IrreversibleStep { description }),
+        }
+    }
+
+    if irreversible.is_empty() {
+        Ok(inverted)
+    } else {
+        Err(irreversible)
+    }
+}
+
+fn invert_step(step: &SqlMigrationStep, schemas: MigrationPair<&SqlSchema>) -> Result<SqlMigrationStep, String> {
+    match step {
+        SqlMigrationStep::CreateTable { table_id } => Ok(SqlMigrationStep::DropTable { table_id: *table_id }),
+        SqlMigrationStep::DropTable { table_id } => Ok(SqlMigrationStep::CreateTable { table_id: *table_id }),
+        SqlMigrationStep::AlterTable(alter_table) => invert_alter_table(alter_table, schemas),
+        SqlMigrationStep::AlterEnum(alter_enum) => invert_alter_enum(alter_enum),
+        other => Err(format!(
+            "{} steps cannot currently be inverted into a down-migration step.",
+            other.description()
+        )),
+    }
+}
+
+fn invert_alter_table(alter_table: &AlterTable, schemas: MigrationPair<&SqlSchema>) -> Result<SqlMigrationStep, String> {
+    let table_name = schemas.walk(alter_table.table_ids).previous.name().to_owned();
+    let mut changes = Vec::with_capacity(alter_table.changes.len());
+
+    for change in alter_table.changes.iter().rev() {
+        changes.push(invert_table_change(change, schemas, &table_name)?);
+    }
+
+    Ok(SqlMigrationStep::AlterTable(AlterTable {
+        table_ids: alter_table.table_ids.swapped(),
+        changes,
+    }))
+}
+
+fn invert_table_change(
+    change: &TableChange,
+    schemas: MigrationPair<&SqlSchema>,
+    table_name: &str,
+) -> Result<TableChange, String> {
+    match change {
+        TableChange::AddColumn { column_id, .. } => Ok(TableChange::DropColumn { column_id: *column_id }),
+        TableChange::DropColumn { column_id } => {
+            let column = schemas.previous.walk(*column_id);
+
+            if column.arity().is_required() && column.default().is_none() {
+                return Err(format!(
+                    "Column `{}` on table `{table_name}` was dropped without a default value. \
+                     It cannot be safely re-added on the down-migration because its data is gone.",
+                    column.name(),
+                ));
+            }
+
+            Ok(TableChange::AddColumn {
+                column_id: *column_id,
+                has_virtual_default: false,
+                preceding_column: crate::sql_schema_differ::preceding_column(schemas.previous.walk(*column_id).table(), *column_id),
+            })
+        }
+        other => {
+            let kind = match other {
+                TableChange::AlterColumn(_) => "AlterColumn",
+                TableChange::DropAndRecreateColumn { .. } => "DropAndRecreateColumn",
+                TableChange::DropPrimaryKey => "DropPrimaryKey",
+                TableChange::AddPrimaryKey => "AddPrimaryKey",
+                TableChange::RenamePrimaryKey => "RenamePrimaryKey",
+                TableChange::AddExclusionConstraint { .. } => "AddExclusionConstraint",
+                TableChange::DropExclusionConstraint { .. } => "DropExclusionConstraint",
+                TableChange::AddCheckConstraint { .. } => "AddCheckConstraint",
+                TableChange::DropCheckConstraint { .. } => "DropCheckConstraint",
+                TableChange::AlterTablePersistence { .. } => "AlterTablePersistence",
+                TableChange::AlterTableCollation { .. } => "AlterTableCollation",
+                TableChange::AlterTableTablespace { .. } => "AlterTableTablespace",
+                TableChange::AddForeignKey { .. } => "AddForeignKey",
+                TableChange::DropForeignKey { .. } => "DropForeignKey",
+                TableChange::AddColumn { .. } | TableChange::DropColumn { .. } => unreachable!(),
+            };
+
+            Err(format!(
+                "{kind} changes on table `{table_name}` cannot currently be inverted into a down-migration step."
+            ))
+        }
+    }
+}
+
+fn invert_alter_enum(alter_enum: &AlterEnum) -> Result<SqlMigrationStep, String> {
+    if !alter_enum.previous_usages_as_default.is_empty() {
+        return Err(
+            "AlterEnum steps that reinstall a column default after dropping an enum variant cannot currently be \
+             inverted into a down-migration step."
+                .to_owned(),
+        );
+    }
+
+    Ok(SqlMigrationStep::AlterEnum(AlterEnum {
+        id: alter_enum.id.swapped(),
+        created_variants: alter_enum.dropped_variants.clone(),
+        dropped_variants: alter_enum.created_variants.clone(),
+        previous_usages_as_default: Vec::new(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database_schema::SqlDatabaseSchema, flavour::SqliteFlavour, migration_pair::MigrationPair};
+    use psl::SourceFile;
+
+    fn sql_schema(datamodel: &str) -> SqlDatabaseSchema {
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+        crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &SqliteFlavour::default())
+    }
+
+    const BASE_DATAMODEL: &str = r#"
+        datasource db {
+          provider = "sqlite"
+          url = "file:dev.db"
+        }
+
+        model Cat {
+          id Int @id
+        }
+    "#;
+
+    const NEXT_DATAMODEL: &str = r#"
+        datasource db {
+          provider = "sqlite"
+          url = "file:dev.db"
+        }
+
+        model Cat {
+          id   Int    @id
+          name String
+        }
+
+        model Dog {
+          id Int @id
+        }
+    "#;
+
+    #[test]
+    fn inverting_a_create_table_and_add_column_diff() {
+        let before = sql_schema(BASE_DATAMODEL);
+        let after = sql_schema(NEXT_DATAMODEL);
+        let schemas = MigrationPair::new(&before, &after);
+
+        let forward_steps = crate::sql_schema_differ::calculate_steps(schemas, &SqliteFlavour::default());
+
+        let schemas = MigrationPair::new(&before.describer_schema, &after.describer_schema);
+        let inverted = invert_steps(&forward_steps, schemas).expect("the diff should be fully reversible");
+
+        let has_drop_table = inverted
+            .iter()
+            .any(|step| matches!(step, SqlMigrationStep::DropTable { table_id } if after.describer_schema.walk(*table_id).name() == "Dog"));
+        assert!(has_drop_table, "the created `Dog` table should be dropped on the way down");
+
+        let has_drop_column = inverted.iter().any(|step| {
+            matches!(step, SqlMigrationStep::AlterTable(alter_table) if alter_table.changes.iter().any(|change| {
+                matches!(change, TableChange::DropColumn { column_id } if after.describer_schema.walk(*column_id).name() == "name")
+            }))
+        });
+        assert!(has_drop_column, "the added `name` column should be dropped on the way down");
+    }
+
+    #[test]
+    fn inverting_a_drop_of_a_required_column_without_a_default_is_irreversible() {
+        let before = sql_schema(NEXT_DATAMODEL);
+        let after = sql_schema(BASE_DATAMODEL);
+        let schemas = MigrationPair::new(&before, &after);
+
+        let forward_steps = crate::sql_schema_differ::calculate_steps(schemas, &SqliteFlavour::default());
+
+        let schemas = MigrationPair::new(&before.describer_schema, &after.describer_schema);
+        let irreversible = invert_steps(&forward_steps, schemas).unwrap_err();
+
+        assert!(irreversible
+            .iter()
+            .any(|step| step.description.contains("name") && step.description.contains("data is gone")));
+    }
+
+    #[test]
+    fn calculate_reversible_migration_returns_both_directions_for_a_reversible_diff() {
+        let before = sql_schema(BASE_DATAMODEL);
+        let after = sql_schema(NEXT_DATAMODEL);
+        let schemas = MigrationPair::new(&before, &after);
+
+        let migration = calculate_reversible_migration(schemas, &SqliteFlavour::default());
+
+        assert!(!migration.up.is_empty());
+        let down = migration.down.expect("the diff should be fully reversible");
+
+        let has_drop_table = down
+            .iter()
+            .any(|step| matches!(step, SqlMigrationStep::DropTable { table_id } if after.describer_schema.walk(*table_id).name() == "Dog"));
+        assert!(has_drop_table, "the created `Dog` table should be dropped on the way down");
+    }
+
+    #[test]
+    fn calculate_reversible_migration_reports_the_error_for_an_irreversible_diff() {
+        let before = sql_schema(NEXT_DATAMODEL);
+        let after = sql_schema(BASE_DATAMODEL);
+        let schemas = MigrationPair::new(&before, &after);
+
+        let migration = calculate_reversible_migration(schemas, &SqliteFlavour::default());
+
+        assert!(!migration.up.is_empty());
+        let irreversible = migration.down.unwrap_err();
+        assert!(irreversible
+            .iter()
+            .any(|description| description.contains("name") && description.contains("data is gone")));
+    }
+}
@@ -0,0 +1,210 @@
+//! A static, dashboard-facing summary of how risky a generated migration is, built from
+//! [`SqlMigrationStep`]s and the schemas they were diffed from. Unlike
+//! [`crate::sql_destructive_change_checker`], this never touches the database — it only
+//! classifies the steps themselves, so it can't account for row counts on a flavour that would
+//! otherwise inspect them.
+
+use crate::{
+    flavour::SqlFlavour,
+    migration_pair::MigrationPair,
+    sql_migration::{AlterTable, SqlMigrationStep, TableChange},
+};
+use sql_schema_describer::SqlSchema;
+
+/// A summary of how risky a migration is, aggregating the destructive-change, rewrite, and
+/// locking classifications already computed elsewhere in the connector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MigrationRisk {
+    /// The number of steps that could delete or corrupt existing data. See
+    /// [`SqlMigrationStep::is_destructive`].
+    pub destructive_count: usize,
+    /// The number of full-table rewrites the steps force, rather than cheap, metadata-only
+    /// changes. A single step can force several, e.g. one per dropped-and-recreated column.
+    pub rewrites: usize,
+    /// The number of steps that hold a lock blocking concurrent reads and/or writes on a table
+    /// for their duration, without necessarily rewriting it (e.g. a non-concurrent index build).
+    pub blocking_operations: usize,
+    /// The overall risk level, derived from the other three counts.
+    pub overall: RiskLevel,
+}
+
+/// The overall risk level of a migration, derived from [`MigrationRisk`]'s counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum RiskLevel {
+    /// No destructive, rewriting, or blocking steps: the migration is purely additive or
+    /// otherwise online.
+    #[default]
+    Low,
+    /// At least one rewrite or blocking step, but nothing destructive.
+    Medium,
+    /// At least one step that could delete or corrupt existing data.
+    High,
+}
+
+/// Classify `steps` into a [`MigrationRisk`]. `schemas` must be the same before/after pair `steps`
+/// was generated from.
+pub(crate) fn score_migration(
+    steps: &[SqlMigrationStep],
+    schemas: MigrationPair<&SqlSchema>,
+    flavour: &dyn SqlFlavour,
+) -> MigrationRisk {
+    let destructive_count = steps.iter().filter(|step| step.is_destructive()).count();
+    let rewrites = steps.iter().map(|step| step_rewrites(step, schemas, flavour)).sum();
+    let blocking_operations = steps.iter().map(step_blocking_operations).sum();
+
+    let overall = if destructive_count > 0 {
+        RiskLevel::High
+    } else if rewrites > 0 || blocking_operations > 0 {
+        RiskLevel::Medium
+    } else {
+        RiskLevel::Low
+    };
+
+    MigrationRisk {
+        destructive_count,
+        rewrites,
+        blocking_operations,
+        overall,
+    }
+}
+
+/// How many full-table rewrites this step forces. A dropped-and-recreated column, a tablespace
+/// move, or a whole table redefinition (SQLite's standard workaround for changes it can't express
+/// as `ALTER TABLE`), always rewrites every row; a column type change only does on a flavour that
+/// doesn't claim [`crate::sql_schema_differ::SqlSchemaDifferFlavour::column_type_change_is_online`]
+/// for it.
+fn step_rewrites(step: &SqlMigrationStep, schemas: MigrationPair<&SqlSchema>, flavour: &dyn SqlFlavour) -> usize {
+    match step {
+        SqlMigrationStep::AlterTable(AlterTable { changes, .. }) => changes
+            .iter()
+            .filter(|change| match change {
+                TableChange::DropAndRecreateColumn { .. } => true,
+                TableChange::AlterColumn(alter_column) => {
+                    alter_column.type_change.is_some()
+                        && !flavour.column_type_change_is_online(schemas.walk(alter_column.column_id))
+                }
+                // Moving a table to another tablespace always rewrites it on disk.
+                TableChange::AlterTableTablespace { .. } => true,
+                _ => false,
+            })
+            .count(),
+        // Every redefined table is recreated from scratch with every row copied over.
+        SqlMigrationStep::RedefineTables(redefines) => redefines.len(),
+        _ => 0,
+    }
+}
+
+/// How many locking-but-not-necessarily-rewriting operations this step performs: a non-concurrent
+/// index build on a table that existed before the migration (as opposed to one being created from
+/// scratch in the same migration), a primary key change, or a truncation.
+fn step_blocking_operations(step: &SqlMigrationStep) -> usize {
+    match step {
+        SqlMigrationStep::CreateIndex {
+            table_id: (Some(_), _),
+            concurrently: false,
+            ..
+        } => 1,
+        SqlMigrationStep::AlterPrimaryKey(_) | SqlMigrationStep::TruncateTable { .. } => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database_schema::SqlDatabaseSchema, flavour::SqliteFlavour, sql_schema_differ};
+    use psl::SourceFile;
+
+    fn sql_schema(datamodel: &str) -> SqlSchema {
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+        let flavour = SqliteFlavour::default();
+
+        crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, &flavour).describer_schema
+    }
+
+    fn risk_of(previous_datamodel: &str, next_datamodel: &str) -> MigrationRisk {
+        let previous = sql_schema(previous_datamodel);
+        let next = sql_schema(next_datamodel);
+        let flavour = SqliteFlavour::default();
+
+        let schemas = MigrationPair::new(SqlDatabaseSchema::from(previous), SqlDatabaseSchema::from(next));
+        let steps = sql_schema_differ::calculate_steps_with_settings(schemas.as_ref(), &flavour, Default::default());
+        let schemas = schemas.map(|schema| schema.describer_schema);
+
+        score_migration(&steps, schemas.as_ref(), &flavour)
+    }
+
+    #[test]
+    fn a_fully_additive_migration_scores_as_low_risk() {
+        let previous = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id Int @id
+            }
+        "#;
+
+        let next = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id   Int     @id
+              name String?
+            }
+
+            model Egg {
+              id Int @id
+            }
+        "#;
+
+        let risk = risk_of(previous, next);
+
+        assert_eq!(
+            risk,
+            MigrationRisk {
+                destructive_count: 0,
+                rewrites: 0,
+                blocking_operations: 0,
+                overall: RiskLevel::Low,
+            }
+        );
+    }
+
+    #[test]
+    fn a_dropped_column_scores_as_high_risk() {
+        let previous = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id   Int     @id
+              name String?
+            }
+        "#;
+
+        let next = r#"
+            datasource db {
+              provider = "sqlite"
+              url      = "file:dev.db"
+            }
+
+            model Chicken {
+              id Int @id
+            }
+        "#;
+
+        let risk = risk_of(previous, next);
+
+        assert_eq!(risk.overall, RiskLevel::High);
+        assert!(risk.destructive_count >= 1);
+    }
+}
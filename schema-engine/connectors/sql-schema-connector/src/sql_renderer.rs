@@ -20,7 +20,8 @@ use self::common::{Quoted, QuotedWithPrefix};
 use crate::{
     migration_pair::MigrationPair,
     sql_migration::{
-        AlterEnum, AlterExtension, AlterTable, CreateExtension, DropExtension, RedefineTable, SequenceChanges,
+        AlterDomain, AlterEnum, AlterExtension, AlterPolicy, AlterTable, CreateDomain, CreateExtension, CreatePolicy,
+        CreateTrigger, DropDomain, DropExtension, DropPolicy, DropTrigger, RedefineTable, SequenceChanges,
     },
 };
 use sql_schema_describer::{
@@ -32,7 +33,10 @@ use sql_schema_describer::{
 pub(crate) trait SqlRenderer {
     fn quote<'a>(&self, name: &'a str) -> Quoted<&'a str>;
 
-    fn render_add_foreign_key(&self, foreign_key: ForeignKeyWalker<'_>) -> String;
+    /// `deferred` requests that the constraint only be checked at transaction commit, where the
+    /// flavour supports it (currently Postgres only). Flavours that don't support deferred
+    /// constraints ignore it and add the foreign key immediately, as usual.
+    fn render_add_foreign_key(&self, foreign_key: ForeignKeyWalker<'_>, deferred: bool) -> String;
 
     fn render_alter_enum(&self, alter_enum: &AlterEnum, schemas: MigrationPair<&SqlSchema>) -> Vec<String>;
 
@@ -53,6 +57,20 @@ pub(crate) trait SqlRenderer {
         unreachable!("unreachable render_alter_index")
     }
 
+    /// Render an `AlterIndex` step, setting or clearing the comment on an index. Only called on
+    /// flavours for which
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::supports_object_comments`] is true.
+    fn render_comment_on_index(&self, _indexes: MigrationPair<IndexWalker<'_>>) -> String {
+        unreachable!("render_comment_on_index on a flavour without object comments")
+    }
+
+    /// Render an `AlterIndex` step's tablespace change: `ALTER INDEX ... SET TABLESPACE ...`.
+    /// Only called on flavours for which
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::compares_index_tablespaces`] is true.
+    fn render_alter_index_tablespace(&self, _indexes: MigrationPair<IndexWalker<'_>>) -> String {
+        unreachable!("render_alter_index_tablespace on a flavour without index tablespaces")
+    }
+
     fn render_alter_table(&self, alter_table: &AlterTable, schemas: MigrationPair<&SqlSchema>) -> Vec<String>;
 
     /// Render a `CreateEnum` step.
@@ -60,6 +78,15 @@ pub(crate) trait SqlRenderer {
 
     fn render_create_index(&self, index: IndexWalker<'_>) -> String;
 
+    /// Render a `CreateIndex` step with `CONCURRENTLY`, for
+    /// [`crate::sql_schema_differ::DifferSettings::concurrent_index_creation`]. Only called on
+    /// flavours for which
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::supports_concurrent_index_creation`]
+    /// is true, since `CONCURRENTLY` is a PostgreSQL-only keyword.
+    fn render_create_index_concurrently(&self, _index: IndexWalker<'_>) -> String {
+        unreachable!("render_create_index_concurrently on a flavour without concurrent index creation")
+    }
+
     /// Render a table creation step.
     fn render_create_table(&self, table: TableWalker<'_>) -> String;
 
@@ -113,10 +140,21 @@ pub(crate) trait SqlRenderer {
     /// Render a `RenameForeignKey` step.
     fn render_rename_foreign_key(&self, fks: MigrationPair<ForeignKeyWalker<'_>>) -> String;
 
+    /// Render an `AlterForeignKey` step, setting or clearing the comment on a foreign key
+    /// constraint. Only called on flavours for which
+    /// [`crate::sql_schema_differ::SqlSchemaDifferFlavour::supports_object_comments`] is true.
+    fn render_comment_on_constraint(&self, _fks: MigrationPair<ForeignKeyWalker<'_>>) -> String {
+        unreachable!("render_comment_on_constraint on a flavour without object comments")
+    }
+
     fn render_create_namespace(&self, _namespace: sql::NamespaceWalker<'_>) -> String {
         unreachable!()
     }
 
+    fn render_drop_namespace(&self, _namespace: sql::NamespaceWalker<'_>) -> String {
+        unreachable!()
+    }
+
     fn render_create_extension(&self, _create: &CreateExtension, _schema: &SqlSchema) -> Vec<String> {
         unreachable!("render_create_extension")
     }
@@ -128,4 +166,104 @@ pub(crate) trait SqlRenderer {
     fn render_drop_extension(&self, _drop: &DropExtension, _schema: &SqlSchema) -> Vec<String> {
         unreachable!("render_drop_extension")
     }
+
+    fn render_create_domain(&self, _create: &CreateDomain, _schema: &SqlSchema) -> Vec<String> {
+        unreachable!("render_create_domain")
+    }
+
+    fn render_alter_domain(&self, _alter: &AlterDomain, _schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
+        unreachable!("render_alter_domain")
+    }
+
+    fn render_drop_domain(&self, _drop: &DropDomain, _schema: &SqlSchema) -> Vec<String> {
+        unreachable!("render_drop_domain")
+    }
+
+    fn render_create_policy(&self, _create: &CreatePolicy, _schema: &SqlSchema) -> Vec<String> {
+        unreachable!("render_create_policy")
+    }
+
+    fn render_alter_policy(&self, _alter: &AlterPolicy, _schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
+        unreachable!("render_alter_policy")
+    }
+
+    fn render_drop_policy(&self, _drop: &DropPolicy, _schema: &SqlSchema) -> Vec<String> {
+        unreachable!("render_drop_policy")
+    }
+
+    fn render_enable_row_level_security(&self, _table: TableWalker<'_>) -> Vec<String> {
+        unreachable!("render_enable_row_level_security")
+    }
+
+    fn render_disable_row_level_security(&self, _table: TableWalker<'_>) -> Vec<String> {
+        unreachable!("render_disable_row_level_security")
+    }
+
+    /// Render a `TruncateTable` step. `cascade` requests that tables with foreign keys
+    /// referencing `table` also be emptied, where the flavour supports it.
+    fn render_truncate_table(&self, _table: TableWalker<'_>, _cascade: bool) -> Vec<String> {
+        unreachable!("render_truncate_table")
+    }
+
+    fn render_add_table_inheritance(&self, _table: TableWalker<'_>, _parent_table: TableWalker<'_>) -> Vec<String> {
+        unreachable!("render_add_table_inheritance")
+    }
+
+    fn render_drop_table_inheritance(&self, _table: TableWalker<'_>, _parent_table: TableWalker<'_>) -> Vec<String> {
+        unreachable!("render_drop_table_inheritance")
+    }
+
+    fn render_create_trigger(&self, _create: &CreateTrigger, _schema: &SqlSchema) -> Vec<String> {
+        unreachable!("render_create_trigger")
+    }
+
+    fn render_drop_trigger(&self, _drop: &DropTrigger, _schema: &SqlSchema) -> Vec<String> {
+        unreachable!("render_drop_trigger")
+    }
+
+    /// A lightweight syntactic sanity check on a statement this renderer just produced, to catch
+    /// rendering bugs before the engine hands the statement back. The default implementation only
+    /// checks for balanced parentheses/quotes and a non-empty statement; it cannot catch most
+    /// actual syntax errors. A flavour that can cheaply ask its own driver whether a statement
+    /// parses should override this to do that instead.
+    fn validate_sql(&self, sql: &str) -> Result<(), String> {
+        basic_sql_sanity_check(sql)
+    }
+}
+
+/// Checks that `sql` is non-empty and has balanced parentheses and quotes. Shared by every
+/// flavour's default [`SqlRenderer::validate_sql`], and as a fallback for flavours whose override
+/// can only check some statements (e.g. a single-statement-only parser asked to validate a script).
+pub(crate) fn basic_sql_sanity_check(sql: &str) -> Result<(), String> {
+    if sql.trim().is_empty() {
+        return Err("rendered an empty statement".to_owned());
+    }
+
+    let mut parens = 0i32;
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+
+    for c in sql.chars() {
+        match c {
+            '\'' if !in_double_quote => in_single_quote = !in_single_quote,
+            '"' if !in_single_quote => in_double_quote = !in_double_quote,
+            '(' if !in_single_quote && !in_double_quote => parens += 1,
+            ')' if !in_single_quote && !in_double_quote => parens -= 1,
+            _ => (),
+        }
+
+        if parens < 0 {
+            return Err(format!("unbalanced parentheses in rendered SQL: {sql}"));
+        }
+    }
+
+    if parens != 0 {
+        return Err(format!("unbalanced parentheses in rendered SQL: {sql}"));
+    }
+
+    if in_single_quote || in_double_quote {
+        return Err(format!("unbalanced quotes in rendered SQL: {sql}"));
+    }
+
+    Ok(())
 }
@@ -367,7 +367,9 @@ impl SqlRenderer for MssqlFlavour {
         )
     }
 
-    fn render_add_foreign_key(&self, foreign_key: sql::ForeignKeyWalker<'_>) -> String {
+    fn render_add_foreign_key(&self, foreign_key: sql::ForeignKeyWalker<'_>, _deferred: bool) -> String {
+        // MSSQL has no notion of a deferred constraint check, so `_deferred` is ignored: the
+        // foreign key is always added immediately.
         let mut add_constraint = String::with_capacity(120);
 
         write!(
@@ -408,6 +410,15 @@ impl SqlRenderer for MssqlFlavour {
         vec![format!("DROP TABLE {}", self.quote_with_schema(namespace, table_name))]
     }
 
+    fn render_truncate_table(&self, table: sql::TableWalker<'_>, _cascade: bool) -> Vec<String> {
+        // MSSQL's TRUNCATE TABLE has no CASCADE option; callers that need to empty
+        // referencing tables must order the truncations themselves.
+        vec![format!(
+            "TRUNCATE TABLE {}",
+            self.quote_with_schema(table.namespace(), table.name())
+        )]
+    }
+
     fn render_drop_view(&self, view: sql::ViewWalker<'_>) -> String {
         format!("DROP VIEW {}", self.quote_with_schema(view.namespace(), view.name()))
     }
@@ -452,6 +463,12 @@ impl SqlRenderer for MssqlFlavour {
         )
     }
 
+    // T-SQL's `DROP SCHEMA` has no `CASCADE`; the schema must already be empty, which it will be
+    // by the time this step runs, since `DropSchema` is ordered after `DropTable`.
+    fn render_drop_namespace(&self, namespace: sql_schema_describer::NamespaceWalker<'_>) -> String {
+        format!("DROP SCHEMA {}", Quoted::mssql_ident(namespace.name()))
+    }
+
     fn render_rename_foreign_key(&self, fks: MigrationPair<sql::ForeignKeyWalker<'_>>) -> String {
         format!(
             r#"EXEC sp_rename '{schema}.{previous}', '{next}', 'OBJECT'"#,
@@ -67,6 +67,7 @@ impl<'a> AlterTableConstructor<'a> {
                 TableChange::AddColumn {
                     column_id,
                     has_virtual_default: _,
+                    preceding_column: _,
                 } => {
                     self.add_column(*column_id);
                 }
@@ -80,9 +81,19 @@ impl<'a> AlterTableConstructor<'a> {
                     column_id,
                     changes,
                     type_change: _,
+                    type_override: _,
                 }) => {
                     self.alter_column(*column_id, changes);
                 }
+                TableChange::AddExclusionConstraint { .. } => unreachable!("AddExclusionConstraint on MSSQL"),
+                TableChange::DropExclusionConstraint { .. } => unreachable!("DropExclusionConstraint on MSSQL"),
+                TableChange::AddCheckConstraint { .. } => unreachable!("AddCheckConstraint on MSSQL"),
+                TableChange::DropCheckConstraint { .. } => unreachable!("DropCheckConstraint on MSSQL"),
+                TableChange::AlterTablePersistence { .. } => unreachable!("AlterTablePersistence on MSSQL"),
+                TableChange::AlterTableCollation { .. } => unreachable!("AlterTableCollation on MSSQL"),
+                TableChange::AlterTableTablespace { .. } => unreachable!("AlterTableTablespace on MSSQL"),
+                TableChange::AddForeignKey { .. } => unreachable!("AddForeignKey on MSSQL"),
+                TableChange::DropForeignKey { .. } => unreachable!("DropForeignKey on MSSQL"),
             };
         }
 
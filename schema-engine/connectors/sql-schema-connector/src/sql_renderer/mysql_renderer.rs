@@ -2,7 +2,7 @@ use super::{common::*, IteratorJoin, SqlRenderer};
 use crate::{
     flavour::MysqlFlavour,
     migration_pair::MigrationPair,
-    sql_migration::{AlterColumn, AlterEnum, AlterTable, RedefineTable, TableChange},
+    sql_migration::{AlterColumn, AlterEnum, AlterTable, CreateTrigger, DropTrigger, RedefineTable, TableChange},
     sql_schema_differ::ColumnChanges,
 };
 use once_cell::sync::Lazy;
@@ -32,12 +32,15 @@ impl MysqlFlavour {
             })
             .map(|default| render_default(col, default.inner()));
 
+        let on_update = col.is_on_update_current_timestamp().then(|| render_current_timestamp(col));
+
         ddl::Column {
             column_name: col.name().into(),
             not_null: col.arity().is_required(),
             column_type: render_column_type(col),
             default,
             auto_increment: col.is_autoincrement(),
+            on_update,
             ..Default::default()
         }
     }
@@ -48,7 +51,9 @@ impl SqlRenderer for MysqlFlavour {
         Quoted::Backticks(name)
     }
 
-    fn render_add_foreign_key(&self, foreign_key: ForeignKeyWalker<'_>) -> String {
+    fn render_add_foreign_key(&self, foreign_key: ForeignKeyWalker<'_>, _deferred: bool) -> String {
+        // MySQL has no notion of a deferred constraint check, so `_deferred` is ignored: the
+        // foreign key is always added immediately.
         ddl::AlterTable {
             table_name: foreign_key.table().name().into(),
             changes: vec![ddl::AlterTableClause::AddForeignKey(ddl::ForeignKey {
@@ -138,11 +143,19 @@ impl SqlRenderer for MysqlFlavour {
                 TableChange::AddColumn {
                     column_id,
                     has_virtual_default: _,
+                    preceding_column,
                 } => {
                     let column = tables.next.walk(*column_id);
                     let col_sql = self.render_column(column);
 
-                    lines.push(format!("ADD COLUMN {col_sql}"));
+                    let position = match preceding_column {
+                        Some(preceding_column) => {
+                            format!(" AFTER {}", self.quote(tables.next.walk(*preceding_column).name()))
+                        }
+                        None => " FIRST".to_owned(),
+                    };
+
+                    lines.push(format!("ADD COLUMN {col_sql}{position}"));
                 }
                 TableChange::DropColumn { column_id } => lines.push(
                     sql_ddl::mysql::AlterTableClause::DropColumn {
@@ -154,6 +167,7 @@ impl SqlRenderer for MysqlFlavour {
                     changes,
                     column_id,
                     type_change: _,
+                    type_override: _,
                 }) => {
                     let columns = schemas.walk(*column_id);
                     let expanded = MysqlAlterColumn::new(columns, *changes);
@@ -173,6 +187,59 @@ impl SqlRenderer for MysqlFlavour {
                     lines.push(format!("DROP COLUMN `{}`", columns.previous.name()));
                     lines.push(format!("ADD COLUMN {}", self.render_column(columns.next)));
                 }
+                TableChange::AddExclusionConstraint { .. } => unreachable!("AddExclusionConstraint on MySQL"),
+                TableChange::DropExclusionConstraint { .. } => unreachable!("DropExclusionConstraint on MySQL"),
+                TableChange::AddCheckConstraint { .. } => unreachable!("AddCheckConstraint on MySQL"),
+                TableChange::DropCheckConstraint { .. } => unreachable!("DropCheckConstraint on MySQL"),
+                TableChange::AlterTablePersistence { .. } => unreachable!("AlterTablePersistence on MySQL"),
+                TableChange::AlterTableCollation { collation } => lines.push(format!("COLLATE {collation}")),
+                TableChange::AlterTableTablespace { .. } => unreachable!("AlterTableTablespace on MySQL"),
+                TableChange::AddForeignKey {
+                    foreign_key_id,
+                    deferred: _,
+                } => {
+                    // MySQL has no notion of a deferred constraint check, so `deferred` is
+                    // ignored: the foreign key is always added immediately.
+                    let foreign_key = schemas.next.walk(*foreign_key_id);
+
+                    lines.push(
+                        ddl::AlterTableClause::AddForeignKey(ddl::ForeignKey {
+                            constraint_name: foreign_key.constraint_name().map(From::from),
+                            constrained_columns: foreign_key
+                                .constrained_columns()
+                                .map(|c| Cow::Borrowed(c.name()))
+                                .collect(),
+                            referenced_table: foreign_key.referenced_table().name().into(),
+                            referenced_columns: foreign_key
+                                .referenced_columns()
+                                .map(|c| Cow::Borrowed(c.name()))
+                                .collect(),
+                            on_delete: Some(match foreign_key.on_delete_action() {
+                                ForeignKeyAction::Cascade => ddl::ForeignKeyAction::Cascade,
+                                ForeignKeyAction::NoAction => ddl::ForeignKeyAction::NoAction,
+                                ForeignKeyAction::Restrict => ddl::ForeignKeyAction::Restrict,
+                                ForeignKeyAction::SetDefault => ddl::ForeignKeyAction::SetDefault,
+                                ForeignKeyAction::SetNull => ddl::ForeignKeyAction::SetNull,
+                            }),
+                            on_update: Some(match foreign_key.on_update_action() {
+                                ForeignKeyAction::Cascade => ddl::ForeignKeyAction::Cascade,
+                                ForeignKeyAction::NoAction => ddl::ForeignKeyAction::NoAction,
+                                ForeignKeyAction::Restrict => ddl::ForeignKeyAction::Restrict,
+                                ForeignKeyAction::SetDefault => ddl::ForeignKeyAction::SetDefault,
+                                ForeignKeyAction::SetNull => ddl::ForeignKeyAction::SetNull,
+                            }),
+                        })
+                        .to_string(),
+                    );
+                }
+                TableChange::DropForeignKey { foreign_key_id } => {
+                    let foreign_key = schemas.previous.walk(*foreign_key_id);
+
+                    lines.push(format!(
+                        "DROP FOREIGN KEY {}",
+                        Quoted::mysql_ident(foreign_key.constraint_name().unwrap())
+                    ));
+                }
             };
         }
 
@@ -314,6 +381,16 @@ impl SqlRenderer for MysqlFlavour {
         })
     }
 
+    fn render_truncate_table(&self, table: TableWalker<'_>, _cascade: bool) -> Vec<String> {
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_display(&sql_ddl::mysql::TruncateTable {
+                    table_name: table.name().into(),
+                })
+            })
+        })
+    }
+
     fn render_redefine_tables(&self, _names: &[RedefineTable], _schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
         unreachable!("render_redefine_table on MySQL")
     }
@@ -340,6 +417,26 @@ impl SqlRenderer for MysqlFlavour {
         unreachable!("render_drop_user_defined_type on MySQL")
     }
 
+    fn render_create_trigger(&self, create: &CreateTrigger, schema: &SqlSchema) -> Vec<String> {
+        let trigger = schema.walk(create.id);
+
+        // `information_schema.TRIGGERS.ACTION_STATEMENT` only exposes the trigger's body, not a
+        // full `CREATE TRIGGER` statement, so the header is reassembled from the structured
+        // timing/event/table fields.
+        vec![format!(
+            "CREATE TRIGGER {} {} {} ON {} FOR EACH ROW {}",
+            Quoted::mysql_ident(trigger.name()),
+            trigger.timing(),
+            trigger.event(),
+            Quoted::mysql_ident(trigger.table().name()),
+            trigger.definition(),
+        )]
+    }
+
+    fn render_drop_trigger(&self, drop: &DropTrigger, schema: &SqlSchema) -> Vec<String> {
+        vec![format!("DROP TRIGGER {}", Quoted::mysql_ident(schema.walk(drop.id).name()))]
+    }
+
     fn render_rename_foreign_key(&self, _fks: MigrationPair<ForeignKeyWalker<'_>>) -> String {
         unreachable!("render RenameForeignKey on MySQL")
     }
@@ -368,8 +465,14 @@ fn render_mysql_modify(
         .map(|expression| format!(" DEFAULT {expression}"))
         .unwrap_or_default();
 
+    let on_update = if next_column.is_on_update_current_timestamp() {
+        format!(" ON UPDATE {}", render_current_timestamp(next_column))
+    } else {
+        String::new()
+    };
+
     format!(
-        "MODIFY {column_name} {column_type}{nullability}{default}{sequence}",
+        "MODIFY {column_name} {column_type}{nullability}{default}{on_update}{sequence}",
         column_name = Quoted::mysql_ident(&next_column.name()),
         column_type = column_type,
         nullability = if next_column.arity().is_required() {
@@ -378,6 +481,7 @@ fn render_mysql_modify(
             " NULL"
         },
         default = default,
+        on_update = on_update,
         sequence = if next_column.is_autoincrement() {
             " AUTO_INCREMENT"
         } else {
@@ -497,20 +601,25 @@ impl MysqlAlterColumn {
     }
 }
 
+/// Renders `CURRENT_TIMESTAMP(<precision>)`, shared by a `@default(now())` default and an
+/// `ON UPDATE CURRENT_TIMESTAMP` attribute, which both key off the column's own timestamp
+/// precision.
+fn render_current_timestamp(column: TableColumnWalker<'_>) -> String {
+    let precision = column
+        .column_native_type()
+        .and_then(MySqlType::timestamp_precision)
+        .unwrap_or(3);
+
+    format!("CURRENT_TIMESTAMP({precision})")
+}
+
 fn render_default<'a>(column: TableColumnWalker<'a>, default: &'a DefaultValue) -> Cow<'a, str> {
     match default.kind() {
         DefaultKind::DbGenerated(Some(val)) => val.as_str().into(),
         DefaultKind::Value(PrismaValue::String(val)) | DefaultKind::Value(PrismaValue::Enum(val)) => {
             Quoted::mysql_string(escape_string_literal(val)).to_string().into()
         }
-        DefaultKind::Now => {
-            let precision = column
-                .column_native_type()
-                .and_then(MySqlType::timestamp_precision)
-                .unwrap_or(3);
-
-            format!("CURRENT_TIMESTAMP({precision})").into()
-        }
+        DefaultKind::Now => render_current_timestamp(column).into(),
         DefaultKind::Value(PrismaValue::DateTime(dt)) if column.column_type_family().is_datetime() => {
             Quoted::mysql_string(dt.to_rfc3339()).to_string().into()
         }
@@ -3,8 +3,9 @@ use crate::{
     flavour::PostgresFlavour,
     migration_pair::MigrationPair,
     sql_migration::{
-        AlterColumn, AlterEnum, AlterExtension, AlterTable, CreateExtension, DropExtension, ExtensionChange,
-        RedefineTable, SequenceChange, SequenceChanges, TableChange,
+        AlterColumn, AlterDomain, AlterEnum, AlterExtension, AlterPolicy, AlterTable, CreateDomain, CreateExtension,
+        CreatePolicy, CreateTrigger, DomainChange, DropDomain, DropExtension, DropPolicy, DropTrigger,
+        ExtensionChange, PolicyChange, RedefineTable, SequenceChange, SequenceChanges, TableChange,
     },
     sql_schema_differ::{ColumnChange, ColumnChanges},
 };
@@ -14,9 +15,10 @@ use sql_ddl::{
     IndexColumn, SortOrder,
 };
 use sql_schema_describer::{
-    postgres::{PostgresSchemaExt, SqlIndexAlgorithm},
+    postgres::{Policy, PolicyCommand, PostgresSchemaExt, SqlIndexAlgorithm},
     walkers::*,
-    ColumnArity, ColumnTypeFamily, DefaultKind, DefaultValue, ForeignKeyAction, PrismaValue, SQLSortOrder, SqlSchema,
+    ColumnArity, ColumnStorage, ColumnTypeFamily, DefaultKind, DefaultValue, ForeignKeyAction, PrismaValue,
+    SQLSortOrder, SqlSchema,
 };
 use std::borrow::Cow;
 
@@ -36,10 +38,42 @@ impl PostgresFlavour {
 
         format!("{SQL_INDENTATION}{column_name} {tpe_str}{nullability_str}{default_str}{identity_str}",)
     }
+
+    fn render_create_index_impl(&self, index: IndexWalker<'_>, concurrently: bool) -> String {
+        let pg_ext: &PostgresSchemaExt = index.schema.downcast_connector_data();
+
+        ddl::CreateIndex {
+            index_name: index.name().into(),
+            is_unique: index.is_unique(),
+            table_reference: &QuotedWithPrefix::pg_from_table_walker(index.table()),
+            using: Some(match pg_ext.index_algorithm(index.id) {
+                SqlIndexAlgorithm::BTree => ddl::IndexAlgorithm::BTree,
+                SqlIndexAlgorithm::Hash => ddl::IndexAlgorithm::Hash,
+                SqlIndexAlgorithm::Gist => ddl::IndexAlgorithm::Gist,
+                SqlIndexAlgorithm::Gin => ddl::IndexAlgorithm::Gin,
+                SqlIndexAlgorithm::SpGist => ddl::IndexAlgorithm::SpGist,
+                SqlIndexAlgorithm::Brin => ddl::IndexAlgorithm::Brin,
+            }),
+            columns: index
+                .columns()
+                .map(|c| IndexColumn {
+                    name: c.as_column().name().into(),
+                    length: None,
+                    sort_order: c.sort_order().map(|so| match so {
+                        SQLSortOrder::Asc => SortOrder::Asc,
+                        SQLSortOrder::Desc => SortOrder::Desc,
+                    }),
+                    operator_class: pg_ext.get_opclass(c.id).map(|c| c.kind.as_ref().into()),
+                })
+                .collect(),
+            concurrently,
+            predicate: index.predicate(),
+        }
+        .to_string()
+    }
 }
 
 impl SqlRenderer for PostgresFlavour {
-    // TODO(MultiSchema): We only do alter_sequence on CockroachDB.
     fn render_alter_sequence(
         &self,
         sequence_idx: MigrationPair<u32>,
@@ -148,11 +182,229 @@ impl SqlRenderer for PostgresFlavour {
             .collect()
     }
 
+    fn render_create_domain(&self, create: &CreateDomain, schema: &SqlSchema) -> Vec<String> {
+        let ext: &PostgresSchemaExt = schema.downcast_connector_data();
+        let domain = ext.get_domain(create.id);
+
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("CREATE DOMAIN ");
+                stmt.push_display(&Quoted::postgres_ident(&domain.name));
+                stmt.push_str(" AS ");
+                stmt.push_str(&domain.base_type);
+
+                if let Some(default) = &domain.default {
+                    stmt.push_str(" DEFAULT ");
+                    stmt.push_str(default);
+                }
+
+                if domain.not_null {
+                    stmt.push_str(" NOT NULL");
+                }
+
+                if let Some(check) = &domain.check {
+                    stmt.push_str(" CHECK (");
+                    stmt.push_str(check);
+                    stmt.push(')');
+                }
+            })
+        })
+    }
+
+    fn render_drop_domain(&self, drop: &DropDomain, schema: &SqlSchema) -> Vec<String> {
+        let ext: &PostgresSchemaExt = schema.downcast_connector_data();
+        let domain = ext.get_domain(drop.id);
+
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("DROP DOMAIN ");
+                stmt.push_display(&Quoted::postgres_ident(&domain.name));
+            })
+        })
+    }
+
+    fn render_create_trigger(&self, create: &CreateTrigger, schema: &SqlSchema) -> Vec<String> {
+        // `pg_get_triggerdef()` already reports the full, verbatim `CREATE TRIGGER` statement.
+        vec![schema.walk(create.id).definition().to_owned()]
+    }
+
+    fn render_drop_trigger(&self, drop: &DropTrigger, schema: &SqlSchema) -> Vec<String> {
+        let trigger = schema.walk(drop.id);
+
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("DROP TRIGGER ");
+                stmt.push_display(&Quoted::postgres_ident(trigger.name()));
+                stmt.push_str(" ON ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(trigger.table()));
+            })
+        })
+    }
+
+    fn render_alter_domain(&self, alter: &AlterDomain, schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
+        let exts: MigrationPair<&PostgresSchemaExt> = schemas.map(|schema| schema.downcast_connector_data());
+        let domains = exts.zip(alter.ids).map(|(ext, id)| ext.get_domain(id));
+
+        alter
+            .changes
+            .iter()
+            .flat_map(|change| {
+                render_step(&mut |step| match change {
+                    DomainChange::AlterBaseType => step.render_statement(&mut |stmt| {
+                        // There is no `ALTER DOMAIN ... TYPE`; a changed base type is rendered as
+                        // a drop and recreate by the differ, so this arm is unreachable in
+                        // practice, but kept for exhaustiveness.
+                        stmt.push_str("ALTER DOMAIN ");
+                        stmt.push_display(&Quoted::postgres_ident(&domains.previous.name));
+                        stmt.push_str(" TYPE ");
+                        stmt.push_str(&domains.next.base_type);
+                    }),
+                    DomainChange::AlterNotNull => step.render_statement(&mut |stmt| {
+                        stmt.push_str("ALTER DOMAIN ");
+                        stmt.push_display(&Quoted::postgres_ident(&domains.previous.name));
+                        stmt.push_str(if domains.next.not_null { " SET NOT NULL" } else { " DROP NOT NULL" });
+                    }),
+                    DomainChange::AlterDefault => step.render_statement(&mut |stmt| {
+                        stmt.push_str("ALTER DOMAIN ");
+                        stmt.push_display(&Quoted::postgres_ident(&domains.previous.name));
+
+                        match &domains.next.default {
+                            Some(default) => {
+                                stmt.push_str(" SET DEFAULT ");
+                                stmt.push_str(default);
+                            }
+                            None => stmt.push_str(" DROP DEFAULT"),
+                        }
+                    }),
+                    DomainChange::AlterCheck => step.render_statement(&mut |stmt| {
+                        stmt.push_str("ALTER DOMAIN ");
+                        stmt.push_display(&Quoted::postgres_ident(&domains.previous.name));
+                        stmt.push_str(" DROP CONSTRAINT IF EXISTS ");
+                        stmt.push_display(&Quoted::postgres_ident(&format!("{}_check", domains.previous.name)));
+
+                        if let Some(check) = &domains.next.check {
+                            stmt.push_str(", ADD CONSTRAINT ");
+                            stmt.push_display(&Quoted::postgres_ident(&format!("{}_check", domains.next.name)));
+                            stmt.push_str(" CHECK (");
+                            stmt.push_str(check);
+                            stmt.push(')');
+                        }
+                    }),
+                })
+            })
+            .collect()
+    }
+
+    fn render_create_policy(&self, create: &CreatePolicy, schema: &SqlSchema) -> Vec<String> {
+        let ext: &PostgresSchemaExt = schema.downcast_connector_data();
+        let policy = ext.get_policy(create.id);
+        let table = schema.walk(policy.table_id);
+
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("CREATE POLICY ");
+                stmt.push_display(&Quoted::postgres_ident(&policy.name));
+                stmt.push_str(" ON ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+                render_policy_definition(stmt, policy);
+            })
+        })
+    }
+
+    fn render_alter_policy(&self, alter: &AlterPolicy, schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
+        let exts: MigrationPair<&PostgresSchemaExt> = schemas.map(|schema| schema.downcast_connector_data());
+        let policies = exts.zip(alter.ids).map(|(ext, id)| ext.get_policy(id));
+        let table = schemas.next.walk(policies.next.table_id);
+
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("ALTER POLICY ");
+                stmt.push_display(&Quoted::postgres_ident(&policies.next.name));
+                stmt.push_str(" ON ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+
+                if alter.changes.contains(&PolicyChange::Roles) {
+                    stmt.push_str(" TO ");
+                    render_policy_roles(stmt, policies.next);
+                }
+
+                if alter.changes.contains(&PolicyChange::Using) {
+                    stmt.push_str(" USING (");
+                    stmt.push_str(policies.next.using.as_deref().unwrap_or("true"));
+                    stmt.push_str(")");
+                }
+
+                if alter.changes.contains(&PolicyChange::WithCheck) {
+                    stmt.push_str(" WITH CHECK (");
+                    stmt.push_str(policies.next.with_check.as_deref().unwrap_or("true"));
+                    stmt.push_str(")");
+                }
+            })
+        })
+    }
+
+    fn render_drop_policy(&self, drop: &DropPolicy, schema: &SqlSchema) -> Vec<String> {
+        let ext: &PostgresSchemaExt = schema.downcast_connector_data();
+        let policy = ext.get_policy(drop.id);
+        let table = schema.walk(policy.table_id);
+
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("DROP POLICY ");
+                stmt.push_display(&Quoted::postgres_ident(&policy.name));
+                stmt.push_str(" ON ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+            })
+        })
+    }
+
+    fn render_enable_row_level_security(&self, table: TableWalker<'_>) -> Vec<String> {
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("ALTER TABLE ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+                stmt.push_str(" ENABLE ROW LEVEL SECURITY");
+            })
+        })
+    }
+
+    fn render_disable_row_level_security(&self, table: TableWalker<'_>) -> Vec<String> {
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("ALTER TABLE ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+                stmt.push_str(" DISABLE ROW LEVEL SECURITY");
+            })
+        })
+    }
+
+    fn render_add_table_inheritance(&self, table: TableWalker<'_>, parent_table: TableWalker<'_>) -> Vec<String> {
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("ALTER TABLE ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+                stmt.push_str(" INHERIT ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(parent_table));
+            })
+        })
+    }
+
+    fn render_drop_table_inheritance(&self, table: TableWalker<'_>, parent_table: TableWalker<'_>) -> Vec<String> {
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_str("ALTER TABLE ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(table));
+                stmt.push_str(" NO INHERIT ");
+                stmt.push_display(&QuotedWithPrefix::pg_from_table_walker(parent_table));
+            })
+        })
+    }
+
     fn quote<'a>(&self, name: &'a str) -> Quoted<&'a str> {
         Quoted::postgres_ident(name)
     }
 
-    fn render_add_foreign_key(&self, foreign_key: ForeignKeyWalker<'_>) -> String {
+    fn render_add_foreign_key(&self, foreign_key: ForeignKeyWalker<'_>, deferred: bool) -> String {
         ddl::AlterTable {
             table_name: &QuotedWithPrefix::pg_from_table_walker(foreign_key.table()),
             clauses: vec![ddl::AlterTableClause::AddForeignKey(ddl::ForeignKey {
@@ -174,6 +426,7 @@ impl SqlRenderer for PostgresFlavour {
                     ForeignKeyAction::SetDefault => ddl::ForeignKeyAction::SetDefault,
                     ForeignKeyAction::SetNull => ddl::ForeignKeyAction::SetNull,
                 }),
+                deferrable: deferred,
             })],
         }
         .to_string()
@@ -228,6 +481,27 @@ impl SqlRenderer for PostgresFlavour {
         })
     }
 
+    fn render_comment_on_index(&self, indexes: MigrationPair<IndexWalker<'_>>) -> String {
+        let previous_table = indexes.previous.table();
+        let index_name = QuotedWithPrefix::pg_new(previous_table.namespace(), indexes.previous.name());
+
+        match indexes.next.description() {
+            Some(description) => format!(
+                "COMMENT ON INDEX {index_name} IS '{}'",
+                escape_string_literal(description)
+            ),
+            None => format!("COMMENT ON INDEX {index_name} IS NULL"),
+        }
+    }
+
+    fn render_alter_index_tablespace(&self, indexes: MigrationPair<IndexWalker<'_>>) -> String {
+        let previous_table = indexes.previous.table();
+        let index_name = QuotedWithPrefix::pg_new(previous_table.namespace(), indexes.previous.name());
+        let tablespace = indexes.next.tablespace().unwrap_or("pg_default");
+
+        format!("ALTER INDEX {index_name} SET TABLESPACE {}", Quoted::postgres_ident(tablespace))
+    }
+
     fn render_alter_table(&self, alter_table: &AlterTable, schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
         let AlterTable { changes, table_ids } = alter_table;
         let mut lines = Vec::new();
@@ -266,6 +540,7 @@ impl SqlRenderer for PostgresFlavour {
                 TableChange::AddColumn {
                     column_id,
                     has_virtual_default: _,
+                    preceding_column: _,
                 } => {
                     let column = schemas.next.walk(*column_id);
                     let col_sql = self.render_column(column);
@@ -280,12 +555,14 @@ impl SqlRenderer for PostgresFlavour {
                     column_id,
                     changes,
                     type_change: _,
+                    type_override,
                 }) => {
                     let columns = schemas.walk(*column_id);
 
                     render_alter_column(
                         columns,
                         changes,
+                        type_override.as_deref(),
                         &mut before_statements,
                         &mut lines,
                         &mut after_statements,
@@ -301,6 +578,66 @@ impl SqlRenderer for PostgresFlavour {
                     let col_sql = self.render_column(columns.next);
                     lines.push(format!("ADD COLUMN {col_sql}"));
                 }
+                TableChange::DropExclusionConstraint { constraint_name } => {
+                    lines.push(format!("DROP CONSTRAINT {}", self.quote(constraint_name)));
+                }
+                TableChange::AddExclusionConstraint {
+                    constraint_name,
+                    definition,
+                } => {
+                    lines.push(format!(
+                        "ADD CONSTRAINT {} {definition}",
+                        self.quote(constraint_name)
+                    ));
+                }
+                TableChange::AddCheckConstraint { .. } => unreachable!("AddCheckConstraint on Postgres"),
+                TableChange::DropCheckConstraint { .. } => unreachable!("DropCheckConstraint on Postgres"),
+                TableChange::AlterTablePersistence { unlogged } => {
+                    lines.push(format!("SET {}", if *unlogged { "UNLOGGED" } else { "LOGGED" }));
+                }
+                TableChange::AlterTableCollation { .. } => unreachable!("AlterTableCollation on Postgres"),
+                TableChange::AlterTableTablespace { tablespace } => {
+                    lines.push(format!("SET TABLESPACE {}", Quoted::postgres_ident(tablespace)));
+                }
+                TableChange::AddForeignKey {
+                    foreign_key_id,
+                    deferred,
+                } => {
+                    let foreign_key = schemas.next.walk(*foreign_key_id);
+
+                    lines.push(
+                        ddl::AlterTableClause::AddForeignKey(ddl::ForeignKey {
+                            constrained_columns: foreign_key.constrained_columns().map(|c| c.name().into()).collect(),
+                            referenced_columns: foreign_key.referenced_columns().map(|c| c.name().into()).collect(),
+                            constraint_name: foreign_key.constraint_name().map(From::from),
+                            referenced_table: &QuotedWithPrefix::pg_from_table_walker(foreign_key.referenced_table()),
+                            on_delete: Some(match foreign_key.on_delete_action() {
+                                ForeignKeyAction::Cascade => ddl::ForeignKeyAction::Cascade,
+                                ForeignKeyAction::NoAction => ddl::ForeignKeyAction::NoAction,
+                                ForeignKeyAction::Restrict => ddl::ForeignKeyAction::Restrict,
+                                ForeignKeyAction::SetDefault => ddl::ForeignKeyAction::SetDefault,
+                                ForeignKeyAction::SetNull => ddl::ForeignKeyAction::SetNull,
+                            }),
+                            on_update: Some(match foreign_key.on_update_action() {
+                                ForeignKeyAction::Cascade => ddl::ForeignKeyAction::Cascade,
+                                ForeignKeyAction::NoAction => ddl::ForeignKeyAction::NoAction,
+                                ForeignKeyAction::Restrict => ddl::ForeignKeyAction::Restrict,
+                                ForeignKeyAction::SetDefault => ddl::ForeignKeyAction::SetDefault,
+                                ForeignKeyAction::SetNull => ddl::ForeignKeyAction::SetNull,
+                            }),
+                            deferrable: *deferred,
+                        })
+                        .to_string(),
+                    );
+                }
+                TableChange::DropForeignKey { foreign_key_id } => {
+                    let foreign_key = schemas.previous.walk(*foreign_key_id);
+
+                    lines.push(format!(
+                        "DROP CONSTRAINT {}",
+                        Quoted::postgres_ident(foreign_key.constraint_name().unwrap())
+                    ));
+                }
             };
         }
 
@@ -354,40 +691,25 @@ impl SqlRenderer for PostgresFlavour {
     }
 
     fn render_create_index(&self, index: IndexWalker<'_>) -> String {
-        let pg_ext: &PostgresSchemaExt = index.schema.downcast_connector_data();
+        self.render_create_index_impl(index, false)
+    }
 
-        ddl::CreateIndex {
-            index_name: index.name().into(),
-            is_unique: index.is_unique(),
-            table_reference: &QuotedWithPrefix::pg_from_table_walker(index.table()),
-            using: Some(match pg_ext.index_algorithm(index.id) {
-                SqlIndexAlgorithm::BTree => ddl::IndexAlgorithm::BTree,
-                SqlIndexAlgorithm::Hash => ddl::IndexAlgorithm::Hash,
-                SqlIndexAlgorithm::Gist => ddl::IndexAlgorithm::Gist,
-                SqlIndexAlgorithm::Gin => ddl::IndexAlgorithm::Gin,
-                SqlIndexAlgorithm::SpGist => ddl::IndexAlgorithm::SpGist,
-                SqlIndexAlgorithm::Brin => ddl::IndexAlgorithm::Brin,
-            }),
-            columns: index
-                .columns()
-                .map(|c| IndexColumn {
-                    name: c.as_column().name().into(),
-                    length: None,
-                    sort_order: c.sort_order().map(|so| match so {
-                        SQLSortOrder::Asc => SortOrder::Asc,
-                        SQLSortOrder::Desc => SortOrder::Desc,
-                    }),
-                    operator_class: pg_ext.get_opclass(c.id).map(|c| c.kind.as_ref().into()),
-                })
-                .collect(),
-        }
-        .to_string()
+    fn render_create_index_concurrently(&self, index: IndexWalker<'_>) -> String {
+        self.render_create_index_impl(index, true)
     }
 
     fn render_create_namespace(&self, ns: sql_schema_describer::NamespaceWalker<'_>) -> String {
         format!("CREATE SCHEMA IF NOT EXISTS {}", Quoted::postgres_ident(ns.name()))
     }
 
+    // CASCADE is required here: dropping a schema that still contains objects (e.g. a table we
+    // failed to migrate away, or one outside Prisma's control) otherwise fails outright on
+    // Postgres. The contained tables should already be gone by the time this step runs, since
+    // `SqlMigrationStep`'s `Ord` places `DropSchema` after `DropTable`.
+    fn render_drop_namespace(&self, namespace: sql_schema_describer::NamespaceWalker<'_>) -> String {
+        format!("DROP SCHEMA IF EXISTS {} CASCADE", Quoted::postgres_ident(namespace.name()))
+    }
+
     fn render_create_table(&self, table: TableWalker<'_>) -> String {
         self.render_create_table_as(table, QuotedWithPrefix::pg_from_table_walker(table))
     }
@@ -408,7 +730,9 @@ impl SqlRenderer for PostgresFlavour {
             String::new()
         };
 
-        format!("CREATE TABLE {table_name} (\n{columns}{pk}\n)")
+        let unlogged = if table.is_unlogged() { "UNLOGGED " } else { "" };
+
+        format!("CREATE {unlogged}TABLE {table_name} (\n{columns}{pk}\n)")
     }
 
     fn render_drop_enum(&self, dropped_enum: EnumWalker<'_>) -> Vec<String> {
@@ -454,6 +778,17 @@ impl SqlRenderer for PostgresFlavour {
         .to_string()
     }
 
+    fn render_truncate_table(&self, table: TableWalker<'_>, cascade: bool) -> Vec<String> {
+        render_step(&mut |step| {
+            step.render_statement(&mut |stmt| {
+                stmt.push_display(&ddl::TruncateTable {
+                    table_name: PostgresIdentifier::new(table.namespace(), table.name()),
+                    cascade,
+                })
+            })
+        })
+    }
+
     fn render_redefine_tables(&self, tables: &[RedefineTable], schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
         let mut result = Vec::new();
 
@@ -501,7 +836,7 @@ impl SqlRenderer for PostgresFlavour {
             }
 
             for fk in tables.next.foreign_keys() {
-                result.push(self.render_add_foreign_key(fk));
+                result.push(self.render_add_foreign_key(fk, false));
             }
         }
 
@@ -528,6 +863,19 @@ impl SqlRenderer for PostgresFlavour {
             next = self.quote(fks.next.constraint_name().unwrap()),
         )
     }
+
+    fn render_comment_on_constraint(&self, fks: MigrationPair<ForeignKeyWalker<'_>>) -> String {
+        let table = QuotedWithPrefix::pg_from_table_walker(fks.previous.table());
+        let constraint_name = self.quote(fks.previous.constraint_name().unwrap());
+
+        match fks.next.description() {
+            Some(description) => format!(
+                "COMMENT ON CONSTRAINT {constraint_name} ON {table} IS '{}'",
+                escape_string_literal(description)
+            ),
+            None => format!("COMMENT ON CONSTRAINT {constraint_name} ON {table} IS NULL"),
+        }
+    }
 }
 
 fn render_column_type(col: TableColumnWalker<'_>, flavour: &PostgresFlavour) -> Cow<'static, str> {
@@ -678,9 +1026,53 @@ fn escape_string_literal(s: &str) -> Cow<'_, str> {
     Cow::Owned(out)
 }
 
+/// Render the `AS ... FOR ... TO ... USING (...) WITH CHECK (...)` tail shared by `CREATE POLICY`
+/// statements.
+fn render_policy_definition(stmt: &mut StatementRenderer, policy: &Policy) {
+    stmt.push_str(" AS ");
+    stmt.push_str(if policy.permissive { "PERMISSIVE" } else { "RESTRICTIVE" });
+
+    stmt.push_str(" FOR ");
+    stmt.push_str(match policy.command {
+        PolicyCommand::Select => "SELECT",
+        PolicyCommand::Insert => "INSERT",
+        PolicyCommand::Update => "UPDATE",
+        PolicyCommand::Delete => "DELETE",
+        PolicyCommand::All => "ALL",
+    });
+
+    stmt.push_str(" TO ");
+    render_policy_roles(stmt, policy);
+
+    if let Some(using) = &policy.using {
+        stmt.push_str(" USING (");
+        stmt.push_str(using);
+        stmt.push_str(")");
+    }
+
+    if let Some(with_check) = &policy.with_check {
+        stmt.push_str(" WITH CHECK (");
+        stmt.push_str(with_check);
+        stmt.push_str(")");
+    }
+}
+
+/// Render a policy's roles, or `PUBLIC` for a policy that applies to every role. We don't
+/// validate that the roles referred to by a policy actually exist: Postgres allows a policy to
+/// reference a role that doesn't exist yet (e.g. one created by a later migration, or managed
+/// outside of Prisma altogether), so we pass role names through verbatim.
+fn render_policy_roles(stmt: &mut StatementRenderer, policy: &Policy) {
+    if policy.roles.iter().any(|role| role == "public") {
+        stmt.push_str("PUBLIC");
+    } else {
+        stmt.join(", ", policy.roles.iter().map(|role| Quoted::postgres_ident(role)));
+    }
+}
+
 fn render_alter_column(
     columns: MigrationPair<TableColumnWalker<'_>>,
     column_changes: &ColumnChanges,
+    type_override: Option<&str>,
     before_statements: &mut Vec<String>,
     clauses: &mut Vec<String>,
     after_statements: &mut Vec<String>,
@@ -689,6 +1081,10 @@ fn render_alter_column(
     let steps = expand_alter_column(columns, column_changes);
     let table_name = QuotedWithPrefix::pg_from_table_walker(columns.previous.table());
     let column_name = Quoted::postgres_ident(columns.previous.name());
+    let next_type = match type_override {
+        Some(type_override) => Cow::Borrowed(type_override),
+        None => render_column_type(columns.next, flavour),
+    };
 
     let alter_column_prefix = format!("ALTER COLUMN {column_name}");
 
@@ -709,14 +1105,17 @@ fn render_alter_column(
             PostgresAlterColumn::SetDefault(new_default) => clauses.push(format!(
                 "{} SET DEFAULT {}",
                 &alter_column_prefix,
-                render_default(&new_default, &render_column_type(columns.next, flavour))
+                render_default(&new_default, &next_type)
             )),
             PostgresAlterColumn::DropNotNull => clauses.push(format!("{} DROP NOT NULL", &alter_column_prefix)),
             PostgresAlterColumn::SetNotNull => clauses.push(format!("{} SET NOT NULL", &alter_column_prefix)),
-            PostgresAlterColumn::SetType => clauses.push(format!(
-                "{} SET DATA TYPE {}",
-                &alter_column_prefix,
-                render_column_type(columns.next, flavour)
+            PostgresAlterColumn::SetType => {
+                clauses.push(format!("{} SET DATA TYPE {}", &alter_column_prefix, next_type))
+            }
+            PostgresAlterColumn::SetTypeUsingTimeZoneConversion => clauses.push(format!(
+                "{prefix} SET DATA TYPE {tpe} USING {column_name} AT TIME ZONE 'UTC'",
+                prefix = &alter_column_prefix,
+                tpe = next_type,
             )),
             PostgresAlterColumn::AddSequence => {
                 // We imitate the sequence that would be automatically created on a `SERIAL` column.
@@ -746,6 +1145,14 @@ fn render_alter_column(
                     "ALTER SEQUENCE {sequence_name} OWNED BY {table_name}.{column_name}",
                 ));
             }
+            PostgresAlterColumn::SetStorage(storage) => {
+                clauses.push(format!("{} SET STORAGE {}", &alter_column_prefix, storage.as_sql()))
+            }
+            PostgresAlterColumn::RenameNotNullConstraint { from, to } => clauses.push(format!(
+                "RENAME CONSTRAINT {} TO {}",
+                Quoted::postgres_ident(&from),
+                Quoted::postgres_ident(&to)
+            )),
         }
     }
 }
@@ -791,17 +1198,58 @@ fn expand_alter_column(
                     changes.push(PostgresAlterColumn::AddSequence)
                 }
             }
+            ColumnChange::Storage => changes.push(PostgresAlterColumn::SetStorage(
+                columns.next.toast_storage().unwrap_or(ColumnStorage::Extended),
+            )),
+            ColumnChange::NotNullConstraintName => changes.push(PostgresAlterColumn::RenameNotNullConstraint {
+                from: columns
+                    .previous
+                    .not_null_constraint_name()
+                    .map(str::to_owned)
+                    .unwrap_or_else(|| default_not_null_constraint_name(columns.previous)),
+                to: columns
+                    .next
+                    .not_null_constraint_name()
+                    .map(str::to_owned)
+                    .unwrap_or_else(|| default_not_null_constraint_name(columns.next)),
+            }),
         }
     }
 
     // This is a flag so we don't push multiple SetTypes from arity and type changes.
     if set_type {
-        changes.push(PostgresAlterColumn::SetType);
+        if is_timestamp_timezone_conversion(columns) {
+            changes.push(PostgresAlterColumn::SetTypeUsingTimeZoneConversion);
+        } else {
+            changes.push(PostgresAlterColumn::SetType);
+        }
     }
 
     changes
 }
 
+/// Postgres's own implicit naming convention for a `NOT NULL` constraint that wasn't given an
+/// explicit name, used as a stand-in when we need to name one side of a
+/// [`PostgresAlterColumn::RenameNotNullConstraint`] but introspection didn't catalogue a name
+/// for it.
+fn default_not_null_constraint_name(column: TableColumnWalker<'_>) -> String {
+    format!("{}_{}_not_null", column.table().name(), column.name())
+}
+
+/// Is this a `timestamp` <-> `timestamptz` conversion? Such a cast reinterprets every value
+/// according to the session time zone, so it gets an explicit `USING ... AT TIME ZONE` clause
+/// instead of Postgres's implicit cast.
+fn is_timestamp_timezone_conversion(columns: MigrationPair<TableColumnWalker<'_>>) -> bool {
+    let previous_type: Option<&PostgresType> = columns.previous.column_native_type();
+    let next_type: Option<&PostgresType> = columns.next.column_native_type();
+
+    matches!(
+        (previous_type, next_type),
+        (Some(PostgresType::Timestamp(_)), Some(PostgresType::Timestamptz(_)))
+            | (Some(PostgresType::Timestamptz(_)), Some(PostgresType::Timestamp(_)))
+    )
+}
+
 /// https://www.postgresql.org/docs/9.1/sql-altertable.html
 #[derive(Debug)]
 enum PostgresAlterColumn {
@@ -809,9 +1257,26 @@ enum PostgresAlterColumn {
     DropDefault,
     DropNotNull,
     SetType,
+    /// Like `SetType`, but for a `timestamp` <-> `timestamptz` conversion, which reinterprets
+    /// every value according to the session time zone rather than just relabelling the column.
+    /// Spelling out `USING ... AT TIME ZONE 'UTC'` makes that reinterpretation explicit instead of
+    /// leaving it to Postgres's implicit cast and whatever time zone the migration happens to run
+    /// in.
+    SetTypeUsingTimeZoneConversion,
     SetNotNull,
     /// Add an auto-incrementing sequence as a default on the column.
     AddSequence,
+    /// `SET STORAGE <keyword>`. When the diffed-to storage is `None` (reverting to the column
+    /// type's implicit default), there's no `SET STORAGE DEFAULT` to fall back to, so
+    /// [`expand_alter_column`] substitutes `Extended`, the implicit default for most TOASTable
+    /// types.
+    SetStorage(ColumnStorage),
+    /// `RENAME CONSTRAINT <from> TO <to>`, for a `NOT NULL` constraint whose name changed. Unlike
+    /// the other variants, this isn't rendered with the `ALTER COLUMN <column>` prefix: constraint
+    /// names live in the table's namespace, not the column's. When one side has no catalogued
+    /// name, [`expand_alter_column`] falls back to Postgres's own implicit naming convention
+    /// (`<table>_<column>_not_null`) rather than leaving that side unrenderable.
+    RenameNotNullConstraint { from: String, to: String },
 }
 
 fn render_default<'a>(default: &'a DefaultValue, full_data_type: &str) -> Cow<'a, str> {
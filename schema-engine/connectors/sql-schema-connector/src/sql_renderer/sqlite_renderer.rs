@@ -1,14 +1,15 @@
 use super::{common::*, SqlRenderer};
 use crate::{
-    flavour::SqliteFlavour,
+    flavour::{SqlFlavour, SqliteFlavour},
     migration_pair::MigrationPair,
-    sql_migration::{AlterEnum, AlterTable, RedefineTable, TableChange},
+    sql_migration::{AlterEnum, AlterTable, CreateTrigger, DropTrigger, RedefineTable, TableChange},
 };
 use indoc::formatdoc;
 use once_cell::sync::Lazy;
+use quaint::connector::rusqlite;
 use regex::Regex;
 use sql_ddl::sqlite as ddl;
-use sql_schema_describer::{walkers::*, *};
+use sql_schema_describer::{sqlite::SqliteSchemaExt, walkers::*, *};
 use std::borrow::Cow;
 
 impl SqlRenderer for SqliteFlavour {
@@ -36,7 +37,7 @@ impl SqlRenderer for SqliteFlavour {
             rendered
         });
 
-        let index_create = format!(
+        let mut index_create = format!(
             "CREATE {index_type}INDEX {index_name} ON {table_reference}({columns})",
             index_type = index_type,
             index_name = index_name,
@@ -44,6 +45,13 @@ impl SqlRenderer for SqliteFlavour {
             columns = columns.join(", ")
         );
 
+        // A partial index's predicate can reference columns that aren't part of the index
+        // itself, so it's rendered verbatim rather than going through the column list above.
+        if let Some(predicate) = index.predicate() {
+            index_create.push_str(" WHERE ");
+            index_create.push_str(predicate);
+        }
+
         if index.name().starts_with("sqlite_") {
             formatdoc!(
                 "Pragma writable_schema=1;
@@ -56,7 +64,7 @@ impl SqlRenderer for SqliteFlavour {
         }
     }
 
-    fn render_add_foreign_key(&self, _foreign_key: ForeignKeyWalker<'_>) -> String {
+    fn render_add_foreign_key(&self, _foreign_key: ForeignKeyWalker<'_>, _deferred: bool) -> String {
         unreachable!("AddForeignKey on SQLite")
     }
 
@@ -73,6 +81,7 @@ impl SqlRenderer for SqliteFlavour {
                 TableChange::AddColumn {
                     column_id,
                     has_virtual_default: _,
+                    preceding_column: _,
                 } => {
                     let column = schemas.next.walk(*column_id);
                     let col_sql = render_column(&column);
@@ -89,6 +98,15 @@ impl SqlRenderer for SqliteFlavour {
                 TableChange::DropColumn { .. } => unreachable!("DropColumn on SQLite"),
                 TableChange::DropPrimaryKey { .. } => unreachable!("DropPrimaryKey on SQLite"),
                 TableChange::RenamePrimaryKey { .. } => unreachable!("AddPrimaryKey on SQLite"),
+                TableChange::AddExclusionConstraint { .. } => unreachable!("AddExclusionConstraint on SQLite"),
+                TableChange::DropExclusionConstraint { .. } => unreachable!("DropExclusionConstraint on SQLite"),
+                TableChange::AddCheckConstraint { .. } => unreachable!("AddCheckConstraint on SQLite"),
+                TableChange::DropCheckConstraint { .. } => unreachable!("DropCheckConstraint on SQLite"),
+                TableChange::AlterTablePersistence { .. } => unreachable!("AlterTablePersistence on SQLite"),
+                TableChange::AlterTableCollation { .. } => unreachable!("AlterTableCollation on SQLite"),
+                TableChange::AlterTableTablespace { .. } => unreachable!("AlterTableTablespace on SQLite"),
+                TableChange::AddForeignKey { .. } => unreachable!("AddForeignKey on SQLite"),
+                TableChange::DropForeignKey { .. } => unreachable!("DropForeignKey on SQLite"),
             };
         }
 
@@ -133,6 +151,10 @@ impl SqlRenderer for SqliteFlavour {
                     }),
                 })
                 .collect(),
+            without_rowid: {
+                let ext: &SqliteSchemaExt = table.schema.downcast_connector_data();
+                ext.table_is_without_rowid(table.id)
+            },
         };
 
         if !table.columns().any(|col| col.is_single_primary_key()) {
@@ -182,6 +204,11 @@ impl SqlRenderer for SqliteFlavour {
         })
     }
 
+    fn render_truncate_table(&self, table: TableWalker<'_>, _cascade: bool) -> Vec<String> {
+        // SQLite has no TRUNCATE statement; deleting every row has the same effect.
+        vec![format!("DELETE FROM {}", Quoted::sqlite_ident(table.name()))]
+    }
+
     fn render_redefine_tables(&self, tables: &[RedefineTable], schemas: MigrationPair<&SqlSchema>) -> Vec<String> {
         // Based on 'Making Other Kinds Of Table Schema Changes' from https://www.sqlite.org/lang_altertable.html,
         // and on https://developers.cloudflare.com/d1/reference/database-commands/#pragma-defer_foreign_keys--onoff.
@@ -203,7 +230,24 @@ impl SqlRenderer for SqliteFlavour {
                 QuotedWithPrefix(None, Quoted::sqlite_ident(&temporary_table_name)),
             ));
 
-            copy_current_table_into_new_table(&mut result, redefine_table, tables, &temporary_table_name);
+            // An `AUTOINCREMENT` table's high-water-mark rowid lives in `sqlite_sequence`, keyed
+            // by table name, not in the table itself. Re-point the previous table's row at the
+            // temporary table before we drop the original, so a later `INSERT` into the
+            // redefined table can't reuse a rowid that belonged to a deleted row. `ALTER TABLE
+            // ... RENAME TO` below takes care of pointing it at the final table name again.
+            if tables
+                .next
+                .columns()
+                .any(|col| col.is_single_primary_key() && col.column_type_family().is_int())
+            {
+                result.push(format!(
+                    r#"UPDATE "sqlite_sequence" SET "name" = {temporary_table_name} WHERE "name" = {old_name}"#,
+                    temporary_table_name = Quoted::sqlite_string(escape_quotes(&temporary_table_name)),
+                    old_name = Quoted::sqlite_string(escape_quotes(tables.previous.name())),
+                ));
+            }
+
+            copy_current_table_into_new_table(self, &mut result, redefine_table, tables, &temporary_table_name);
 
             result.push(format!(r#"DROP TABLE "{}""#, tables.previous.name()));
 
@@ -248,9 +292,35 @@ impl SqlRenderer for SqliteFlavour {
         unreachable!("render_drop_user_defined_type on SQLite")
     }
 
+    fn render_create_trigger(&self, create: &CreateTrigger, schema: &SqlSchema) -> Vec<String> {
+        // `sqlite_master.sql` already holds the full, verbatim `CREATE TRIGGER` statement.
+        vec![schema.walk(create.id).definition().to_owned()]
+    }
+
+    fn render_drop_trigger(&self, drop: &DropTrigger, schema: &SqlSchema) -> Vec<String> {
+        vec![format!("DROP TRIGGER {}", Quoted::sqlite_ident(schema.walk(drop.id).name()))]
+    }
+
     fn render_rename_foreign_key(&self, _fks: MigrationPair<ForeignKeyWalker<'_>>) -> String {
         unreachable!("render RenameForeignKey on SQLite")
     }
+
+    fn validate_sql(&self, sql: &str) -> Result<(), String> {
+        super::basic_sql_sanity_check(sql)?;
+
+        // A trigger's body is checked against the schema at creation time, so preparing one
+        // against an empty scratch connection would reject a trigger that only becomes valid
+        // once the tables it references exist — which, in a migration, might only be true a few
+        // statements later in the same batch. Leave those to the basic check above.
+        if sql.trim_start().to_ascii_uppercase().starts_with("CREATE TRIGGER") {
+            return Ok(());
+        }
+
+        let conn = rusqlite::Connection::open_in_memory().map_err(|err| err.to_string())?;
+        conn.prepare(sql).map_err(|err| err.to_string())?;
+
+        Ok(())
+    }
 }
 
 fn render_column_type(t: &ColumnType) -> &str {
@@ -278,10 +348,15 @@ fn escape_quotes(s: &str) -> Cow<'_, str> {
 
 /// Copy the existing data into the new table.
 ///
-/// The process is complicated by the migrations that add make an optional column required with a
-/// default value. In this case, we need to treat them differently and `coalesce`ing them with the
-/// default value, since SQLite does not have the `DEFAULT` keyword.
+/// The process is complicated by migrations that make an optional column required. In that case,
+/// we need to treat them differently and `coalesce` them with a value that existing `NULL` rows
+/// can fall back to, since SQLite does not have the `DEFAULT` keyword. That fallback value is the
+/// column's own default if it has one, or otherwise the flavour's configured
+/// [`SqlFlavour::not_null_coalesce_sentinel`], if any. A column with neither is left unmodified:
+/// the copy will fail on existing `NULL` rows, but that case is already reported by the
+/// destructive change checker as unexecutable ahead of time.
 fn copy_current_table_into_new_table(
+    flavour: &SqliteFlavour,
     steps: &mut Vec<String>,
     redefine_table: &RedefineTable,
     tables: MigrationPair<TableWalker<'_>>,
@@ -299,20 +374,20 @@ fn copy_current_table_into_new_table(
     let source_columns = redefine_table.column_pairs.iter().map(|(column_ides, changes, _)| {
         let columns = tables.map(|t| t.schema).walk(*column_ides);
 
-        let col_became_required_with_a_default =
-            changes.arity_changed() && columns.next.arity().is_required() && columns.next.default().is_some();
+        let col_became_required = changes.arity_changed() && columns.next.arity().is_required();
+
+        let coalesce_value = col_became_required.then(|| {
+            columns
+                .next
+                .default()
+                .map(|default| render_default(default.inner()).into_owned())
+                .or_else(|| flavour.not_null_coalesce_sentinel().map(String::from))
+        });
 
-        if col_became_required_with_a_default {
+        if let Some(coalesce_value) = coalesce_value.flatten() {
             format!(
-                "coalesce({column_name}, {default_value}) AS {column_name}",
+                "coalesce({column_name}, {coalesce_value}) AS {column_name}",
                 column_name = Quoted::sqlite_ident(columns.previous.name()),
-                default_value = render_default(
-                    columns
-                        .next
-                        .default()
-                        .expect("default on required column with default")
-                        .inner()
-                )
             )
         } else {
             Quoted::sqlite_ident(columns.previous.name()).to_string()
@@ -367,3 +442,218 @@ fn render_default(default: &DefaultValue) -> Cow<'_, str> {
         DefaultKind::DbGenerated(None) | DefaultKind::Sequence(_) | DefaultKind::UniqueRowid => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sql_schema_describer::{ColumnArity, ColumnType, ColumnTypeFamily, IndexColumn};
+
+    fn test_schema() -> (SqlSchema, TableId) {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("Cat".to_owned(), Default::default(), None);
+
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "id".to_owned(),
+                tpe: ColumnType::pure(ColumnTypeFamily::Int, ColumnArity::Required),
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+        let deleted_at = schema.push_table_column(
+            table_id,
+            Column {
+                name: "deletedAt".to_owned(),
+                tpe: ColumnType::pure(ColumnTypeFamily::DateTime, ColumnArity::Nullable),
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+        let name = schema.push_table_column(
+            table_id,
+            Column {
+                name: "name".to_owned(),
+                tpe: ColumnType::pure(ColumnTypeFamily::String, ColumnArity::Required),
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        let index_id = schema.push_index(table_id, "Cat_name_idx".to_owned());
+        schema.push_index_column(IndexColumn {
+            index_id,
+            column_id: name,
+            sort_order: None,
+            length: None,
+            collation: None,
+        });
+        schema.set_index_predicate(index_id, format!("\"{}\" IS NULL", schema.walk(deleted_at).name()));
+        schema.set_connector_data(Box::new(SqliteSchemaExt::default()));
+
+        (schema, table_id)
+    }
+
+    #[test]
+    fn render_create_index_renders_a_partial_index_predicate() {
+        let (schema, table_id) = test_schema();
+        let table = schema.walk(table_id);
+        let index = table.indexes().next().unwrap();
+
+        let rendered = SqliteFlavour::default().render_create_index(index);
+
+        assert_eq!(
+            rendered,
+            r#"CREATE INDEX "Cat_name_idx" ON "Cat"("name") WHERE "deletedAt" IS NULL"#
+        );
+    }
+
+    #[test]
+    fn render_create_index_predicate_can_reference_a_column_not_in_the_index() {
+        // The predicate above references `deletedAt`, which isn't one of the indexed columns
+        // (only `name` is). The renderer must not try to validate or re-derive the predicate
+        // from the index's own columns: it's opaque, verbatim SQL.
+        let (schema, table_id) = test_schema();
+        let table = schema.walk(table_id);
+        let index = table.indexes().next().unwrap();
+
+        assert_eq!(index.column_names().collect::<Vec<_>>(), vec!["name"]);
+        assert!(index.predicate().unwrap().contains("deletedAt"));
+    }
+
+    #[test]
+    fn render_truncate_table_deletes_all_rows() {
+        let (schema, table_id) = test_schema();
+        let table = schema.walk(table_id);
+
+        let rendered = SqliteFlavour::default().render_truncate_table(table, false);
+
+        assert_eq!(rendered, vec![r#"DELETE FROM "Cat""#.to_owned()]);
+    }
+
+    #[test]
+    fn render_create_table_appends_without_rowid_when_set() {
+        let (mut schema, table_id) = test_schema();
+
+        schema.set_connector_data(Box::new(SqliteSchemaExt {
+            without_rowid_tables: std::collections::HashSet::from([table_id]),
+            ..Default::default()
+        }));
+
+        let table = schema.walk(table_id);
+        let rendered = SqliteFlavour::default().render_create_table(table);
+
+        assert!(
+            rendered.ends_with(") WITHOUT ROWID"),
+            "expected the statement to end with `WITHOUT ROWID`, got: {rendered}"
+        );
+    }
+
+    #[test]
+    fn render_create_table_omits_without_rowid_by_default() {
+        let (schema, table_id) = test_schema();
+        let table = schema.walk(table_id);
+
+        let rendered = SqliteFlavour::default().render_create_table(table);
+
+        assert!(!rendered.contains("WITHOUT ROWID"));
+    }
+
+    fn redefine_table(previous_table_id: TableId, next_table_id: TableId) -> RedefineTable {
+        RedefineTable {
+            added_columns: Vec::new(),
+            added_columns_with_virtual_defaults: Vec::new(),
+            dropped_columns: Vec::new(),
+            dropped_primary_key: false,
+            column_pairs: Vec::new(),
+            table_ids: MigrationPair::new(previous_table_id, next_table_id),
+            checks_changed: false,
+        }
+    }
+
+    #[test]
+    fn render_redefine_tables_brackets_the_redefine_with_defer_foreign_keys() {
+        let (previous, previous_table_id) = test_schema();
+        let (next, next_table_id) = test_schema();
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let tables = vec![redefine_table(previous_table_id, next_table_id)];
+
+        let rendered = SqliteFlavour::default().render_redefine_tables(&tables, schemas);
+
+        assert_eq!(rendered.first().unwrap(), "PRAGMA defer_foreign_keys=ON");
+        assert_eq!(rendered.get(1).unwrap(), "PRAGMA foreign_keys=OFF");
+        assert_eq!(rendered[rendered.len() - 2], "PRAGMA foreign_keys=ON");
+        assert_eq!(rendered.last().unwrap(), "PRAGMA defer_foreign_keys=OFF");
+    }
+
+    #[test]
+    fn render_redefine_tables_brackets_multiple_table_redefines_in_a_single_pragma_pair() {
+        // Several tables redefined in the same migration must still be wrapped by a single
+        // `PRAGMA defer_foreign_keys` pair, not one per table, since the pragma is
+        // connection-scoped rather than per-statement.
+        let (mut previous, first_previous_table_id) = test_schema();
+        let (mut next, first_next_table_id) = test_schema();
+
+        let second_previous_table_id = previous.push_table("Dog".to_owned(), Default::default(), None);
+        let second_next_table_id = next.push_table("Dog".to_owned(), Default::default(), None);
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let tables = vec![
+            redefine_table(first_previous_table_id, first_next_table_id),
+            redefine_table(second_previous_table_id, second_next_table_id),
+        ];
+
+        let rendered = SqliteFlavour::default().render_redefine_tables(&tables, schemas);
+
+        assert_eq!(
+            rendered.iter().filter(|stmt| stmt.as_str() == "PRAGMA defer_foreign_keys=ON").count(),
+            1
+        );
+        assert_eq!(
+            rendered.iter().filter(|stmt| stmt.as_str() == "PRAGMA defer_foreign_keys=OFF").count(),
+            1
+        );
+        assert_eq!(rendered.first().unwrap(), "PRAGMA defer_foreign_keys=ON");
+        assert_eq!(rendered.last().unwrap(), "PRAGMA defer_foreign_keys=OFF");
+    }
+
+    #[test]
+    fn validate_sql_accepts_well_formed_statements() {
+        let flavour = SqliteFlavour::default();
+
+        assert!(flavour.validate_sql(r#"CREATE TABLE "Cat" ("id" INTEGER PRIMARY KEY)"#).is_ok());
+    }
+
+    #[test]
+    fn validate_sql_catches_a_malformed_statement() {
+        let flavour = SqliteFlavour::default();
+
+        assert!(flavour.validate_sql(r#"CREATE TABLE "Cat" ("id" INTEGER PRIMARY KEY"#).is_err());
+        assert!(flavour.validate_sql("").is_err());
+    }
+
+    #[test]
+    fn validate_sql_does_not_reject_a_trigger_that_references_a_not_yet_existing_table() {
+        // `prepare()` against a scratch connection would reject this, since `Dog` doesn't exist
+        // there; but inside an actual migration batch, `Dog` might already have been created by
+        // an earlier statement in the same script.
+        let flavour = SqliteFlavour::default();
+
+        let trigger = r#"CREATE TRIGGER "my_trigger" AFTER INSERT ON "Dog" BEGIN SELECT 1; END"#;
+
+        assert!(flavour.validate_sql(trigger).is_ok());
+    }
+}
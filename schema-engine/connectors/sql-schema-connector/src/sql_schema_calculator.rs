@@ -92,6 +92,7 @@ fn push_model_indexes(model: ModelWalker<'_>, table_id: sql::TableId, ctx: &mut
                     SortOrder::Desc => sql::SQLSortOrder::Desc,
                 }),
                 length: field.length(),
+                collation: None,
             });
         }
     }
@@ -124,6 +125,7 @@ fn push_model_indexes(model: ModelWalker<'_>, table_id: sql::TableId, ctx: &mut
                     SortOrder::Desc => sql::SQLSortOrder::Desc,
                 }),
                 length: sf.length(),
+                collation: None,
             });
         }
     }
@@ -273,12 +275,14 @@ fn push_relation_tables(ctx: &mut Context<'_>) {
                 column_id: column_a_id,
                 sort_order: None,
                 length: None,
+                collation: None,
             });
             ctx.schema.describer_schema.push_index_column(sql::IndexColumn {
                 index_id,
                 column_id: column_b_id,
                 sort_order: None,
                 length: None,
+                collation: None,
             });
         }
 
@@ -294,6 +298,7 @@ fn push_relation_tables(ctx: &mut Context<'_>) {
                 column_id: column_b_id,
                 sort_order: None,
                 length: None,
+                collation: None,
             });
         }
 
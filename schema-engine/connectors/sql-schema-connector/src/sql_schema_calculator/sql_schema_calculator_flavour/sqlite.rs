@@ -1,6 +1,7 @@
 use super::SqlSchemaCalculatorFlavour;
 use crate::flavour::SqliteFlavour;
 use psl::parser_database::{walkers::*, ScalarType};
+use sql_schema_describer::sqlite::SqliteSchemaExt;
 
 impl SqlSchemaCalculatorFlavour for SqliteFlavour {
     // Integer primary keys on SQLite are automatically assigned the rowid, which means they are automatically autoincrementing.
@@ -12,4 +13,29 @@ impl SqlSchemaCalculatorFlavour for SqliteFlavour {
             .unwrap_or(false)
             && field.scalar_type() == Some(ScalarType::Int)
     }
+
+    fn push_connector_data(&self, context: &mut crate::sql_schema_calculator::Context<'_>) {
+        let mut sqlite_ext = SqliteSchemaExt::default();
+
+        // The renderer always spells out `AUTOINCREMENT` for a single-column integer primary
+        // key (see `render_column` in the SQLite renderer), so the desired schema always has the
+        // literal keyword wherever it has a rowid-alias integer primary key. Recording that here
+        // lets the differ notice when the current database, introspected without the keyword,
+        // needs a redefine to add it.
+        for table_id in context.model_id_to_table_id.values() {
+            let table = context.schema.walk(*table_id);
+
+            if let Some(column) = table
+                .columns()
+                .find(|column| column.is_single_primary_key() && column.column_type_family().is_int())
+            {
+                sqlite_ext.autoincrement_columns.insert(column.id);
+            }
+        }
+
+        context
+            .schema
+            .describer_schema
+            .set_connector_data(Box::new(sqlite_ext));
+    }
 }
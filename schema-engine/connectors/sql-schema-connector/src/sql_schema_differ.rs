@@ -8,6 +8,8 @@ mod table;
 pub(crate) use column::{ColumnChange, ColumnChanges};
 pub(crate) use sql_schema_differ_flavour::SqlSchemaDifferFlavour;
 
+pub(crate) use differ_database::DifferSettings;
+
 use self::differ_database::DifferDatabase;
 use crate::{
     database_schema::SqlDatabaseSchema,
@@ -16,7 +18,10 @@ use crate::{
     SqlFlavour,
 };
 use column::ColumnTypeChange;
-use sql_schema_describer::{walkers::ForeignKeyWalker, IndexId, TableColumnId};
+use sql_schema_describer::{
+    walkers::{ForeignKeyWalker, TableWalker},
+    IndexId, TableColumnId, TableId,
+};
 use std::{borrow::Cow, collections::HashSet};
 use table::TableDiffer;
 
@@ -24,12 +29,27 @@ pub(crate) fn calculate_steps(
     schemas: MigrationPair<&SqlDatabaseSchema>,
     flavour: &dyn SqlFlavour,
 ) -> Vec<SqlMigrationStep> {
-    let db = DifferDatabase::new(schemas, flavour);
+    calculate_steps_with_settings(schemas, flavour, DifferSettings::default())
+}
+
+/// Like [`calculate_steps`], but with [`DifferSettings`] escape hatches for behavior that isn't
+/// derived from comparing the two schemas.
+pub(crate) fn calculate_steps_with_settings(
+    schemas: MigrationPair<&SqlDatabaseSchema>,
+    flavour: &dyn SqlFlavour,
+    settings: DifferSettings,
+) -> Vec<SqlMigrationStep> {
+    let db = DifferDatabase::new_with_settings(schemas, flavour, settings);
     let mut steps: Vec<SqlMigrationStep> = Vec::new();
 
     flavour.push_extension_steps(&mut steps, &db);
+    flavour.push_domain_steps(&mut steps, &db);
+    flavour.push_policy_steps(&mut steps, &db);
 
-    push_created_schema_steps(&mut steps, &db);
+    if flavour.supports_multi_schema() {
+        push_created_schema_steps(&mut steps, &db);
+        push_dropped_schema_steps(&mut steps, &db);
+    }
     push_created_table_steps(&mut steps, &db);
     push_dropped_table_steps(&mut steps, &db);
     push_dropped_index_steps(&mut steps, &db);
@@ -37,9 +57,172 @@ pub(crate) fn calculate_steps(
     push_altered_table_steps(&mut steps, &db);
     push_redefined_table_steps(&mut steps, &db);
 
+    // Table inheritance requires the table and column structure above to already be in place, since
+    // Postgres only lets a table INHERIT a parent once their shared columns have matching types.
+    flavour.push_table_inheritance_steps(&mut steps, &db);
+
     flavour.push_enum_steps(&mut steps, &db);
     flavour.push_alter_sequence_steps(&mut steps, &db);
 
+    // Triggers are paired and diffed after the table/column/index structure above is final, since
+    // a dropped column referenced by a trigger body needs the table change to have already been
+    // decided. `steps.sort()` below is what actually orders the resulting DropTrigger/CreateTrigger
+    // steps relative to everything else.
+    flavour.push_trigger_steps(&mut steps, &db);
+
+    dedupe_redundant_index_steps(&mut steps);
+
+    steps.sort();
+
+    if db.settings.defer_foreign_keys {
+        move_foreign_key_additions_to_the_end(&mut steps);
+    }
+
+    steps
+}
+
+/// Like [`calculate_steps`], but hands the steps back as an iterator instead of a `Vec`, for
+/// [`crate::SqlSchemaConnector::apply_diff_streaming`], which applies each step to the database as
+/// it's produced instead of collecting them into a [`crate::sql_migration::SqlMigration`] first.
+///
+/// This does *not* reduce the diff's own peak memory usage: `steps.sort()` in
+/// [`calculate_steps_with_settings`] — together with [`dedupe_redundant_index_steps`] and, when
+/// foreign keys are deferred, [`move_foreign_key_additions_to_the_end`] — needs every step in hand
+/// before it can decide the final order, so the full `Vec` is still built internally before this
+/// hands it off as an iterator. What a caller actually saves is not holding the applied SQL, a
+/// `Migration`, and a changelog all alongside the steps at once; a true incremental,
+/// dependency-correct emission (without a global sort) would mean reworking how ordering is
+/// decided across the whole differ, which is out of scope here.
+pub(crate) fn calculate_steps_streaming(
+    schemas: MigrationPair<&SqlDatabaseSchema>,
+    flavour: &dyn SqlFlavour,
+) -> impl Iterator<Item = SqlMigrationStep> {
+    calculate_steps(schemas, flavour).into_iter()
+}
+
+/// Moves every `AddForeignKey` step to the end of `steps`, after every other step, preserving
+/// their relative order among themselves and leaving the relative order of everything else
+/// unchanged. Used when `DifferSettings::defer_foreign_keys` is set.
+fn move_foreign_key_additions_to_the_end(steps: &mut Vec<SqlMigrationStep>) {
+    let (mut rest, foreign_keys): (Vec<_>, Vec<_>) = std::mem::take(steps)
+        .into_iter()
+        .partition(|step| !matches!(step, SqlMigrationStep::AddForeignKey { .. }));
+
+    rest.extend(foreign_keys);
+
+    *steps = rest;
+}
+
+/// Removes exact duplicate `CreateIndex`/`DropIndex` steps targeting the same index, which can
+/// happen when a column change and an independent index change both decide that the same index
+/// needs to be recreated (see `push_index_changes_for_column_changes` and
+/// `push_created_index_steps`/`push_dropped_index_steps`). A `DropIndex` and a `CreateIndex` for
+/// the same index id are kept side by side, since together they are an intentional recreate, not
+/// a duplicate — only repeats of the *same* step variant for the *same* index are redundant.
+fn dedupe_redundant_index_steps(steps: &mut Vec<SqlMigrationStep>) {
+    let mut seen_creates: HashSet<IndexId> = HashSet::new();
+    let mut seen_drops: HashSet<IndexId> = HashSet::new();
+
+    steps.retain(|step| match step {
+        SqlMigrationStep::CreateIndex { index_id, .. } => seen_creates.insert(*index_id),
+        SqlMigrationStep::DropIndex { index_id } => seen_drops.insert(*index_id),
+        _ => true,
+    });
+}
+
+/// Returns true iff `calculate_steps` would produce no steps at all for this pair of schemas, i.e.
+/// the two schemas are identical as far as the diffing rules are concerned. Purely cosmetic
+/// differences that the flavour doesn't consider material — comments on a flavour that doesn't
+/// diff them, column ordering that doesn't affect anything observable, and so on — don't count,
+/// since they never produce a step in the first place.
+pub(crate) fn schemas_equivalent(schemas: MigrationPair<&SqlDatabaseSchema>, flavour: &dyn SqlFlavour) -> bool {
+    calculate_steps(schemas, flavour).is_empty()
+}
+
+/// Diff an empty schema against `schema`, as when generating the very first migration of a
+/// project. This is a thin wrapper around [`calculate_steps`], which already guarantees —
+/// because of the fixed ordering of [`SqlMigrationStep`]'s variants, `CreateTable` sorting before
+/// `AddForeignKey` — that every table is created before any foreign key referencing it is added,
+/// even for tables that reference each other. The assertion below only double-checks that
+/// invariant instead of re-implementing it.
+pub(crate) fn calculate_initial_steps(schema: &SqlDatabaseSchema, flavour: &dyn SqlFlavour) -> Vec<SqlMigrationStep> {
+    let empty = SqlDatabaseSchema::from(flavour.empty_database_schema());
+    let steps = calculate_steps(MigrationPair::new(&empty, schema), flavour);
+
+    debug_assert!(
+        {
+            let mut created_tables: HashSet<TableId> = HashSet::new();
+
+            steps.iter().all(|step| match step {
+                SqlMigrationStep::CreateTable { table_id } => {
+                    created_tables.insert(*table_id);
+                    true
+                }
+                SqlMigrationStep::AddForeignKey { foreign_key_id, .. } => {
+                    let fk = schema.describer_schema.walk(*foreign_key_id);
+                    created_tables.contains(&fk.table().id) && created_tables.contains(&fk.referenced_table().id)
+                }
+                _ => true,
+            })
+        },
+        "an initial migration must create every table before adding a foreign key that references it"
+    );
+
+    steps
+}
+
+/// Diff only the foreign keys between `schemas`, ignoring every other kind of change (columns,
+/// indexes, tables, enums, ...). Meant for repairing foreign key drift on its own — for example
+/// re-adding the constraints a bulk load dropped to speed up inserts — without generating the
+/// column/index churn a full [`calculate_steps`] diff of the same two schemas would include.
+///
+/// Tables that need a full recreate (see [`DifferDatabase::non_redefined_table_pairs`]) are
+/// skipped entirely: their foreign keys are handled as part of that recreate, not as standalone
+/// steps. This also means a foreign key whose *referenced* column changed type, without the
+/// constrained column itself changing, is reported as unchanged here — [`foreign_keys_match`]
+/// only checks the constrained columns for type changes, so such a change needs the column type change from
+/// [`calculate_steps`] applied alongside it, not just the foreign key steps this function emits.
+pub(crate) fn calculate_fk_steps(
+    schemas: MigrationPair<&SqlDatabaseSchema>,
+    flavour: &dyn SqlFlavour,
+) -> Vec<SqlMigrationStep> {
+    let db = DifferDatabase::new(schemas, flavour);
+    let mut steps: Vec<SqlMigrationStep> = Vec::new();
+
+    if db.flavour.should_push_foreign_keys_from_created_tables() {
+        for table in db.created_tables() {
+            for fk in table.foreign_keys() {
+                steps.push(SqlMigrationStep::AddForeignKey {
+                    foreign_key_id: fk.id,
+                    deferred: false,
+                });
+            }
+        }
+    }
+
+    if db.flavour.should_drop_foreign_keys_from_dropped_tables() {
+        for table in db.dropped_tables() {
+            for fk in table.foreign_keys() {
+                steps.push(SqlMigrationStep::DropForeignKey { foreign_key_id: fk.id });
+            }
+        }
+    }
+
+    for table in db.non_redefined_table_pairs() {
+        for created_fk in table.created_foreign_keys() {
+            steps.push(SqlMigrationStep::AddForeignKey {
+                foreign_key_id: created_fk.id,
+                deferred: false,
+            });
+        }
+
+        for dropped_fk in table.dropped_foreign_keys() {
+            steps.push(SqlMigrationStep::DropForeignKey {
+                foreign_key_id: dropped_fk.id,
+            });
+        }
+    }
+
     steps.sort();
 
     steps
@@ -51,13 +234,22 @@ fn push_created_schema_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDatab
     }
 }
 
+fn push_dropped_schema_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+    for schema in db.dropped_namespaces() {
+        steps.push(SqlMigrationStep::DropSchema(schema.id))
+    }
+}
+
 fn push_created_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
     for table in db.created_tables() {
         steps.push(SqlMigrationStep::CreateTable { table_id: table.id });
 
         if db.flavour.should_push_foreign_keys_from_created_tables() {
             for fk in table.foreign_keys() {
-                steps.push(SqlMigrationStep::AddForeignKey { foreign_key_id: fk.id });
+                steps.push(SqlMigrationStep::AddForeignKey {
+                    foreign_key_id: fk.id,
+                    deferred: db.settings.defer_foreign_keys,
+                });
             }
         }
 
@@ -69,6 +261,7 @@ fn push_created_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
                     table_id: (None, index.table().id),
                     index_id: index.id,
                     from_drop_and_recreate: false,
+                    concurrently: false,
                 });
 
             steps.extend(create_indexes_from_created_tables);
@@ -96,16 +289,28 @@ fn push_dropped_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
 
 fn push_altered_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
     for table in db.non_redefined_table_pairs() {
-        for created_fk in table.created_foreign_keys() {
-            steps.push(SqlMigrationStep::AddForeignKey {
-                foreign_key_id: created_fk.id,
-            })
-        }
+        // Coalescing inlines the created/dropped foreign keys into this table's own `AlterTable`
+        // step below, instead of emitting them as separate steps here. `move_foreign_key_additions_to_the_end`
+        // only knows how to move standalone `AddForeignKey` steps, so we don't coalesce when
+        // `defer_foreign_keys` is also set — otherwise the coalesced foreign key would be added
+        // inline with the rest of the `AlterTable`, defeating the deferral.
+        let coalesce_foreign_keys = db.settings.coalesce_foreign_keys_into_alter_table
+            && db.flavour.supports_multiple_alter_table_clauses()
+            && !db.settings.defer_foreign_keys;
+
+        if !coalesce_foreign_keys {
+            for created_fk in table.created_foreign_keys() {
+                steps.push(SqlMigrationStep::AddForeignKey {
+                    foreign_key_id: created_fk.id,
+                    deferred: db.settings.defer_foreign_keys,
+                })
+            }
 
-        for dropped_fk in table.dropped_foreign_keys() {
-            steps.push(SqlMigrationStep::DropForeignKey {
-                foreign_key_id: dropped_fk.id,
-            })
+            for dropped_fk in table.dropped_foreign_keys() {
+                steps.push(SqlMigrationStep::DropForeignKey {
+                    foreign_key_id: dropped_fk.id,
+                })
+            }
         }
 
         for fk in table.foreign_key_pairs() {
@@ -117,7 +322,7 @@ fn push_altered_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
         // Indexes.
         for i in table
             .index_pairs()
-            .filter(|pair| db.flavour.index_should_be_renamed(*pair))
+            .filter(|pair| !db.settings.ignore_index_renames && db.flavour.index_should_be_renamed(*pair))
         {
             let index: MigrationPair<IndexId> = i.map(|i| i.id);
 
@@ -130,6 +335,15 @@ fn push_altered_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
             steps.push(step);
         }
 
+        for i in table.index_pairs().filter(|pair| {
+            (db.flavour.supports_object_comments() && pair.previous.description() != pair.next.description())
+                || (db.flavour.compares_index_tablespaces() && pair.previous.tablespace() != pair.next.tablespace())
+        }) {
+            steps.push(SqlMigrationStep::AlterIndex {
+                index: i.map(|i| i.id),
+            });
+        }
+
         // Order matters.
         let mut changes = Vec::new();
         if let Some(change) = dropped_primary_key(&table) {
@@ -140,6 +354,14 @@ fn push_altered_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
             changes.push(change);
         }
 
+        if coalesce_foreign_keys {
+            for dropped_fk in table.dropped_foreign_keys() {
+                changes.push(TableChange::DropForeignKey {
+                    foreign_key_id: dropped_fk.id,
+                });
+            }
+        }
+
         dropped_columns(&table, &mut changes);
         added_columns(&table, &mut changes);
 
@@ -151,6 +373,21 @@ fn push_altered_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
             changes.push(change)
         }
 
+        db.flavour.push_exclusion_constraint_changes(&table, &mut changes);
+        db.flavour.push_enum_check_constraint_changes(&table, &mut changes);
+        db.flavour.push_table_persistence_changes(&table, &mut changes);
+        db.flavour.push_table_collation_changes(&table, &mut changes);
+        db.flavour.push_table_tablespace_changes(&table, &mut changes);
+
+        if coalesce_foreign_keys {
+            for created_fk in table.created_foreign_keys() {
+                changes.push(TableChange::AddForeignKey {
+                    foreign_key_id: created_fk.id,
+                    deferred: db.settings.defer_foreign_keys,
+                });
+            }
+        }
+
         if changes.is_empty() {
             continue;
         }
@@ -179,10 +416,18 @@ fn added_columns(differ: &TableDiffer<'_, '_>, changes: &mut Vec<TableChange>) {
         changes.push(TableChange::AddColumn {
             column_id: column.id,
             has_virtual_default: next_column_has_virtual_default(column.id, differ.db),
+            preceding_column: preceding_column(differ.next(), column.id),
         })
     }
 }
 
+/// The column that immediately precedes `column_id` in `table`, if any. Used to let connectors
+/// that support it (currently only MySQL, through `ADD COLUMN ... AFTER`/`FIRST`) place added
+/// columns at their intended position instead of always appending them at the end of the table.
+pub(crate) fn preceding_column(table: TableWalker<'_>, column_id: TableColumnId) -> Option<TableColumnId> {
+    table.columns().take_while(|c| c.id != column_id).last().map(|c| c.id)
+}
+
 fn alter_columns(table_differ: &TableDiffer<'_, '_>) -> Vec<TableChange> {
     let mut alter_columns: Vec<_> = table_differ
         .column_pairs()
@@ -195,22 +440,41 @@ fn alter_columns(table_differ: &TableDiffer<'_, '_>) -> Vec<TableChange> {
 
             let column_id = MigrationPair::new(column_differ.previous.id, column_differ.next.id);
 
+            let forced_recreate = table_differ
+                .db
+                .settings
+                .force_recreate_columns
+                .contains(&column_differ.previous.id);
+
+            let type_override = table_differ
+                .db
+                .settings
+                .type_overrides
+                .get(&column_differ.next.id)
+                .cloned();
+
             match changes.type_change {
                 Some(ColumnTypeChange::NotCastable) => Some(TableChange::DropAndRecreateColumn { column_id, changes }),
+                Some(ColumnTypeChange::RiskyCast) | Some(ColumnTypeChange::SafeCast) if forced_recreate => {
+                    Some(TableChange::DropAndRecreateColumn { column_id, changes })
+                }
                 Some(ColumnTypeChange::RiskyCast) => Some(TableChange::AlterColumn(AlterColumn {
                     column_id,
                     changes,
                     type_change: Some(crate::sql_migration::ColumnTypeChange::RiskyCast),
+                    type_override,
                 })),
                 Some(ColumnTypeChange::SafeCast) => Some(TableChange::AlterColumn(AlterColumn {
                     column_id,
                     changes,
                     type_change: Some(crate::sql_migration::ColumnTypeChange::SafeCast),
+                    type_override,
                 })),
                 None => Some(TableChange::AlterColumn(AlterColumn {
                     column_id,
                     changes,
                     type_change: None,
+                    type_override,
                 })),
             }
         })
@@ -323,12 +587,15 @@ fn push_alter_primary_key(differ: &TableDiffer<'_, '_>, steps: &mut Vec<SqlMigra
 }
 
 fn push_created_index_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+    let concurrently = db.settings.concurrent_index_creation && db.flavour.supports_concurrent_index_creation();
+
     for tables in db.non_redefined_table_pairs() {
         for index in tables.created_indexes() {
             steps.push(SqlMigrationStep::CreateIndex {
                 table_id: (Some(tables.previous().id), tables.next().id),
                 index_id: index.id,
                 from_drop_and_recreate: false,
+                concurrently,
             })
         }
 
@@ -354,6 +621,7 @@ fn push_created_index_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferDataba
                     table_id: (Some(tables.previous().id), tables.next().id),
                     index_id: index.next.id,
                     from_drop_and_recreate: true,
+                    concurrently: false,
                 })
             }
         }
@@ -426,6 +694,7 @@ fn push_redefined_table_steps(steps: &mut Vec<SqlMigrationStep>, db: &DifferData
                     .collect(),
                 dropped_columns: differ.dropped_columns().map(|col| col.id).collect(),
                 column_pairs,
+                checks_changed: differ.checks_changed(),
             }
         })
         .collect();
@@ -442,7 +711,10 @@ fn foreign_keys_match(fks: MigrationPair<&ForeignKeyWalker<'_>>, db: &DifferData
     let constrains_same_column_count = fks.previous.constrained_columns().len() == fks.next.constrained_columns().len();
 
     let constrains_same_columns = fks.interleave(|fk| fk.constrained_columns()).all(|cols| {
-        let type_changed = || db.column_changes_for_walkers(cols).type_changed();
+        let type_changed = || {
+            db.column_changes_for_walkers(cols).type_changed()
+                && !(db.flavour.fk_type_leniency() && is_uuid_string_leniency_change(cols))
+        };
 
         let arities_ok = db.flavour.can_cope_with_foreign_key_column_becoming_non_nullable()
             || (cols.previous.arity() == cols.next.arity()
@@ -459,6 +731,9 @@ fn foreign_keys_match(fks: MigrationPair<&ForeignKeyWalker<'_>>, db: &DifferData
     let same_on_delete_action = fks.previous.on_delete_action() == fks.next.on_delete_action();
     let same_on_update_action = fks.previous.on_update_action() == fks.next.on_update_action();
 
+    let same_match_type =
+        !db.flavour.compares_foreign_key_match_types() || fks.previous.match_type() == fks.next.match_type();
+
     references_same_table
         && references_same_column_count
         && constrains_same_column_count
@@ -466,6 +741,19 @@ fn foreign_keys_match(fks: MigrationPair<&ForeignKeyWalker<'_>>, db: &DifferData
         && references_same_columns
         && same_on_delete_action
         && same_on_update_action
+        && same_match_type
+}
+
+/// Is this column's type change purely a switch between the `Uuid` and `String` type families,
+/// in either direction? This is the specific leniency `fk_type_leniency` gates: other type
+/// changes on a constrained column always force the foreign key to be recreated.
+fn is_uuid_string_leniency_change(cols: MigrationPair<sql_schema_describer::walkers::TableColumnWalker<'_>>) -> bool {
+    use sql_schema_describer::ColumnTypeFamily;
+
+    matches!(
+        (cols.previous.column_type_family(), cols.next.column_type_family()),
+        (ColumnTypeFamily::Uuid, ColumnTypeFamily::String) | (ColumnTypeFamily::String, ColumnTypeFamily::Uuid)
+    )
 }
 
 fn push_foreign_key_pair_changes(
@@ -485,6 +773,7 @@ fn push_foreign_key_pair_changes(
         });
         steps.push(SqlMigrationStep::AddForeignKey {
             foreign_key_id: fk.next.id,
+            deferred: db.settings.defer_foreign_keys,
         });
         return;
     }
@@ -493,6 +782,12 @@ fn push_foreign_key_pair_changes(
         return;
     }
 
+    if db.flavour.supports_object_comments() && fk.previous.description() != fk.next.description() {
+        steps.push(SqlMigrationStep::AlterForeignKey {
+            foreign_key_id: fk.map(|fk| fk.id),
+        });
+    }
+
     if fk
         .map(|fk| fk.constraint_name())
         .transpose()
@@ -516,6 +811,7 @@ fn push_foreign_key_pair_changes(
         } else {
             steps.push(SqlMigrationStep::AddForeignKey {
                 foreign_key_id: fk.next.id,
+                deferred: db.settings.defer_foreign_keys,
             });
             steps.push(SqlMigrationStep::DropForeignKey {
                 foreign_key_id: fk.previous.id,
@@ -541,3 +837,1633 @@ fn is_prisma_implicit_m2m_fk(fk: ForeignKeyWalker<'_>) -> bool {
 fn all_match<T: PartialEq>(a: &mut dyn ExactSizeIterator<Item = T>, b: &mut dyn ExactSizeIterator<Item = T>) -> bool {
     a.len() == b.len() && a.zip(b).all(|(a, b)| a == b)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flavour::PostgresFlavour;
+    use psl::SourceFile;
+
+    fn sql_schema(datamodel: &str) -> SqlDatabaseSchema {
+        sql_schema_with_flavour(datamodel, &PostgresFlavour::default())
+    }
+
+    fn sql_schema_with_flavour(datamodel: &str, flavour: &dyn SqlFlavour) -> SqlDatabaseSchema {
+        let sources = [("schema.prisma".to_owned(), SourceFile::from(datamodel))];
+        let validated_schema = psl::parse_schema_multi(&sources).unwrap();
+
+        crate::sql_schema_calculator::calculate_sql_schema(&validated_schema, flavour)
+    }
+
+    #[test]
+    fn initial_migration_creates_mutually_referencing_tables_before_their_foreign_keys() {
+        let schema = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id       Int     @id
+                  eggId    Int?    @unique
+                  egg      Egg?    @relation("ChickenToEgg", fields: [eggId], references: [id])
+                  laidEggs Egg[]   @relation("LaidBy")
+                }
+
+                model Egg {
+                  id        Int      @id
+                  laidById  Int
+                  laidBy    Chicken  @relation("LaidBy", fields: [laidById], references: [id])
+                  chicken   Chicken? @relation("ChickenToEgg")
+                }
+            "#,
+        );
+
+        let steps = calculate_initial_steps(&schema, &PostgresFlavour::default());
+
+        let mut created_tables = HashSet::new();
+
+        for step in &steps {
+            match step {
+                SqlMigrationStep::CreateTable { table_id } => {
+                    created_tables.insert(*table_id);
+                }
+                SqlMigrationStep::AddForeignKey { foreign_key_id, .. } => {
+                    let fk = schema.describer_schema.walk(*foreign_key_id);
+                    assert!(
+                        created_tables.contains(&fk.table().id),
+                        "the referencing table must be created before its foreign key is added"
+                    );
+                    assert!(
+                        created_tables.contains(&fk.referenced_table().id),
+                        "the referenced table must be created before the foreign key is added"
+                    );
+                }
+                _ => (),
+            }
+        }
+
+        assert_eq!(created_tables.len(), 2, "both tables should be created");
+    }
+
+    #[test]
+    fn calculate_fk_steps_only_produces_foreign_key_steps() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id    Int   @id
+                  name  String
+                }
+
+                model Egg {
+                  id        Int      @id
+                  laidById  Int
+                  laidBy    Chicken  @relation(fields: [laidById], references: [id])
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id    Int     @id
+                  name  String?
+                }
+
+                model Coop {
+                  id Int @id
+                }
+
+                model Egg {
+                  id        Int      @id
+                  laidById  Int
+                  laidBy    Chicken  @relation(fields: [laidById], references: [id])
+                  coopId    Int
+                  coop      Coop     @relation(fields: [coopId], references: [id])
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let flavour = PostgresFlavour::default();
+
+        let full_diff_fk_steps: Vec<_> = calculate_steps(schemas, &flavour)
+            .into_iter()
+            .filter(|step| matches!(step, SqlMigrationStep::AddForeignKey { .. }))
+            .collect();
+
+        let fk_steps = calculate_fk_steps(schemas, &flavour);
+
+        assert!(!fk_steps.is_empty());
+        assert!(
+            fk_steps
+                .iter()
+                .all(|step| matches!(step, SqlMigrationStep::AddForeignKey { .. })),
+            "calculate_fk_steps must only ever produce AddForeignKey/DropForeignKey steps, got: {fk_steps:?}"
+        );
+        assert_eq!(
+            fk_steps, full_diff_fk_steps,
+            "the foreign key steps on their own should match the foreign key steps from a full diff"
+        );
+    }
+
+    #[test]
+    fn calculate_fk_steps_skips_tables_that_need_a_full_recreate() {
+        let flavour = crate::flavour::SqliteFlavour::default();
+
+        let previous = sql_schema_with_flavour(
+            r#"
+                datasource db {
+                  provider = "sqlite"
+                  url = "file:dev.db"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String
+                }
+            "#,
+            &flavour,
+        );
+
+        let next = sql_schema_with_flavour(
+            r#"
+                datasource db {
+                  provider = "sqlite"
+                  url = "file:dev.db"
+                }
+
+                model Chicken {
+                  id   String @id
+                  name String
+                }
+            "#,
+            &flavour,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        // The id column's type change forces `Chicken` to be redefined rather than altered in
+        // place, so calculate_fk_steps has nothing of its own to contribute here — any foreign
+        // keys on the table are handled as part of that recreate instead.
+        assert!(calculate_fk_steps(schemas, &flavour).is_empty());
+    }
+
+    #[test]
+    fn calculate_steps_streaming_yields_the_same_order_as_calculate_steps() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id       Int   @id
+                  eggId    Int?  @unique
+                  egg      Egg?  @relation("ChickenToEgg", fields: [eggId], references: [id])
+                  laidEggs Egg[] @relation("LaidBy")
+                }
+
+                model Egg {
+                  id        Int      @id
+                  laidById  Int
+                  laidBy    Chicken  @relation("LaidBy", fields: [laidById], references: [id])
+                  chicken   Chicken? @relation("ChickenToEgg")
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let batch = calculate_steps(schemas, &PostgresFlavour::default());
+        let streamed: Vec<_> = calculate_steps_streaming(schemas, &PostgresFlavour::default()).collect();
+
+        assert!(!batch.is_empty());
+        assert_eq!(batch, streamed);
+    }
+
+    #[test]
+    fn defer_foreign_keys_moves_every_add_foreign_key_step_to_the_end() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id       Int   @id
+                  eggId    Int?  @unique
+                  egg      Egg?  @relation("ChickenToEgg", fields: [eggId], references: [id])
+                  laidEggs Egg[] @relation("LaidBy")
+                }
+
+                model Egg {
+                  id        Int      @id
+                  laidById  Int
+                  laidBy    Chicken  @relation("LaidBy", fields: [laidById], references: [id])
+                  chicken   Chicken? @relation("ChickenToEgg")
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                defer_foreign_keys: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(
+            steps.iter().any(|step| matches!(step, SqlMigrationStep::AddForeignKey { .. })),
+            "the diff should contain at least one AddForeignKey step to begin with"
+        );
+
+        let add_foreign_key_positions: Vec<usize> = steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| matches!(step, SqlMigrationStep::AddForeignKey { .. }))
+            .map(|(idx, _)| idx)
+            .collect();
+        let last_non_foreign_key_position = steps
+            .iter()
+            .rposition(|step| !matches!(step, SqlMigrationStep::AddForeignKey { .. }));
+
+        if let Some(last_non_foreign_key_position) = last_non_foreign_key_position {
+            assert!(
+                add_foreign_key_positions.iter().all(|pos| *pos > last_non_foreign_key_position),
+                "every AddForeignKey step must come after every other step"
+            );
+        }
+
+        assert!(
+            steps
+                .iter()
+                .filter_map(|step| match step {
+                    SqlMigrationStep::AddForeignKey { deferred, .. } => Some(*deferred),
+                    _ => None,
+                })
+                .all(|deferred| deferred),
+            "every AddForeignKey step must be marked as deferred"
+        );
+    }
+
+    #[test]
+    fn concurrent_index_creation_marks_created_indexes_and_requires_a_separate_transaction() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String @unique
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                concurrent_index_creation: true,
+                ..Default::default()
+            },
+        );
+
+        let create_index_steps: Vec<&SqlMigrationStep> = steps
+            .iter()
+            .filter(|step| matches!(step, SqlMigrationStep::CreateIndex { .. }))
+            .collect();
+
+        assert!(
+            !create_index_steps.is_empty(),
+            "the diff should contain at least one CreateIndex step to begin with"
+        );
+
+        assert!(
+            create_index_steps
+                .iter()
+                .all(|step| matches!(step, SqlMigrationStep::CreateIndex { concurrently: true, .. })),
+            "every CreateIndex step must be marked as concurrent"
+        );
+
+        assert!(
+            create_index_steps.iter().all(|step| step.requires_separate_transaction()),
+            "a concurrently created index must require a separate transaction"
+        );
+
+        // Without the setting, neither applies.
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(
+            step,
+            SqlMigrationStep::CreateIndex { concurrently: false, .. }
+        )));
+        assert!(!steps.iter().any(|step| step.requires_separate_transaction()));
+    }
+
+    #[test]
+    fn coalesce_foreign_keys_into_alter_table_merges_the_added_foreign_key_into_the_alter_table_step() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                }
+
+                model Egg {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id    Int  @id
+                  eggId Int?
+                  egg   Egg? @relation(fields: [eggId], references: [id])
+                }
+
+                model Egg {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                coalesce_foreign_keys_into_alter_table: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(
+            !steps.iter().any(|step| matches!(step, SqlMigrationStep::AddForeignKey { .. })),
+            "the added foreign key should not be a separate AddForeignKey step"
+        );
+
+        let alter_table_steps: Vec<&AlterTable> = steps
+            .iter()
+            .filter_map(|step| match step {
+                SqlMigrationStep::AlterTable(alter_table) => Some(alter_table),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(alter_table_steps.len(), 1, "there should be a single AlterTable step for Chicken");
+
+        assert!(
+            alter_table_steps[0]
+                .changes
+                .iter()
+                .any(|change| matches!(change, TableChange::AddForeignKey { .. })),
+            "the AlterTable step should contain the coalesced AddForeignKey change"
+        );
+
+        // Without the setting, the foreign key is a separate step, as usual.
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(step, SqlMigrationStep::AddForeignKey { .. })));
+    }
+
+    #[test]
+    fn defer_foreign_keys_takes_precedence_over_coalesce_foreign_keys_into_alter_table() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                }
+
+                model Egg {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id    Int  @id
+                  eggId Int?
+                  egg   Egg? @relation(fields: [eggId], references: [id])
+                }
+
+                model Egg {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                coalesce_foreign_keys_into_alter_table: true,
+                defer_foreign_keys: true,
+                ..Default::default()
+            },
+        );
+
+        // With both settings on, the foreign key must still end up as a standalone,
+        // deferred AddForeignKey step at the very end, rather than silently staying inlined
+        // in the AlterTable step where `move_foreign_key_additions_to_the_end` can't see it.
+        let last_non_foreign_key_position = steps
+            .iter()
+            .rposition(|step| !matches!(step, SqlMigrationStep::AddForeignKey { .. }));
+
+        let add_foreign_key_positions: Vec<usize> = steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| matches!(step, SqlMigrationStep::AddForeignKey { deferred: true, .. }))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        assert_eq!(
+            add_foreign_key_positions.len(),
+            1,
+            "the foreign key should be a single, standalone, deferred AddForeignKey step: {steps:#?}"
+        );
+
+        if let Some(last_non_foreign_key_position) = last_non_foreign_key_position {
+            assert!(
+                add_foreign_key_positions.iter().all(|pos| *pos > last_non_foreign_key_position),
+                "the AddForeignKey step must come after every other step"
+            );
+        }
+
+        assert!(
+            !steps.iter().filter_map(|step| match step {
+                SqlMigrationStep::AlterTable(alter_table) => Some(alter_table),
+                _ => None,
+            })
+            .any(|alter_table| alter_table
+                .changes
+                .iter()
+                .any(|change| matches!(change, TableChange::AddForeignKey { .. }))),
+            "the foreign key must not also be inlined into an AlterTable step"
+        );
+    }
+
+    #[test]
+    fn type_override_is_rendered_instead_of_the_inferred_type() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id   Int    @id
+                  name String @db.Text
+                }
+            "#,
+        );
+
+        let column_id = next
+            .describer_schema
+            .table_walkers()
+            .find(|t| t.name() == "Chicken")
+            .unwrap()
+            .column("name")
+            .unwrap()
+            .id;
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                type_overrides: [(column_id, "CITEXT".to_owned())].into_iter().collect(),
+                ..Default::default()
+            },
+        );
+
+        let alter_column = steps
+            .iter()
+            .find_map(|step| match step {
+                SqlMigrationStep::AlterTable(alter_table) => {
+                    alter_table.changes.iter().find_map(|change| match change {
+                        TableChange::AlterColumn(alter_column) => Some(alter_column),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            })
+            .expect("there should be an AlterColumn step for the type change");
+
+        assert_eq!(alter_column.type_override.as_deref(), Some("CITEXT"));
+        assert_eq!(alter_column.type_change, Some(ColumnTypeChange::RiskyCast));
+    }
+
+    #[test]
+    fn add_foreign_key_steps_are_not_deferred_by_default() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                }
+
+                model Egg {
+                  id        Int     @id
+                  chickenId Int
+                  chicken   Chicken @relation(fields: [chickenId], references: [id])
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(
+            step,
+            SqlMigrationStep::AddForeignKey { deferred: false, .. }
+        )));
+    }
+
+    #[test]
+    fn ignore_index_renames_suppresses_a_rename_only_step() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id    Int    @id
+                  email String @unique(map: "chicken_email_unique")
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id    Int    @id
+                  email String @unique(map: "chicken_email_key")
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step, SqlMigrationStep::RenameIndex { .. })));
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                ignore_index_renames: true,
+                ..Default::default()
+            },
+        );
+        assert!(!steps
+            .iter()
+            .any(|step| matches!(step, SqlMigrationStep::RenameIndex { .. } | SqlMigrationStep::RedefineIndex { .. })));
+    }
+
+    // `ignore_index_renames` is a blunt instrument: it can't tell a cosmetic rename apart from
+    // one that is actually needed because two indexes swapped names, so it suppresses both.
+    #[test]
+    fn ignore_index_renames_also_suppresses_a_genuine_name_swap() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                  a  Int
+                  b  Int
+
+                  @@unique([a], map: "idx_a")
+                  @@unique([b], map: "idx_b")
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Chicken {
+                  id Int @id
+                  a  Int
+                  b  Int
+
+                  @@unique([a], map: "idx_b")
+                  @@unique([b], map: "idx_a")
+                }
+            "#,
+        );
+
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+        let rename_count = steps
+            .iter()
+            .filter(|step| matches!(step, SqlMigrationStep::RenameIndex { .. }))
+            .count();
+        assert_eq!(rename_count, 2, "both indexes should be renamed to swap names");
+
+        let steps = calculate_steps_with_settings(
+            schemas,
+            &PostgresFlavour::default(),
+            DifferSettings {
+                ignore_index_renames: true,
+                ..Default::default()
+            },
+        );
+        assert!(!steps
+            .iter()
+            .any(|step| matches!(step, SqlMigrationStep::RenameIndex { .. } | SqlMigrationStep::RedefineIndex { .. })));
+    }
+
+    #[test]
+    fn setting_an_index_comment_produces_an_alter_index_step() {
+        use sql_schema_describer::SqlSchema;
+
+        let mut previous = SqlSchema::default();
+        let previous_table_id = previous.push_table("a".to_owned(), Default::default(), None);
+        let previous_index_id = previous.push_index(previous_table_id, "a_idx".to_owned());
+
+        let mut next = SqlSchema::default();
+        let next_table_id = next.push_table("a".to_owned(), Default::default(), None);
+        let next_index_id = next.push_index(next_table_id, "a_idx".to_owned());
+        next.set_index_description(next_index_id, "the index comment".to_owned());
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![SqlMigrationStep::AlterIndex {
+                index: MigrationPair::new(previous_index_id, next_index_id),
+            }]
+        );
+
+        // Clearing it back out produces the opposite change.
+        let schemas = MigrationPair::new(&next, &previous);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![SqlMigrationStep::AlterIndex {
+                index: MigrationPair::new(next_index_id, previous_index_id),
+            }]
+        );
+    }
+
+    #[test]
+    fn setting_a_foreign_key_comment_produces_an_alter_foreign_key_step() {
+        use sql_schema_describer::{ForeignKeyAction, SqlSchema};
+
+        fn schema_with_fk() -> (SqlSchema, sql_schema_describer::ForeignKeyId) {
+            let mut schema = SqlSchema::default();
+            let a = schema.push_table("a".to_owned(), Default::default(), None);
+            let b = schema.push_table("b".to_owned(), Default::default(), None);
+            let fk_id = schema.push_foreign_key(
+                Some("a_b_fkey".to_owned()),
+                [a, b],
+                [ForeignKeyAction::NoAction, ForeignKeyAction::NoAction],
+            );
+            (schema, fk_id)
+        }
+
+        let (previous, previous_fk_id) = schema_with_fk();
+        let (mut next, next_fk_id) = schema_with_fk();
+        next.set_foreign_key_description(next_fk_id, "the constraint comment".to_owned());
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![SqlMigrationStep::AlterForeignKey {
+                foreign_key_id: MigrationPair::new(previous_fk_id, next_fk_id),
+            }]
+        );
+    }
+
+    #[test]
+    fn changing_a_foreign_keys_match_type_drops_and_recreates_it() {
+        use sql_schema_describer::{ForeignKeyAction, ForeignKeyMatchType, SqlSchema};
+
+        fn schema_with_fk(match_type: ForeignKeyMatchType) -> (SqlSchema, sql_schema_describer::ForeignKeyId) {
+            let mut schema = SqlSchema::default();
+            let a = schema.push_table("a".to_owned(), Default::default(), None);
+            let b = schema.push_table("b".to_owned(), Default::default(), None);
+            let fk_id = schema.push_foreign_key(
+                Some("a_b_fkey".to_owned()),
+                [a, b],
+                [ForeignKeyAction::NoAction, ForeignKeyAction::NoAction],
+            );
+            schema.set_foreign_key_match_type(fk_id, match_type);
+            (schema, fk_id)
+        }
+
+        let (previous, previous_fk_id) = schema_with_fk(ForeignKeyMatchType::Simple);
+        let (next, next_fk_id) = schema_with_fk(ForeignKeyMatchType::Full);
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![
+                SqlMigrationStep::DropForeignKey {
+                    foreign_key_id: previous_fk_id
+                },
+                SqlMigrationStep::AddForeignKey {
+                    foreign_key_id: next_fk_id,
+                    deferred: false,
+                },
+            ]
+        );
+
+        // MySQL doesn't introspect the match type, so it always stays at the default and never
+        // forces a drop/recreate.
+        use crate::flavour::MysqlFlavour;
+
+        let steps = calculate_steps(schemas, &MysqlFlavour::default());
+
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn toggling_unlogged_produces_an_alter_table_persistence_change() {
+        use enumflags2::BitFlags;
+        use sql_schema_describer::{postgres::PostgresSchemaExt, SqlSchema, TableProperties};
+
+        let mut previous = SqlSchema::default();
+        previous.push_table("a".to_owned(), Default::default(), None);
+        previous.set_connector_data(Box::<PostgresSchemaExt>::default());
+
+        let mut next = SqlSchema::default();
+        next.push_table_with_properties(
+            "a".to_owned(),
+            Default::default(),
+            BitFlags::from_flag(TableProperties::Unlogged),
+            None,
+        );
+        next.set_connector_data(Box::<PostgresSchemaExt>::default());
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![SqlMigrationStep::AlterTable(AlterTable {
+                table_ids: MigrationPair::new(
+                    previous.describer_schema.table_walkers().next().unwrap().id,
+                    next.describer_schema.table_walkers().next().unwrap().id,
+                ),
+                changes: vec![TableChange::AlterTablePersistence { unlogged: true }],
+            })]
+        );
+
+        // Going back to logged produces the opposite change.
+        let schemas = MigrationPair::new(&next, &previous);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(
+            step,
+            SqlMigrationStep::AlterTable(AlterTable { changes, .. })
+                if changes == &vec![TableChange::AlterTablePersistence { unlogged: false }]
+        )));
+    }
+
+    #[test]
+    fn changing_table_default_collation_produces_an_alter_table_collation_change() {
+        use crate::flavour::MysqlFlavour;
+        use sql_schema_describer::SqlSchema;
+
+        let mut previous = SqlSchema::default();
+        previous.push_table("a".to_owned(), Default::default(), None);
+
+        let mut next = SqlSchema::default();
+        let next_table_id = next.push_table("a".to_owned(), Default::default(), None);
+        next.set_table_default_collation(next_table_id, "utf8mb4_unicode_ci".to_owned());
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &MysqlFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![SqlMigrationStep::AlterTable(AlterTable {
+                table_ids: MigrationPair::new(
+                    previous.describer_schema.table_walkers().next().unwrap().id,
+                    next.describer_schema.table_walkers().next().unwrap().id,
+                ),
+                changes: vec![TableChange::AlterTableCollation {
+                    collation: "utf8mb4_unicode_ci".to_owned()
+                }],
+            })]
+        );
+
+        // An implicit default on the other side (introspected as `None`) isn't itself a change.
+        let mut implicit = SqlSchema::default();
+        implicit.push_table("a".to_owned(), Default::default(), None);
+        let implicit = SqlDatabaseSchema::from(implicit);
+
+        let schemas = MigrationPair::new(&next, &implicit);
+        let steps = calculate_steps(schemas, &MysqlFlavour::default());
+
+        assert!(!steps.iter().any(|step| matches!(
+            step,
+            SqlMigrationStep::AlterTable(AlterTable { changes, .. }) if changes.iter().any(|c| matches!(c, TableChange::AlterTableCollation { .. }))
+        )));
+    }
+
+    #[test]
+    fn changing_table_tablespace_produces_an_alter_table_tablespace_change() {
+        use sql_schema_describer::{postgres::PostgresSchemaExt, SqlSchema};
+
+        let mut previous = SqlSchema::default();
+        previous.push_table("a".to_owned(), Default::default(), None);
+        previous.set_connector_data(Box::<PostgresSchemaExt>::default());
+
+        let mut next = SqlSchema::default();
+        let next_table_id = next.push_table("a".to_owned(), Default::default(), None);
+        next.set_table_tablespace(next_table_id, "fast_ssd".to_owned());
+        next.set_connector_data(Box::<PostgresSchemaExt>::default());
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert_eq!(
+            steps,
+            vec![SqlMigrationStep::AlterTable(AlterTable {
+                table_ids: MigrationPair::new(
+                    previous.describer_schema.table_walkers().next().unwrap().id,
+                    next.describer_schema.table_walkers().next().unwrap().id,
+                ),
+                changes: vec![TableChange::AlterTableTablespace {
+                    tablespace: "fast_ssd".to_owned()
+                }],
+            })]
+        );
+
+        // Moving back to the database's default tablespace (introspected as `None`) isn't
+        // itself a change, since there's no distinguishable name to render `SET TABLESPACE` with.
+        let mut implicit = SqlSchema::default();
+        implicit.push_table("a".to_owned(), Default::default(), None);
+        implicit.set_connector_data(Box::<PostgresSchemaExt>::default());
+        let implicit = SqlDatabaseSchema::from(implicit);
+
+        let schemas = MigrationPair::new(&next, &implicit);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(!steps.iter().any(|step| matches!(
+            step,
+            SqlMigrationStep::AlterTable(AlterTable { changes, .. }) if changes.iter().any(|c| matches!(c, TableChange::AlterTableTablespace { .. }))
+        )));
+    }
+
+    #[test]
+    fn changing_sequence_start_and_increment_produces_an_alter_sequence() {
+        use sql_migration::SequenceChange;
+        use sql_schema_describer::{
+            postgres::{PostgresSchemaExt, Sequence},
+            Column, ColumnArity, ColumnType, ColumnTypeFamily, DefaultValue, SqlSchema,
+        };
+
+        fn schema_with_sequence(sequence: Sequence) -> SqlSchema {
+            let mut schema = SqlSchema::default();
+            let table_id = schema.push_table("Test".to_owned(), Default::default(), None);
+            let column_id = schema.push_table_column(
+                table_id,
+                Column {
+                    name: "id".to_owned(),
+                    tpe: ColumnType::pure(ColumnTypeFamily::Int, ColumnArity::Required),
+                    auto_increment: true,
+                    description: None,
+                    generated_as: None,
+                    toast_storage: None,
+                    not_null_constraint_name: None,
+                    on_update_now: false,
+                },
+            );
+            schema.push_table_default_value(column_id, DefaultValue::sequence("Test_id_seq"));
+
+            let mut ext = PostgresSchemaExt::default();
+            ext.sequences.push(sequence);
+            schema.set_connector_data(Box::new(ext));
+
+            schema
+        }
+
+        let previous = schema_with_sequence(Sequence {
+            namespace_id: Default::default(),
+            name: "Test_id_seq".to_owned(),
+            start_value: 12,
+            min_value: 10,
+            max_value: 39,
+            increment_by: 3,
+            cycle: false,
+            cache_size: 4,
+            r#virtual: false,
+        });
+
+        let next = schema_with_sequence(Sequence {
+            namespace_id: Default::default(),
+            name: "Test_id_seq".to_owned(),
+            start_value: 9,
+            min_value: 8,
+            max_value: 9009,
+            increment_by: 33,
+            cycle: false,
+            cache_size: 12,
+            r#virtual: false,
+        });
+
+        let previous = SqlDatabaseSchema::from(previous);
+        let next = SqlDatabaseSchema::from(next);
+        let schemas = MigrationPair::new(&previous, &next);
+
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        let changes = steps.iter().find_map(|step| match step {
+            SqlMigrationStep::AlterSequence(_, changes) => Some(changes),
+            _ => None,
+        });
+
+        let changes = changes.expect("expected an AlterSequence step");
+
+        for change in [
+            SequenceChange::MinValue,
+            SequenceChange::MaxValue,
+            SequenceChange::Start,
+            SequenceChange::Cache,
+            SequenceChange::Increment,
+        ] {
+            assert!(changes.0.contains(change), "expected {change:?} to have changed");
+        }
+    }
+
+    #[test]
+    fn creating_a_domain_produces_a_create_domain_step() {
+        use sql_schema_describer::postgres::{Domain, PostgresSchemaExt};
+
+        fn schema_with_domains(domains: Vec<Domain>) -> SqlSchema {
+            let mut schema = SqlSchema::default();
+            let mut ext = PostgresSchemaExt::default();
+
+            for domain in domains {
+                ext.push_domain(domain);
+            }
+
+            schema.set_connector_data(Box::new(ext));
+
+            schema
+        }
+
+        let previous = SqlDatabaseSchema::from(schema_with_domains(Vec::new()));
+        let next = SqlDatabaseSchema::from(schema_with_domains(vec![Domain {
+            name: "positive_int".to_owned(),
+            schema: "public".to_owned(),
+            base_type: "integer".to_owned(),
+            not_null: false,
+            default: None,
+            check: Some("VALUE > 0".to_owned()),
+        }]));
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(step, SqlMigrationStep::CreateDomain(_))));
+    }
+
+    #[test]
+    fn altering_a_domains_not_null_produces_an_alter_domain_step() {
+        use sql_schema_describer::postgres::{Domain, PostgresSchemaExt};
+
+        fn schema_with_domain(not_null: bool) -> SqlSchema {
+            let mut schema = SqlSchema::default();
+            let mut ext = PostgresSchemaExt::default();
+
+            ext.push_domain(Domain {
+                name: "positive_int".to_owned(),
+                schema: "public".to_owned(),
+                base_type: "integer".to_owned(),
+                not_null,
+                default: None,
+                check: None,
+            });
+
+            schema.set_connector_data(Box::new(ext));
+
+            schema
+        }
+
+        let previous = SqlDatabaseSchema::from(schema_with_domain(false));
+        let next = SqlDatabaseSchema::from(schema_with_domain(true));
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        let changes = steps.iter().find_map(|step| match step {
+            SqlMigrationStep::AlterDomain(alter_domain) => Some(&alter_domain.changes),
+            _ => None,
+        });
+
+        let changes = changes.expect("expected an AlterDomain step");
+
+        assert!(changes.contains(&sql_migration::DomainChange::AlterNotNull));
+    }
+
+    #[test]
+    fn creating_a_trigger_produces_a_create_trigger_step() {
+        use sql_schema_describer::SqlSchema;
+
+        fn schema_with_triggers(trigger_names: &[&str]) -> SqlSchema {
+            let mut schema = SqlSchema::default();
+            let table_id = schema.push_table("orders".to_owned(), Default::default(), None);
+
+            for name in trigger_names {
+                schema.push_trigger(
+                    table_id,
+                    (*name).to_owned(),
+                    "AFTER".to_owned(),
+                    "INSERT".to_owned(),
+                    "BEGIN UPDATE stock SET quantity = quantity - 1; END".to_owned(),
+                );
+            }
+
+            schema
+        }
+
+        let previous = SqlDatabaseSchema::from(schema_with_triggers(&[]));
+        let next = SqlDatabaseSchema::from(schema_with_triggers(&["decrement_stock"]));
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(step, SqlMigrationStep::CreateTrigger(_))));
+    }
+
+    #[test]
+    fn modifying_a_trigger_produces_a_drop_and_create_trigger_step() {
+        use sql_schema_describer::SqlSchema;
+
+        fn schema_with_trigger(event: &str) -> SqlSchema {
+            let mut schema = SqlSchema::default();
+            let table_id = schema.push_table("orders".to_owned(), Default::default(), None);
+
+            schema.push_trigger(
+                table_id,
+                "decrement_stock".to_owned(),
+                "AFTER".to_owned(),
+                event.to_owned(),
+                "BEGIN UPDATE stock SET quantity = quantity - 1; END".to_owned(),
+            );
+
+            schema
+        }
+
+        let previous = SqlDatabaseSchema::from(schema_with_trigger("INSERT"));
+        let next = SqlDatabaseSchema::from(schema_with_trigger("UPDATE"));
+
+        let schemas = MigrationPair::new(&previous, &next);
+        let steps = calculate_steps(schemas, &PostgresFlavour::default());
+
+        assert!(steps.iter().any(|step| matches!(step, SqlMigrationStep::DropTrigger(_))));
+        assert!(steps.iter().any(|step| matches!(step, SqlMigrationStep::CreateTrigger(_))));
+    }
+
+    #[test]
+    fn schemas_equivalent_is_true_for_identical_schemas() {
+        let dm = r#"
+            datasource db {
+              provider = "postgresql"
+              url = "postgresql://localhost/dev"
+            }
+
+            model Cat {
+              id   Int    @id
+              name String
+            }
+        "#;
+
+        let schema = sql_schema(dm);
+        let schemas = MigrationPair::new(&schema, &schema);
+
+        assert!(schemas_equivalent(schemas, &PostgresFlavour::default()));
+    }
+
+    #[test]
+    fn schemas_equivalent_is_false_when_a_column_was_added() {
+        let before = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Cat {
+                  id Int @id
+                }
+            "#,
+        );
+
+        let after = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Cat {
+                  id   Int    @id
+                  name String
+                }
+            "#,
+        );
+
+        assert!(!schemas_equivalent(
+            MigrationPair::new(&before, &after),
+            &PostgresFlavour::default()
+        ));
+    }
+
+    #[test]
+    fn force_recreate_columns_escalates_a_safe_cast_to_drop_and_recreate() {
+        use crate::flavour::MssqlFlavour;
+
+        let flavour = MssqlFlavour::default();
+
+        let previous = sql_schema_with_flavour(
+            r#"
+                datasource db {
+                  provider = "sqlserver"
+                  url = "sqlserver://localhost/dev"
+                }
+
+                model A {
+                  id Int @id
+                }
+            "#,
+            &flavour,
+        );
+
+        let next = sql_schema_with_flavour(
+            r#"
+                datasource db {
+                  provider = "sqlserver"
+                  url = "sqlserver://localhost/dev"
+                }
+
+                model A {
+                  id BigInt @id
+                }
+            "#,
+            &flavour,
+        );
+
+        let previous_table = previous.describer_schema.table_walkers().next().unwrap();
+        let column_id = previous_table.column("id").unwrap().id;
+
+        let mut settings = DifferSettings::default();
+        settings.force_recreate_columns.insert(column_id);
+
+        let steps = calculate_steps_with_settings(MigrationPair::new(&previous, &next), &flavour, settings);
+
+        let alter_table = steps
+            .iter()
+            .find_map(|step| match step {
+                SqlMigrationStep::AlterTable(alter_table) => Some(alter_table),
+                _ => None,
+            })
+            .expect("expected an AlterTable step");
+
+        assert!(
+            alter_table
+                .changes
+                .iter()
+                .any(|change| matches!(change, TableChange::DropAndRecreateColumn { .. })),
+            "a forced column should be dropped and recreated even though the cast is safe"
+        );
+        assert!(
+            alter_table.changes.iter().any(|change| matches!(change, TableChange::AddPrimaryKey)),
+            "recreating a primary key column on MSSQL must also recreate the primary key"
+        );
+
+        // Without forcing the column, the safe cast should not be escalated, and the primary key
+        // should be left alone.
+        let unforced_steps =
+            calculate_steps_with_settings(MigrationPair::new(&previous, &next), &flavour, DifferSettings::default());
+
+        let unforced_alter_table = unforced_steps.iter().find_map(|step| match step {
+            SqlMigrationStep::AlterTable(alter_table) => Some(alter_table),
+            _ => None,
+        });
+
+        if let Some(alter_table) = unforced_alter_table {
+            assert!(!alter_table
+                .changes
+                .iter()
+                .any(|change| matches!(change, TableChange::DropAndRecreateColumn { .. })));
+        }
+    }
+
+    #[test]
+    fn enum_check_constraint_changes_are_generated_per_column_when_an_enum_is_shared_across_tables() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model A {
+                  id     Int    @id
+                  status String
+                }
+
+                model B {
+                  id     Int    @id
+                  status String
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                enum Status {
+                  ACTIVE
+                  DONE
+                }
+
+                model A {
+                  id     Int    @id
+                  status Status
+                }
+
+                model B {
+                  id     Int    @id
+                  status Status
+                }
+            "#,
+        );
+
+        let db = DifferDatabase::new(MigrationPair::new(&previous, &next), &PostgresFlavour::default());
+
+        let mut changes_by_table = Vec::new();
+
+        for table in db.table_pairs() {
+            let mut changes = Vec::new();
+            enums::push_check_constraint_changes(&table, &mut changes);
+
+            if !changes.is_empty() {
+                changes_by_table.push((table.tables.next.name().to_owned(), changes));
+            }
+        }
+
+        changes_by_table.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(changes_by_table.len(), 2, "both A and B should get their own check constraint");
+
+        for (table_name, changes) in &changes_by_table {
+            assert_eq!(changes.len(), 1);
+
+            match &changes[0] {
+                TableChange::AddCheckConstraint {
+                    constraint_name,
+                    definition,
+                } => {
+                    assert_eq!(constraint_name, &format!("{table_name}_status_check"));
+                    assert!(definition.contains("'ACTIVE'"));
+                    assert!(definition.contains("'DONE'"));
+                }
+                other => panic!("expected AddCheckConstraint, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn enum_check_constraint_changes_are_dropped_when_a_column_stops_using_an_enum() {
+        let previous = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                enum Status {
+                  ACTIVE
+                  DONE
+                }
+
+                model A {
+                  id     Int    @id
+                  status Status
+                }
+            "#,
+        );
+
+        let next = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model A {
+                  id     Int    @id
+                  status String
+                }
+            "#,
+        );
+
+        let db = DifferDatabase::new(MigrationPair::new(&previous, &next), &PostgresFlavour::default());
+
+        let table = db.table_pairs().next().unwrap();
+        let mut changes = Vec::new();
+        enums::push_check_constraint_changes(&table, &mut changes);
+
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(
+            &changes[0],
+            TableChange::DropCheckConstraint { constraint_name } if constraint_name == "A_status_check"
+        ));
+    }
+
+    fn first_index_id(schema: &SqlDatabaseSchema) -> (TableId, IndexId) {
+        let table = schema.describer_schema.table_walkers().next().unwrap();
+        let index = table.indexes().next().unwrap();
+
+        (table.id, index.id)
+    }
+
+    #[test]
+    fn dedupe_redundant_index_steps_removes_duplicate_creates_and_drops() {
+        let schema = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Cat {
+                  id   Int    @id
+                  name String @unique
+                }
+            "#,
+        );
+
+        let (table_id, index_id) = first_index_id(&schema);
+
+        let mut steps = vec![
+            SqlMigrationStep::CreateIndex {
+                table_id: (Some(table_id), table_id),
+                index_id,
+                from_drop_and_recreate: false,
+                concurrently: false,
+            },
+            SqlMigrationStep::CreateIndex {
+                table_id: (Some(table_id), table_id),
+                index_id,
+                from_drop_and_recreate: true,
+                concurrently: false,
+            },
+            SqlMigrationStep::DropIndex { index_id },
+            SqlMigrationStep::DropIndex { index_id },
+        ];
+
+        dedupe_redundant_index_steps(&mut steps);
+
+        let create_count = steps
+            .iter()
+            .filter(|step| matches!(step, SqlMigrationStep::CreateIndex { .. }))
+            .count();
+        let drop_count = steps
+            .iter()
+            .filter(|step| matches!(step, SqlMigrationStep::DropIndex { .. }))
+            .count();
+
+        assert_eq!(create_count, 1, "duplicate CreateIndex steps for the same index must collapse into one");
+        assert_eq!(drop_count, 1, "duplicate DropIndex steps for the same index must collapse into one");
+    }
+
+    #[test]
+    fn dedupe_redundant_index_steps_keeps_an_intentional_drop_and_recreate() {
+        let schema = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Cat {
+                  id   Int    @id
+                  name String @unique
+                }
+            "#,
+        );
+
+        let (table_id, index_id) = first_index_id(&schema);
+
+        let mut steps = vec![
+            SqlMigrationStep::DropIndex { index_id },
+            SqlMigrationStep::CreateIndex {
+                table_id: (Some(table_id), table_id),
+                index_id,
+                from_drop_and_recreate: true,
+                concurrently: false,
+            },
+        ];
+
+        dedupe_redundant_index_steps(&mut steps);
+
+        assert_eq!(steps.len(), 2, "a drop paired with a create for the same index is an intentional recreate, not a duplicate");
+    }
+
+    #[test]
+    fn fk_type_leniency_defaults_to_true() {
+        assert!(PostgresFlavour::default().fk_type_leniency());
+    }
+
+    #[test]
+    fn is_uuid_string_leniency_change_is_true_for_uuid_and_string_either_way() {
+        let schema = sql_schema(
+            r#"
+                datasource db {
+                  provider = "postgresql"
+                  url = "postgresql://localhost/dev"
+                }
+
+                model Cat {
+                  id       Int    @id
+                  uuidCol  String @db.Uuid
+                  textCol  String
+                }
+            "#,
+        );
+
+        let table = schema.describer_schema.table_walkers().next().unwrap();
+        let uuid_col = table.column("uuidCol").unwrap();
+        let text_col = table.column("textCol").unwrap();
+        let id_col = table.column("id").unwrap();
+
+        assert!(is_uuid_string_leniency_change(MigrationPair::new(uuid_col, text_col)));
+        assert!(is_uuid_string_leniency_change(MigrationPair::new(text_col, uuid_col)));
+        assert!(!is_uuid_string_leniency_change(MigrationPair::new(uuid_col, id_col)));
+    }
+}
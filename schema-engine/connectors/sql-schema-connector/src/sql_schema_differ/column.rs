@@ -1,16 +1,52 @@
+use super::sql_schema_differ_flavour::VarcharLengthChange;
 use crate::{flavour::SqlFlavour, migration_pair::MigrationPair};
 use enumflags2::BitFlags;
 
 use sql_schema_describer::{walkers::TableColumnWalker, DefaultKind, PrismaValue};
 
-pub(crate) fn all_changes(cols: MigrationPair<TableColumnWalker<'_>>, flavour: &dyn SqlFlavour) -> ColumnChanges {
+pub(crate) fn all_changes(
+    cols: MigrationPair<TableColumnWalker<'_>>,
+    flavour: &dyn SqlFlavour,
+    type_override: Option<&str>,
+    ignore_varchar_length: bool,
+) -> ColumnChanges {
     let mut changes = BitFlags::empty();
-    let type_change = flavour.column_type_change(cols);
+    let mut type_change = flavour.column_type_change(cols);
 
     if cols.previous.arity() != cols.next.arity() {
         changes |= ColumnChange::Arity
     };
 
+    // A generated column's storage kind (`STORED` vs `VIRTUAL`) cannot be altered in place, so a
+    // change there is treated like an uncastable type change, forcing the column to be dropped
+    // and recreated.
+    if flavour.compares_generated_column_storage()
+        && cols.previous.generated_column_storage() != cols.next.generated_column_storage()
+    {
+        type_change = Some(ColumnTypeChange::NotCastable);
+    }
+
+    // An overridden type is an arbitrary native type string we have no way to verify the
+    // safety of, so a cast the matrix would otherwise consider safe, or no type change at all, is
+    // classified as risky. A `NotCastable` classification is left alone: the override can't make
+    // an impossible cast possible.
+    if type_override.is_some() {
+        type_change = Some(match type_change {
+            None | Some(ColumnTypeChange::SafeCast) => ColumnTypeChange::RiskyCast,
+            Some(ColumnTypeChange::RiskyCast) => ColumnTypeChange::RiskyCast,
+            Some(ColumnTypeChange::NotCastable) => ColumnTypeChange::NotCastable,
+        });
+    }
+
+    // A pure varchar/char length increase is harmless and some teams don't want it to migrate at
+    // all. A length decrease is left alone even here, since it can truncate existing data: it
+    // keeps surfacing as a `RiskyCast` to warn about it rather than being silently ignored.
+    if ignore_varchar_length {
+        if let Some(VarcharLengthChange::Increase) = flavour.varchar_length_change(cols) {
+            type_change = None;
+        }
+    }
+
     if type_change.is_some() {
         changes |= ColumnChange::TypeChanged;
     };
@@ -23,6 +59,32 @@ pub(crate) fn all_changes(cols: MigrationPair<TableColumnWalker<'_>>, flavour: &
         changes |= ColumnChange::Autoincrement;
     }
 
+    // The TOAST storage strategy can be altered in place (`ALTER COLUMN ... SET STORAGE`), so
+    // unlike generated-column storage above this doesn't force a recreate.
+    if flavour.compares_column_storage() && cols.previous.toast_storage() != cols.next.toast_storage() {
+        changes |= ColumnChange::Storage;
+    }
+
+    // A `NOT NULL` constraint's name can be changed in place with `RENAME CONSTRAINT`, so unlike
+    // most other constraint changes this doesn't force a recreate. Only compared when the column
+    // is `NOT NULL` on both sides: an arity change already covers a constraint being added or
+    // dropped entirely, and is handled separately above.
+    if flavour.compares_not_null_constraint_names()
+        && cols.previous.arity().is_required()
+        && cols.next.arity().is_required()
+        && cols.previous.not_null_constraint_name() != cols.next.not_null_constraint_name()
+    {
+        changes |= ColumnChange::NotNullConstraintName;
+    }
+
+    // `ON UPDATE CURRENT_TIMESTAMP` can be toggled in place with `MODIFY COLUMN`, so unlike a
+    // generated-column storage change above this doesn't force a recreate.
+    if flavour.compares_on_update_current_timestamp()
+        && cols.previous.is_on_update_current_timestamp() != cols.next.is_on_update_current_timestamp()
+    {
+        changes |= ColumnChange::OnUpdateNow;
+    }
+
     ColumnChanges { type_change, changes }
 }
 
@@ -89,7 +151,11 @@ fn defaults_match(cols: MigrationPair<TableColumnWalker<'_>>, flavour: &dyn SqlF
         (Some(DefaultKind::Now), None) => false,
         (Some(DefaultKind::Now), Some(DefaultKind::Value(_))) => false,
 
-        (Some(DefaultKind::DbGenerated(_)), Some(DefaultKind::Value(_))) => false,
+        (Some(DefaultKind::DbGenerated(Some(db_generated))), Some(DefaultKind::Value(value)))
+        | (Some(DefaultKind::Value(value)), Some(DefaultKind::DbGenerated(Some(db_generated)))) => {
+            flavour.default_value_matches_db_generated(value, db_generated) && names_match
+        }
+        (Some(DefaultKind::DbGenerated(None)), Some(DefaultKind::Value(_))) => false,
         (Some(DefaultKind::DbGenerated(_)), Some(DefaultKind::Now)) => false,
         (Some(DefaultKind::DbGenerated(_)), None) => false,
         (_, Some(DefaultKind::DbGenerated(None))) => true,
@@ -152,6 +218,9 @@ pub(crate) enum ColumnChange {
     Default,
     TypeChanged,
     Autoincrement,
+    Storage,
+    NotNullConstraintName,
+    OnUpdateNow,
 }
 
 // This should be pub(crate), but SqlMigration is exported, so it has to be
@@ -214,3 +283,375 @@ pub enum ColumnTypeChange {
     RiskyCast,
     NotCastable,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flavour::PostgresFlavour;
+    use sql_schema_describer::{
+        Column, ColumnArity, ColumnStorage, ColumnType, ColumnTypeFamily, GeneratedColumnStorage, SqlSchema,
+    };
+
+    fn schema_with_column_storage(storage: Option<ColumnStorage>) -> SqlSchema {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("a".to_owned(), Default::default(), None);
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "blob".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: "bytea".to_owned(),
+                    family: ColumnTypeFamily::Binary,
+                    arity: ColumnArity::Required,
+                    native_type: None,
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: storage,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+        schema
+    }
+
+    #[test]
+    fn column_storage_change_is_diffed_as_a_storage_change() {
+        let previous = schema_with_column_storage(Some(ColumnStorage::Extended));
+        let next = schema_with_column_storage(Some(ColumnStorage::External));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(changes.iter().any(|change| change == ColumnChange::Storage));
+        assert_eq!(changes.type_change, None, "a storage change must not force a recreate");
+    }
+
+    #[test]
+    fn matching_column_storage_does_not_produce_a_storage_change() {
+        let previous = schema_with_column_storage(Some(ColumnStorage::Extended));
+        let next = schema_with_column_storage(Some(ColumnStorage::Extended));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(!changes.iter().any(|change| change == ColumnChange::Storage));
+    }
+
+    fn schema_with_generated_column(storage: Option<GeneratedColumnStorage>) -> SqlSchema {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("a".to_owned(), Default::default(), None);
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "gen".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: "text".to_owned(),
+                    family: ColumnTypeFamily::String,
+                    arity: ColumnArity::Required,
+                    native_type: None,
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: storage,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+        schema
+    }
+
+    #[test]
+    fn generated_column_storage_change_forces_a_recreate() {
+        let previous = schema_with_generated_column(Some(GeneratedColumnStorage::Stored));
+        let next = schema_with_generated_column(Some(GeneratedColumnStorage::Virtual));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert_eq!(changes.type_change, Some(ColumnTypeChange::NotCastable));
+    }
+
+    #[test]
+    fn matching_generated_column_storage_does_not_force_a_recreate() {
+        let previous = schema_with_generated_column(Some(GeneratedColumnStorage::Stored));
+        let next = schema_with_generated_column(Some(GeneratedColumnStorage::Stored));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert_eq!(changes.type_change, None);
+    }
+
+    fn schema_with_native_type(native_type: psl::builtin_connectors::PostgresType) -> SqlSchema {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("a".to_owned(), Default::default(), None);
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "col".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: String::new(),
+                    family: ColumnTypeFamily::Int,
+                    arity: ColumnArity::Required,
+                    native_type: Some(psl::datamodel_connector::NativeTypeInstance::new::<
+                        psl::builtin_connectors::PostgresType,
+                    >(native_type)),
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+        schema
+    }
+
+    #[test]
+    fn a_type_override_escalates_a_safe_cast_to_risky() {
+        use psl::builtin_connectors::PostgresType;
+
+        let previous = schema_with_native_type(PostgresType::Integer);
+        let next = schema_with_native_type(PostgresType::Text);
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let without_override = all_changes(columns, &PostgresFlavour::default(), None, false);
+        assert_eq!(without_override.type_change, Some(ColumnTypeChange::SafeCast));
+
+        let with_override = all_changes(columns, &PostgresFlavour::default(), Some("VARCHAR(191)"), false);
+        assert_eq!(with_override.type_change, Some(ColumnTypeChange::RiskyCast));
+    }
+
+    #[test]
+    fn ignore_varchar_length_suppresses_a_pure_length_increase() {
+        use psl::builtin_connectors::PostgresType;
+
+        let previous = schema_with_native_type(PostgresType::VarChar(Some(10)));
+        let next = schema_with_native_type(PostgresType::VarChar(Some(50)));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let ignored = all_changes(columns, &PostgresFlavour::default(), None, true);
+        assert_eq!(ignored.type_change, None);
+
+        let not_ignored = all_changes(columns, &PostgresFlavour::default(), None, false);
+        assert_eq!(not_ignored.type_change, Some(ColumnTypeChange::SafeCast));
+    }
+
+    #[test]
+    fn ignore_varchar_length_still_warns_about_a_length_decrease() {
+        use psl::builtin_connectors::PostgresType;
+
+        let previous = schema_with_native_type(PostgresType::VarChar(Some(50)));
+        let next = schema_with_native_type(PostgresType::VarChar(Some(10)));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, true);
+        assert_eq!(
+            changes.type_change,
+            Some(ColumnTypeChange::RiskyCast),
+            "a length decrease can truncate data and must still warn even when ignored"
+        );
+    }
+
+    fn schema_with_not_null_constraint_name(arity: ColumnArity, name: Option<&str>) -> SqlSchema {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("a".to_owned(), Default::default(), None);
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "required".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: "text".to_owned(),
+                    family: ColumnTypeFamily::String,
+                    arity,
+                    native_type: None,
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: name.map(str::to_owned),
+                on_update_now: false,
+            },
+        );
+        schema
+    }
+
+    #[test]
+    fn not_null_constraint_rename_is_diffed_as_a_rename() {
+        let previous = schema_with_not_null_constraint_name(ColumnArity::Required, Some("old_name"));
+        let next = schema_with_not_null_constraint_name(ColumnArity::Required, Some("new_name"));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(changes.iter().any(|change| change == ColumnChange::NotNullConstraintName));
+        assert_eq!(changes.type_change, None, "a constraint rename must not force a recreate");
+    }
+
+    #[test]
+    fn an_unnamed_not_null_constraint_becoming_named_is_diffed_as_a_rename() {
+        let previous = schema_with_not_null_constraint_name(ColumnArity::Required, None);
+        let next = schema_with_not_null_constraint_name(ColumnArity::Required, Some("new_name"));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(changes.iter().any(|change| change == ColumnChange::NotNullConstraintName));
+    }
+
+    #[test]
+    fn matching_not_null_constraint_names_do_not_produce_a_rename() {
+        let previous = schema_with_not_null_constraint_name(ColumnArity::Required, Some("same_name"));
+        let next = schema_with_not_null_constraint_name(ColumnArity::Required, Some("same_name"));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(!changes.iter().any(|change| change == ColumnChange::NotNullConstraintName));
+    }
+
+    #[test]
+    fn a_nullable_column_does_not_diff_not_null_constraint_names() {
+        let previous = schema_with_not_null_constraint_name(ColumnArity::Nullable, Some("old_name"));
+        let next = schema_with_not_null_constraint_name(ColumnArity::Nullable, Some("new_name"));
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(!changes.iter().any(|change| change == ColumnChange::NotNullConstraintName));
+    }
+
+    fn schema_with_on_update_now(on_update_now: bool) -> SqlSchema {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("a".to_owned(), Default::default(), None);
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "updated_at".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: String::new(),
+                    family: ColumnTypeFamily::DateTime,
+                    arity: ColumnArity::Required,
+                    native_type: None,
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now,
+            },
+        );
+        schema
+    }
+
+    #[test]
+    fn toggling_on_update_current_timestamp_on_is_diffed_as_a_change() {
+        let previous = schema_with_on_update_now(false);
+        let next = schema_with_on_update_now(true);
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &crate::flavour::MysqlFlavour::default(), None, false);
+
+        assert!(changes.iter().any(|change| change == ColumnChange::OnUpdateNow));
+        assert_eq!(changes.type_change, None, "toggling ON UPDATE must not force a recreate");
+    }
+
+    #[test]
+    fn toggling_on_update_current_timestamp_off_is_diffed_as_a_change() {
+        let previous = schema_with_on_update_now(true);
+        let next = schema_with_on_update_now(false);
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &crate::flavour::MysqlFlavour::default(), None, false);
+
+        assert!(changes.iter().any(|change| change == ColumnChange::OnUpdateNow));
+    }
+
+    #[test]
+    fn matching_on_update_current_timestamp_does_not_produce_a_change() {
+        let previous = schema_with_on_update_now(true);
+        let next = schema_with_on_update_now(true);
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &crate::flavour::MysqlFlavour::default(), None, false);
+
+        assert!(!changes.iter().any(|change| change == ColumnChange::OnUpdateNow));
+    }
+
+    #[test]
+    fn on_update_current_timestamp_is_ignored_outside_mysql() {
+        let previous = schema_with_on_update_now(false);
+        let next = schema_with_on_update_now(true);
+
+        let columns = MigrationPair::new(
+            previous.walk_table_columns().next().unwrap(),
+            next.walk_table_columns().next().unwrap(),
+        );
+
+        let changes = all_changes(columns, &PostgresFlavour::default(), None, false);
+
+        assert!(!changes.iter().any(|change| change == ColumnChange::OnUpdateNow));
+    }
+}
@@ -2,22 +2,80 @@ use super::{column, enums::EnumDiffer, table::TableDiffer};
 use crate::{flavour::SqlFlavour, migration_pair::MigrationPair, SqlDatabaseSchema};
 use indexmap::IndexMap;
 use sql_schema_describer::{
-    postgres::{ExtensionId, ExtensionWalker, PostgresSchemaExt},
-    walkers::{EnumWalker, TableColumnWalker, TableWalker},
+    postgres::{DomainWalker, ExtensionId, ExtensionWalker, PostgresSchemaExt},
+    walkers::{EnumWalker, TableColumnWalker, TableWalker, TriggerWalker},
     NamespaceId, NamespaceWalker, TableColumnId, TableId,
 };
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     ops::Bound,
 };
 
 type Table<'a> = (Option<Cow<'a, str>>, Cow<'a, str>);
 
+/// Escape-hatch settings for a single diffing run that aren't derived from comparing the two
+/// schemas, but instead force specific behavior regardless of what the usual diffing rules would
+/// decide.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DifferSettings {
+    /// Columns, identified by their id in the *previous* schema, whose type change must be
+    /// migrated through a full drop-and-recreate even when the castability matrix says an
+    /// in-place `SafeCast`/`RiskyCast` would do. For edge cases the matrix handles too
+    /// leniently — e.g. forcing a rebuild to re-tighten storage — where the caller knows better
+    /// than the matrix.
+    pub(crate) force_recreate_columns: HashSet<TableColumnId>,
+    /// Move every `AddForeignKey` step to the end of the step order, after every other step, and
+    /// render them as deferred constraints where the flavour supports it (currently Postgres
+    /// only). Intended for schemas with circular foreign key dependencies, where adding every
+    /// table and its data first, then wiring up the foreign keys afterwards, avoids having to
+    /// order the `AddForeignKey` steps relative to each other at all. SQLite has no use for this:
+    /// it handles such schemas through `RedefineTables` and `PRAGMA defer_foreign_keys` instead.
+    pub(crate) defer_foreign_keys: bool,
+    /// Render created indexes with `CONCURRENTLY`, where the flavour supports it (currently
+    /// Postgres only; see [`super::SqlSchemaDifferFlavour::supports_concurrent_index_creation`]).
+    /// A concurrently created index can't run inside the migration's transaction, so steps
+    /// created with this set also report
+    /// [`crate::sql_migration::SqlMigrationStep::requires_separate_transaction`] as `true`, for
+    /// an orchestrator to run them outside of it.
+    pub(crate) concurrent_index_creation: bool,
+    /// Columns, identified by their id in the *next* schema, whose generated type should be the
+    /// given string instead of whatever the flavour's usual native-type mapping would pick (e.g.
+    /// `TEXT` instead of the default `VARCHAR(191)` for a PSL `String` field). Also affects
+    /// castability: a type change that the matrix would otherwise call a `SafeCast`, or no type
+    /// change at all, becomes a `RiskyCast` for an overridden column, since the override is an
+    /// arbitrary native type we can't verify the safety of.
+    pub(crate) type_overrides: HashMap<TableColumnId, String>,
+    /// Suppress every rename-only `RenameIndex`/`RedefineIndex` step, for teams that don't track
+    /// index names and don't want them to churn migrations. This is a blunt instrument: if two
+    /// indexes swapped names, the renames that would actually fix that are suppressed along with
+    /// every cosmetic one.
+    pub(crate) ignore_index_renames: bool,
+    /// Merge a table's created/dropped foreign keys into that table's own `AlterTable` step as
+    /// `TableChange::AddForeignKey`/`TableChange::DropForeignKey`, instead of emitting them as
+    /// separate `SqlMigrationStep::AddForeignKey`/`SqlMigrationStep::DropForeignKey` steps. Only
+    /// takes effect on a flavour where
+    /// [`super::SqlSchemaDifferFlavour::supports_multiple_alter_table_clauses`] is true; foreign
+    /// key renames and drop+recreate pairs (e.g. because the referenced table is being
+    /// redefined) are unaffected, since those have their own ordering requirements relative to
+    /// other steps.
+    pub(crate) coalesce_foreign_keys_into_alter_table: bool,
+    /// Treat a pure `varchar`/`char` length increase (the base type and everything else about the
+    /// column stays the same) as a no-op, rather than the `AlterColumn` it would otherwise produce,
+    /// for teams that map every string to an unbounded type and don't want length-only drift to
+    /// migrate. A length *decrease* is left alone even when this is set: it can truncate existing
+    /// data, so it keeps surfacing as a [`super::column::ColumnTypeChange::RiskyCast`] to warn about
+    /// it instead of being silently ignored. See
+    /// [`super::SqlSchemaDifferFlavour::varchar_length_change`].
+    pub(crate) ignore_varchar_length: bool,
+}
+
 pub(crate) struct DifferDatabase<'a> {
     pub(super) flavour: &'a dyn SqlFlavour,
     /// The schemas being diffed
     pub(crate) schemas: MigrationPair<&'a SqlDatabaseSchema>,
+    /// Escape-hatch settings for this diffing run. See [`DifferSettings`].
+    pub(crate) settings: DifferSettings,
     /// Namespace name -> namespace indexes.
     namespaces: IndexMap<Cow<'a, str>, MigrationPair<Option<NamespaceId>>>,
     /// Table name -> table indexes.
@@ -36,6 +94,14 @@ pub(crate) struct DifferDatabase<'a> {
 
 impl<'a> DifferDatabase<'a> {
     pub(crate) fn new(schemas: MigrationPair<&'a SqlDatabaseSchema>, flavour: &'a dyn SqlFlavour) -> Self {
+        Self::new_with_settings(schemas, flavour, DifferSettings::default())
+    }
+
+    pub(crate) fn new_with_settings(
+        schemas: MigrationPair<&'a SqlDatabaseSchema>,
+        flavour: &'a dyn SqlFlavour,
+        settings: DifferSettings,
+    ) -> Self {
         let namespace_count_lb = std::cmp::max(
             schemas.previous.describer_schema.namespaces_count(),
             schemas.next.describer_schema.namespaces_count(),
@@ -48,6 +114,7 @@ impl<'a> DifferDatabase<'a> {
         let mut db = DifferDatabase {
             flavour,
             schemas,
+            settings,
             namespaces: IndexMap::with_capacity(namespace_count_lb),
             tables: IndexMap::with_capacity(table_count_lb),
             columns: BTreeMap::new(),
@@ -144,7 +211,13 @@ impl<'a> DifferDatabase<'a> {
 
                     if let Some(column_ids) = column_ids.transpose() {
                         let column_walkers = schemas.walk(column_ids);
-                        let changes = column::all_changes(column_walkers, flavour);
+                        let type_override = db.settings.type_overrides.get(&column_ids.next).map(String::as_str);
+                        let changes = column::all_changes(
+                            column_walkers,
+                            flavour,
+                            type_override,
+                            db.settings.ignore_varchar_length,
+                        );
                         db.column_changes.insert(column_ids, changes);
                     }
                 }
@@ -201,6 +274,14 @@ impl<'a> DifferDatabase<'a> {
             .map(move |namespace_id| self.schemas.next.walk(namespace_id))
     }
 
+    pub(crate) fn dropped_namespaces(&self) -> impl Iterator<Item = NamespaceWalker<'_>> + '_ {
+        self.namespaces
+            .values()
+            .filter(|p| p.next.is_none())
+            .filter_map(|p| p.previous)
+            .map(move |namespace_id| self.schemas.previous.walk(namespace_id))
+    }
+
     pub(crate) fn dropped_columns(&self, table: MigrationPair<TableId>) -> impl Iterator<Item = TableColumnId> + '_ {
         self.range_columns(table)
             .filter(|(_k, v)| v.next.is_none())
@@ -312,6 +393,66 @@ impl<'a> DifferDatabase<'a> {
         })
     }
 
+    /// Domains present in both schemas, paired by name and schema.
+    pub(crate) fn domain_pairs(&self) -> impl Iterator<Item = MigrationPair<DomainWalker<'a>>> + '_ {
+        self.previous_domains().filter_map(move |previous| {
+            self.next_domains()
+                .find(|next| domains_match(&previous, next))
+                .map(|next| MigrationPair::new(previous, next))
+        })
+    }
+
+    /// Domains not present in the previous schema.
+    pub(crate) fn created_domains<'db>(&'db self) -> impl Iterator<Item = DomainWalker<'a>> + 'db {
+        self.next_domains()
+            .filter(move |next| !self.previous_domains().any(|previous| domains_match(&previous, next)))
+    }
+
+    /// Domains not present in the next schema.
+    pub(crate) fn dropped_domains<'db>(&'db self) -> impl Iterator<Item = DomainWalker<'a>> + 'db {
+        self.previous_domains()
+            .filter(move |previous| !self.next_domains().any(|next| domains_match(previous, &next)))
+    }
+
+    /// Triggers present in both schemas, paired by name and table name.
+    pub(crate) fn trigger_pairs(&self) -> impl Iterator<Item = MigrationPair<TriggerWalker<'a>>> + '_ {
+        self.previous_triggers().filter_map(move |previous| {
+            self.next_triggers()
+                .find(|next| triggers_match(&previous, next))
+                .map(|next| MigrationPair::new(previous, next))
+        })
+    }
+
+    /// Triggers not present in the previous schema.
+    pub(crate) fn created_triggers<'db>(&'db self) -> impl Iterator<Item = TriggerWalker<'a>> + 'db {
+        self.next_triggers()
+            .filter(move |next| !self.previous_triggers().any(|previous| triggers_match(&previous, next)))
+    }
+
+    /// Triggers not present in the next schema.
+    pub(crate) fn dropped_triggers<'db>(&'db self) -> impl Iterator<Item = TriggerWalker<'a>> + 'db {
+        self.previous_triggers()
+            .filter(move |previous| !self.next_triggers().any(|next| triggers_match(previous, &next)))
+    }
+
+    fn previous_triggers(&self) -> impl Iterator<Item = TriggerWalker<'a>> {
+        self.schemas.previous.describer_schema.trigger_walkers()
+    }
+
+    fn next_triggers(&self) -> impl Iterator<Item = TriggerWalker<'a>> {
+        self.schemas.next.describer_schema.trigger_walkers()
+    }
+
+    fn previous_domains(&self) -> impl Iterator<Item = DomainWalker<'a>> {
+        let conn_data: &PostgresSchemaExt = self.schemas.previous.describer_schema.downcast_connector_data();
+        conn_data.domain_walkers()
+    }
+
+    fn next_domains(&self) -> impl Iterator<Item = DomainWalker<'a>> {
+        let conn_data: &PostgresSchemaExt = self.schemas.next.describer_schema.downcast_connector_data();
+        conn_data.domain_walkers()
+    }
+
     fn previous_enums(&self) -> impl Iterator<Item = EnumWalker<'a>> {
         self.schemas.previous.describer_schema.enum_walkers()
     }
@@ -342,6 +483,14 @@ pub(crate) fn extensions_match(previous: ExtensionWalker<'_>, next: ExtensionWal
     names_match && versions_match && schemas_match
 }
 
+fn domains_match(previous: &DomainWalker<'_>, next: &DomainWalker<'_>) -> bool {
+    previous.name() == next.name() && previous.schema() == next.schema()
+}
+
 fn enums_match(previous: &EnumWalker<'_>, next: &EnumWalker<'_>) -> bool {
     previous.name() == next.name() && previous.namespace() == next.namespace()
 }
+
+fn triggers_match(previous: &TriggerWalker<'_>, next: &TriggerWalker<'_>) -> bool {
+    previous.name() == next.name() && previous.table().name() == next.table().name()
+}
@@ -1,6 +1,7 @@
 use sql_schema_describer::walkers::EnumWalker;
 
-use crate::migration_pair::MigrationPair;
+use super::table::TableDiffer;
+use crate::{migration_pair::MigrationPair, sql_migration::TableChange};
 
 pub(crate) struct EnumDiffer<'a> {
     pub(crate) enums: MigrationPair<EnumWalker<'a>>,
@@ -31,3 +32,41 @@ impl<'a> EnumDiffer<'a> {
 fn values_match(previous: &str, next: &str) -> bool {
     previous == next
 }
+
+/// Maps enum creations/drops affecting `table`'s columns into `AddCheckConstraint`/
+/// `DropCheckConstraint` changes. A single enum can be used by columns on several different
+/// tables; this is called once per table, so each affected column gets its own constraint.
+pub(crate) fn push_check_constraint_changes(table: &TableDiffer<'_, '_>, changes: &mut Vec<TableChange>) {
+    let db = table.db;
+
+    for enm in db.dropped_enums() {
+        for column in table.column_pairs() {
+            if column.previous.column_type_family_as_enum().map(|e| e.id) == Some(enm.id) {
+                changes.push(TableChange::DropCheckConstraint {
+                    constraint_name: check_constraint_name(table.tables.previous.name(), column.previous.name()),
+                });
+            }
+        }
+    }
+
+    for enm in db.created_enums() {
+        for column in table.column_pairs() {
+            if column.next.column_type_family_as_enum().map(|e| e.id) == Some(enm.id) {
+                changes.push(TableChange::AddCheckConstraint {
+                    constraint_name: check_constraint_name(table.tables.next.name(), column.next.name()),
+                    definition: check_constraint_definition(column.next.name(), enm),
+                });
+            }
+        }
+    }
+}
+
+fn check_constraint_name(table_name: &str, column_name: &str) -> String {
+    format!("{table_name}_{column_name}_check")
+}
+
+fn check_constraint_definition(column_name: &str, enm: EnumWalker<'_>) -> String {
+    let variants = enm.values().map(|v| format!("'{v}'")).collect::<Vec<_>>().join(", ");
+
+    format!("CHECK (\"{column_name}\" IN ({variants}))")
+}
@@ -1,8 +1,8 @@
 use super::{differ_database::DifferDatabase, ColumnTypeChange};
 use crate::{migration_pair::MigrationPair, sql_migration::SqlMigrationStep, sql_schema_differ};
 use sql_schema_describer::{
-    walkers::{IndexWalker, TableColumnWalker, TableWalker},
-    TableColumnId,
+    walkers::{IndexColumnWalker, IndexWalker, TableColumnWalker, TableWalker},
+    PrismaValue, TableColumnId,
 };
 
 mod mssql;
@@ -27,6 +27,14 @@ pub(crate) trait SqlSchemaDifferFlavour {
         true
     }
 
+    /// Whether the database can rename a column with a native `RENAME COLUMN` statement, instead
+    /// of going through a table redefinition. Defaults to `true`, since every connector but SQLite
+    /// supports renaming columns natively unconditionally; SQLite overrides this to gate on its
+    /// version, since `ALTER TABLE ... RENAME COLUMN` only landed in SQLite 3.25.0.
+    fn can_rename_column(&self) -> bool {
+        true
+    }
+
     /// Returns true only if the database can cope with an optional column
     /// constrained by a foreign key being made NOT NULL.
     fn can_cope_with_foreign_key_column_becoming_non_nullable(&self) -> bool {
@@ -44,6 +52,51 @@ pub(crate) trait SqlSchemaDifferFlavour {
     /// Return whether a column's type needs to be migrated, and how.
     fn column_type_change(&self, differ: MigrationPair<TableColumnWalker<'_>>) -> Option<ColumnTypeChange>;
 
+    /// Whether a column type change can be applied without blocking reads and writes on the
+    /// table for its duration (e.g. a metadata-only change, or one the database can perform
+    /// with a brief lock rather than rewriting every row). This is orthogonal to
+    /// [`ColumnTypeChange`], which is about whether the cast can lose data, not about how the
+    /// database performs it: a widening int cast is always a `SafeCast`, but it may or may not
+    /// require a table rewrite depending on the flavour. Defaults to `false`, so a flavour has
+    /// to opt in to claiming a type change is cheap.
+    fn column_type_change_is_online(&self, _columns: MigrationPair<TableColumnWalker<'_>>) -> bool {
+        false
+    }
+
+    /// Whether a `DbGenerated` default (an opaque SQL expression captured from introspection) has
+    /// the same effective value as `value`, so that switching between the two representations
+    /// (e.g. a literal being rewritten as an equivalent cast expression) is not treated as a
+    /// default change requiring a migration step.
+    ///
+    /// The default implementation only recognizes `db_generated` being the literal's own
+    /// rendering, optionally wrapped in a single `::<type>` cast as produced by PostgreSQL and
+    /// CockroachDB. It cannot know whether an arbitrary SQL expression like `nextval(...)` is
+    /// runtime-dependent, so it conservatively returns `false` for anything it cannot parse back
+    /// into the same literal.
+    fn default_value_matches_db_generated(&self, value: &PrismaValue, db_generated: &str) -> bool {
+        let literal = db_generated.split("::").next().unwrap_or(db_generated).trim();
+        let literal = literal.trim_matches('\'');
+
+        match value {
+            PrismaValue::Boolean(b) => literal.eq_ignore_ascii_case(if *b { "true" } else { "false" }),
+            PrismaValue::Int(i) => literal.parse::<i64>().map(|n| n == *i).unwrap_or(false),
+            PrismaValue::BigInt(i) => literal.parse::<i64>().map(|n| n == *i).unwrap_or(false),
+            PrismaValue::Float(f) => literal == f.to_string(),
+            PrismaValue::String(s) | PrismaValue::Enum(s) => literal == s,
+            _ => false,
+        }
+    }
+
+    /// Whether a foreign key column that switched between the `Uuid` and `String` type families
+    /// should still be considered the same column for the purposes of matching up foreign keys,
+    /// instead of forcing the foreign key to be dropped and recreated. Defaults to `true`, since
+    /// that switch is usually a storage-level artifact — e.g. a UUID column introspected as a
+    /// plain string column — rather than a meaningful type change. Strict callers that want any
+    /// type change on a constrained column to force a recreate can override this to `false`.
+    fn fk_type_leniency(&self) -> bool {
+        true
+    }
+
     /// Push enum-related steps.
     fn push_enum_steps(&self, _steps: &mut Vec<SqlMigrationStep>, _db: &DifferDatabase<'_>) {}
 
@@ -53,19 +106,143 @@ pub(crate) trait SqlSchemaDifferFlavour {
     /// Push AlterExtension steps.
     fn push_extension_steps(&self, _steps: &mut Vec<SqlMigrationStep>, _db: &DifferDatabase<'_>) {}
 
+    /// Push `CreateDomain`/`AlterDomain`/`DropDomain` steps, pairing previous and next domains by
+    /// name and schema. Gated behind a capability because only PostgreSQL has domains.
+    fn push_domain_steps(&self, _steps: &mut Vec<SqlMigrationStep>, _db: &DifferDatabase<'_>) {}
+
     /// Define database-specific extension modules.
     fn define_extensions(&self, _db: &mut DifferDatabase<'_>) {}
 
+    /// Push row level security policy and `ENABLE`/`DISABLE ROW LEVEL SECURITY` steps, pairing
+    /// previous and next policies by name per table.
+    fn push_policy_steps(&self, _steps: &mut Vec<SqlMigrationStep>, _db: &DifferDatabase<'_>) {}
+
+    /// Push `CreateTrigger`/`DropTrigger` steps, pairing previous and next triggers by name per
+    /// table. Most engines can't alter a trigger in place, so a changed trigger is always a
+    /// `DropTrigger` followed by a `CreateTrigger`, never an in-place alteration. Gated behind a
+    /// capability because SQLite, MySQL, and PostgreSQL differ in trigger support.
+    fn push_trigger_steps(&self, _steps: &mut Vec<SqlMigrationStep>, _db: &DifferDatabase<'_>) {}
+
+    /// Push `AddTableInheritance`/`DropTableInheritance` steps, pairing previous and next parent
+    /// tables by name per table.
+    fn push_table_inheritance_steps(&self, _steps: &mut Vec<SqlMigrationStep>, _db: &DifferDatabase<'_>) {}
+
     /// Connector-specific criterias deciding whether two indexes match.
     fn indexes_match(&self, _a: IndexWalker<'_>, _b: IndexWalker<'_>) -> bool {
         true
     }
 
+    /// Whether two index columns' prefix lengths should be considered equal. Gated behind a
+    /// capability because only MySQL supports indexing a prefix of a column; every other
+    /// connector always leaves `IndexColumn::length` at `None`, so plain equality already does
+    /// the right thing for them.
+    fn index_column_lengths_match(&self, a: IndexColumnWalker<'_>, b: IndexColumnWalker<'_>) -> bool {
+        a.length() == b.length()
+    }
+
+    /// Whether a changed index column collation should force the index to be recreated. Gated
+    /// behind a capability because only PostgreSQL currently introspects per-column index
+    /// collations; every other connector always leaves `IndexColumn::collation` at `None`, so
+    /// this would otherwise be a costly no-op comparison for them.
+    fn compares_index_column_collation(&self) -> bool {
+        false
+    }
+
+    /// Whether a changed generated-column storage kind (`STORED` vs `VIRTUAL`) should force the
+    /// column to be dropped and recreated. Gated behind a capability because only PostgreSQL
+    /// currently introspects `Column::generated_as`; every other connector always leaves it at
+    /// `None`, so this would otherwise be a costly no-op comparison for them.
+    fn compares_generated_column_storage(&self) -> bool {
+        false
+    }
+
+    /// Whether a changed TOAST storage strategy (`PLAIN`/`EXTERNAL`/`EXTENDED`/`MAIN`) should be
+    /// diffed and rendered as `ALTER COLUMN ... SET STORAGE`. Gated behind a capability because
+    /// only PostgreSQL currently introspects `Column::toast_storage`; every other connector
+    /// always leaves it at `None`, so this would otherwise be a costly no-op comparison for them.
+    fn compares_column_storage(&self) -> bool {
+        false
+    }
+
+    /// Whether a changed `NOT NULL` constraint name should be diffed and rendered as a
+    /// constraint rename, rather than ignored. Gated behind a capability because only PostgreSQL
+    /// currently introspects `Column::not_null_constraint_name`; every other connector always
+    /// leaves it at `None`, so this would otherwise be a costly no-op comparison for them.
+    fn compares_not_null_constraint_names(&self) -> bool {
+        false
+    }
+
+    /// Whether a changed `ON UPDATE CURRENT_TIMESTAMP` attribute should be diffed and rendered.
+    /// Gated behind a capability because only MySQL introspects `Column::on_update_now`; every
+    /// other connector always leaves it at `false`, so this would otherwise be a costly no-op
+    /// comparison for them.
+    fn compares_on_update_current_timestamp(&self) -> bool {
+        false
+    }
+
+    /// Whether a changed index tablespace should be diffed and rendered as `ALTER INDEX ... SET
+    /// TABLESPACE ...`. Gated behind a capability because only PostgreSQL currently introspects
+    /// `Index::tablespace`; every other connector always leaves it at `None`, so this would
+    /// otherwise be a costly no-op comparison for them.
+    fn compares_index_tablespaces(&self) -> bool {
+        false
+    }
+
+    /// Whether a changed foreign key `MATCH` type should force the foreign key to be dropped and
+    /// recreated. Gated behind a capability because only PostgreSQL introspects
+    /// `ForeignKey::match_type`; every other connector always leaves it at
+    /// `ForeignKeyMatchType::Simple`, so this would otherwise be a costly no-op comparison for
+    /// them.
+    fn compares_foreign_key_match_types(&self) -> bool {
+        false
+    }
+
+    /// If this column pair's type change is purely a `varchar`/`char` length change (the base
+    /// type is otherwise identical), return whether the length grew or shrank. Returns `None` if
+    /// the type didn't change, or changed in some other way than length. Used to honor
+    /// [`super::DifferSettings::ignore_varchar_length`], which only this flavour's logic is
+    /// equipped to recognize as "pure" — every other connector returns `None` unconditionally.
+    fn varchar_length_change(&self, _columns: MigrationPair<TableColumnWalker<'_>>) -> Option<VarcharLengthChange> {
+        None
+    }
+
+    /// Whether this flavour can render `CREATE INDEX CONCURRENTLY`, honored when
+    /// [`super::DifferSettings::concurrent_index_creation`] is set. Only PostgreSQL supports
+    /// building an index without locking out writes to the table.
+    fn supports_concurrent_index_creation(&self) -> bool {
+        false
+    }
+
+    /// Whether this flavour can render more than one clause in a single `ALTER TABLE` statement,
+    /// e.g. `ALTER TABLE t ADD COLUMN ..., ADD CONSTRAINT ...`. Gates
+    /// [`super::DifferSettings::coalesce_foreign_keys_into_alter_table`]: only a flavour that
+    /// supports multi-clause `ALTER TABLE` can inline a created/dropped foreign key into the
+    /// table's own `AlterTable` step rather than emitting it as a separate step.
+    fn supports_multiple_alter_table_clauses(&self) -> bool {
+        false
+    }
+
     /// Returns whether the underlying database implicitly drops indexes on dropped (and potentially recreated) columns.
     fn indexes_should_be_recreated_after_column_drop(&self) -> bool {
         false
     }
 
+    /// Whether a changed index predicate (the `WHERE` clause of a partial/filtered index) should
+    /// force the index to be dropped and recreated. Gated behind a capability because only
+    /// PostgreSQL currently introspects `Index::predicate`; every other connector always leaves
+    /// it at `None`, so this would otherwise be a costly no-op comparison for them.
+    fn supports_filtered_indexes(&self) -> bool {
+        false
+    }
+
+    /// Whether this flavour introspects and can render comments on indexes and foreign key
+    /// constraints. Gated behind a capability because only PostgreSQL currently introspects
+    /// `Index::description`/`ForeignKey::description`; every other connector always leaves them
+    /// at `None`, so this would otherwise be a costly no-op comparison for them.
+    fn supports_object_comments(&self) -> bool {
+        false
+    }
+
     /// Return whether an index should be renamed by the migration.
     fn index_should_be_renamed(&self, indexes: MigrationPair<IndexWalker<'_>>) -> bool {
         indexes.previous.name() != indexes.next.name()
@@ -80,6 +257,66 @@ pub(crate) trait SqlSchemaDifferFlavour {
         false
     }
 
+    /// Evaluate `EXCLUDE` constraint (Postgres-only) additions and removals for a table, pairing
+    /// previous and next constraints by name.
+    fn push_exclusion_constraint_changes(
+        &self,
+        _table: &sql_schema_differ::TableDiffer<'_, '_>,
+        _changes: &mut Vec<crate::sql_migration::TableChange>,
+    ) {
+    }
+
+    /// Evaluate a change to a table's persistence (Postgres-only: `LOGGED`/`UNLOGGED`).
+    fn push_table_persistence_changes(
+        &self,
+        _table: &sql_schema_differ::TableDiffer<'_, '_>,
+        _changes: &mut Vec<crate::sql_migration::TableChange>,
+    ) {
+    }
+
+    /// Evaluate a change to a table's default collation (MySQL-only).
+    fn push_table_collation_changes(
+        &self,
+        _table: &sql_schema_differ::TableDiffer<'_, '_>,
+        _changes: &mut Vec<crate::sql_migration::TableChange>,
+    ) {
+    }
+
+    /// Evaluate a change to a table's tablespace (Postgres-only).
+    fn push_table_tablespace_changes(
+        &self,
+        _table: &sql_schema_differ::TableDiffer<'_, '_>,
+        _changes: &mut Vec<crate::sql_migration::TableChange>,
+    ) {
+    }
+
+    /// Whether this flavour represents enums as a plain column plus a `CHECK` constraint
+    /// restricting it to the enum's variants, rather than as a native enum type. When true,
+    /// enum creations and drops are turned into per-column `CHECK` constraint changes (see
+    /// [`Self::push_enum_check_constraint_changes`]) instead of
+    /// [`crate::sql_migration::SqlMigrationStep::CreateEnum`]/
+    /// [`crate::sql_migration::SqlMigrationStep::DropEnum`]/
+    /// [`crate::sql_migration::SqlMigrationStep::AlterEnum`].
+    fn emulates_enums_as_check_constraints(&self) -> bool {
+        false
+    }
+
+    /// Evaluate enum creations/drops affecting `table`'s columns and turn them into `CHECK`
+    /// constraint changes, for flavours where [`Self::emulates_enums_as_check_constraints`] is
+    /// true. A single enum can be shared by columns on several different tables; each affected
+    /// column gets its own constraint, named `<table>_<column>_check`.
+    fn push_enum_check_constraint_changes(
+        &self,
+        table: &sql_schema_differ::TableDiffer<'_, '_>,
+        changes: &mut Vec<crate::sql_migration::TableChange>,
+    ) {
+        if !self.emulates_enums_as_check_constraints() {
+            return;
+        }
+
+        sql_schema_differ::enums::push_check_constraint_changes(table, changes);
+    }
+
     /// Evaluate indexes/constraints that need to be dropped and re-created based on other changes in the schema
     fn push_index_changes_for_column_changes(
         &self,
@@ -162,3 +399,11 @@ pub(crate) trait SqlSchemaDifferFlavour {
         false
     }
 }
+
+/// The direction of a pure `varchar`/`char` length change, as classified by
+/// [`SqlSchemaDifferFlavour::varchar_length_change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VarcharLengthChange {
+    Increase,
+    Decrease,
+}
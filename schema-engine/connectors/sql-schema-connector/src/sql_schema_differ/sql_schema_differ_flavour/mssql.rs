@@ -65,6 +65,23 @@ impl SqlSchemaDifferFlavour for MssqlFlavour {
         }
     }
 
+    fn column_type_change_is_online(&self, differ: MigrationPair<sql::TableColumnWalker<'_>>) -> bool {
+        // `ALTER TABLE ... ALTER COLUMN` to a longer `varchar`/`nvarchar` (or to the unbounded
+        // `max`) only has to validate that existing rows still fit and doesn't need to touch the
+        // stored bytes, so SQL Server can apply it without rewriting the table. Every other type
+        // change, including widening an integer type, re-evaluates and rewrites every row.
+        match (
+            differ.previous.column_native_type::<MsSqlType>(),
+            differ.next.column_native_type::<MsSqlType>(),
+        ) {
+            (Some(MsSqlType::VarChar(prev)), Some(MsSqlType::VarChar(next)))
+            | (Some(MsSqlType::NVarChar(prev)), Some(MsSqlType::NVarChar(next))) => {
+                varchar_length_increased(*prev, *next)
+            }
+            _ => false,
+        }
+    }
+
     fn primary_key_changed(&self, tables: MigrationPair<sql::TableWalker<'_>>) -> bool {
         let pk_clusterings = tables.map(|t| {
             let ext: &MssqlSchemaExt = t.schema.downcast_connector_data();
@@ -102,6 +119,7 @@ impl SqlSchemaDifferFlavour for MssqlFlavour {
                 table_id: (None, table.next().id),
                 index_id: created_index.next.id,
                 from_drop_and_recreate: false,
+                concurrently: false,
             })
         }
     }
@@ -1150,3 +1168,80 @@ fn native_type_change_riskyness(previous: &MsSqlType, next: &MsSqlType) -> Optio
         _ => Some(cast()),
     }
 }
+
+/// Whether a `varchar`/`nvarchar` length parameter grew, treating `Max` as larger than any
+/// bounded length and `None` (an unspecified length, which SQL Server defaults to `1`) as the
+/// smallest possible length.
+fn varchar_length_increased(previous: Option<MsSqlTypeParameter>, next: Option<MsSqlTypeParameter>) -> bool {
+    use MsSqlTypeParameter::*;
+
+    match (previous, next) {
+        (Some(Max), Some(Max)) => false,
+        (_, Some(Max)) => true,
+        (Some(Max), _) => false,
+        (Some(Number(prev)), Some(Number(next))) => prev <= next,
+        (None, Some(Number(_))) => true,
+        (_, None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sql_schema_describer::{Column, ColumnArity, ColumnType, SqlSchema};
+
+    fn column_schema(native_type: MsSqlType) -> (SqlSchema, TableColumnId) {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("A".to_owned(), Default::default(), None);
+        let column_id = schema.push_table_column(
+            table_id,
+            Column {
+                name: "a".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: String::new(),
+                    family: ColumnTypeFamily::String,
+                    arity: ColumnArity::Required,
+                    native_type: Some(psl::datamodel_connector::NativeTypeInstance::new(native_type)),
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        (schema, column_id)
+    }
+
+    #[test]
+    fn increasing_a_varchar_length_is_online() {
+        let (previous, previous_id) = column_schema(MsSqlType::VarChar(Some(MsSqlTypeParameter::Number(20))));
+        let (next, next_id) = column_schema(MsSqlType::VarChar(Some(MsSqlTypeParameter::Number(40))));
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(MssqlFlavour::default().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn growing_a_varchar_to_max_is_online() {
+        let (previous, previous_id) = column_schema(MsSqlType::VarChar(Some(MsSqlTypeParameter::Number(20))));
+        let (next, next_id) = column_schema(MsSqlType::VarChar(Some(MsSqlTypeParameter::Max)));
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(MssqlFlavour::default().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn widening_an_integer_is_not_online() {
+        let (previous, previous_id) = column_schema(MsSqlType::Int);
+        let (next, next_id) = column_schema(MsSqlType::BigInt);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(!MssqlFlavour::default().column_type_change_is_online(columns));
+    }
+}
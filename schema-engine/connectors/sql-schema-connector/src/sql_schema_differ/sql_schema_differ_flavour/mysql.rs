@@ -2,15 +2,38 @@ use super::SqlSchemaDifferFlavour;
 use crate::{
     flavour::MysqlFlavour,
     migration_pair::MigrationPair,
-    sql_schema_differ::{all_match, ColumnTypeChange},
+    sql_migration::{CreateTrigger, DropTrigger, SqlMigrationStep},
+    sql_schema_differ::{all_match, differ_database::DifferDatabase, ColumnTypeChange},
 };
 use psl::builtin_connectors::MySqlType;
 use sql_schema_describer::{
-    walkers::{IndexWalker, TableColumnWalker},
+    walkers::{IndexColumnWalker, IndexWalker, TableColumnWalker},
     ColumnTypeFamily,
 };
 
 impl SqlSchemaDifferFlavour for MysqlFlavour {
+    fn push_trigger_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+        for trigger in db.dropped_triggers() {
+            steps.push(SqlMigrationStep::DropTrigger(DropTrigger { id: trigger.id }));
+        }
+
+        for triggers in db.trigger_pairs() {
+            if triggers.previous.timing() != triggers.next.timing()
+                || triggers.previous.event() != triggers.next.event()
+                || triggers.previous.definition() != triggers.next.definition()
+            {
+                steps.push(SqlMigrationStep::DropTrigger(DropTrigger {
+                    id: triggers.previous.id,
+                }));
+                steps.push(SqlMigrationStep::CreateTrigger(CreateTrigger { id: triggers.next.id }));
+            }
+        }
+
+        for trigger in db.created_triggers() {
+            steps.push(SqlMigrationStep::CreateTrigger(CreateTrigger { id: trigger.id }));
+        }
+    }
+
     fn can_rename_foreign_key(&self) -> bool {
         false
     }
@@ -23,6 +46,10 @@ impl SqlSchemaDifferFlavour for MysqlFlavour {
         false
     }
 
+    fn compares_on_update_current_timestamp(&self) -> bool {
+        true
+    }
+
     fn column_type_change(&self, differ: MigrationPair<TableColumnWalker<'_>>) -> Option<ColumnTypeChange> {
         // On MariaDB, JSON is an alias for LONGTEXT. https://mariadb.com/kb/en/json-data-type/
         if self.is_mariadb() {
@@ -72,6 +99,20 @@ impl SqlSchemaDifferFlavour for MysqlFlavour {
         None
     }
 
+    fn column_type_change_is_online(&self, differ: MigrationPair<TableColumnWalker<'_>>) -> bool {
+        // MySQL's InnoDB can widen a `VARCHAR`'s length in place (`ALGORITHM=INPLACE`, no table
+        // rebuild) as long as the number of bytes needed to store the length prefix doesn't
+        // change (1 byte for up to 255 bytes, 2 bytes beyond that). Everything else, including
+        // widening an integer type, rebuilds the whole table (`ALGORITHM=COPY`).
+        // https://dev.mysql.com/doc/refman/8.0/en/innodb-online-ddl-operations.html
+        match (differ.previous.column_native_type(), differ.next.column_native_type()) {
+            (Some(MySqlType::VarChar(prev)), Some(MySqlType::VarChar(next))) => {
+                (*prev <= 255) == (*next <= 255) && next >= prev
+            }
+            _ => false,
+        }
+    }
+
     fn index_should_be_renamed(&self, indexes: MigrationPair<IndexWalker<'_>>) -> bool {
         // Implements correct comparison for truncated index names.
         let (previous_name, next_name) = indexes.as_ref().map(|idx| idx.name()).into_tuple();
@@ -79,6 +120,10 @@ impl SqlSchemaDifferFlavour for MysqlFlavour {
         previous_name != next_name
     }
 
+    fn index_column_lengths_match(&self, a: IndexColumnWalker<'_>, b: IndexColumnWalker<'_>) -> bool {
+        normalized_prefix_length(a) == normalized_prefix_length(b)
+    }
+
     fn lower_cases_table_names(&self) -> bool {
         self.lower_cases_table_names()
     }
@@ -102,6 +147,41 @@ impl SqlSchemaDifferFlavour for MysqlFlavour {
             names.previous == names.next
         }
     }
+
+    fn push_table_collation_changes(
+        &self,
+        table: &crate::sql_schema_differ::TableDiffer<'_, '_>,
+        changes: &mut Vec<crate::sql_migration::TableChange>,
+    ) {
+        let collations = table.tables.map(|t| t.default_collation());
+
+        // An implicit default (introspected as `None`) is not a change on its own; we only have
+        // the collation to emit a statement when the next schema names one explicitly.
+        if let Some(next) = collations.next {
+            if collations.previous != Some(next) {
+                changes.push(crate::sql_migration::TableChange::AlterTableCollation {
+                    collation: next.to_owned(),
+                });
+            }
+        }
+    }
+
+    fn supports_multiple_alter_table_clauses(&self) -> bool {
+        true
+    }
+}
+
+/// A prefix length equal to the full length of the indexed column is equivalent to indexing the
+/// column without a prefix at all, so normalize it away before comparing two index columns.
+fn normalized_prefix_length(column: IndexColumnWalker<'_>) -> Option<u32> {
+    let length = column.length()?;
+
+    match column.as_column().column_native_type::<MySqlType>() {
+        Some(
+            MySqlType::Char(full) | MySqlType::VarChar(full) | MySqlType::Binary(full) | MySqlType::VarBinary(full),
+        ) if *full == length => None,
+        _ => Some(length),
+    }
 }
 
 fn risky() -> ColumnTypeChange {
@@ -1371,3 +1451,65 @@ fn native_type_change(types: MigrationPair<&MySqlType>) -> Option<ColumnTypeChan
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use psl::datamodel_connector::NativeTypeInstance;
+    use sql_schema_describer::{Column, ColumnArity, ColumnType, SqlSchema, TableColumnId};
+
+    fn column_schema(native_type: MySqlType, family: ColumnTypeFamily) -> (SqlSchema, TableColumnId) {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("A".to_owned(), Default::default(), None);
+        let column_id = schema.push_table_column(
+            table_id,
+            Column {
+                name: "a".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: String::new(),
+                    family,
+                    arity: ColumnArity::Required,
+                    native_type: Some(NativeTypeInstance::new(native_type)),
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        (schema, column_id)
+    }
+
+    #[test]
+    fn increasing_a_varchar_length_within_the_same_length_class_is_online() {
+        let (previous, previous_id) = column_schema(MySqlType::VarChar(20), ColumnTypeFamily::String);
+        let (next, next_id) = column_schema(MySqlType::VarChar(40), ColumnTypeFamily::String);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(MysqlFlavour::default().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn increasing_a_varchar_length_past_the_255_byte_boundary_is_not_online() {
+        let (previous, previous_id) = column_schema(MySqlType::VarChar(100), ColumnTypeFamily::String);
+        let (next, next_id) = column_schema(MySqlType::VarChar(300), ColumnTypeFamily::String);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(!MysqlFlavour::default().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn widening_an_integer_is_not_online() {
+        let (previous, previous_id) = column_schema(MySqlType::Int, ColumnTypeFamily::Int);
+        let (next, next_id) = column_schema(MySqlType::BigInt, ColumnTypeFamily::BigInt);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(!MysqlFlavour::default().column_type_change_is_online(columns));
+    }
+}
@@ -4,8 +4,9 @@ use crate::{
     flavour::PostgresFlavour,
     migration_pair::MigrationPair,
     sql_migration::{
-        AlterEnum, AlterExtension, CreateExtension, DropExtension, ExtensionChange, SequenceChange, SequenceChanges,
-        SqlMigrationStep,
+        AlterDomain, AlterEnum, AlterExtension, AlterPolicy, CreateDomain, CreateExtension, CreatePolicy, CreateTrigger,
+        DomainChange, DropDomain, DropExtension, DropPolicy, DropTrigger, ExtensionChange, PolicyChange,
+        SequenceChange, SequenceChanges, SqlMigrationStep, TableChange,
     },
     sql_schema_differ::{column::ColumnTypeChange, differ_database::DifferDatabase},
 };
@@ -15,7 +16,7 @@ use psl::builtin_connectors::{CockroachType, PostgresType};
 use regex::RegexSet;
 use sql_schema_describer::{
     postgres::PostgresSchemaExt,
-    walkers::{IndexWalker, TableColumnWalker},
+    walkers::{IndexWalker, TableColumnWalker, TableWalker},
 };
 
 /// These can be tables or views, depending on the PostGIS version. In both cases, they should be ignored.
@@ -71,6 +72,63 @@ impl SqlSchemaDifferFlavour for PostgresFlavour {
         }
     }
 
+    fn varchar_length_change(
+        &self,
+        columns: MigrationPair<TableColumnWalker<'_>>,
+    ) -> Option<super::VarcharLengthChange> {
+        use psl::builtin_connectors::PostgresType::*;
+        use super::VarcharLengthChange;
+
+        match (columns.previous.column_native_type(), columns.next.column_native_type()) {
+            (Some(VarChar(Some(prev))), Some(VarChar(Some(next))))
+            | (Some(Char(Some(prev))), Some(Char(Some(next)))) => {
+                if next > prev {
+                    Some(VarcharLengthChange::Increase)
+                } else if next < prev {
+                    Some(VarcharLengthChange::Decrease)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn column_type_change_is_online(&self, columns: MigrationPair<TableColumnWalker<'_>>) -> bool {
+        // CockroachDB performs schema changes, including column type changes, through a
+        // background schema change job that backfills and validates the new representation
+        // without taking the table offline, so any cast it can perform at all is online.
+        // https://www.cockroachlabs.com/docs/stable/online-schema-changes
+        if self.is_cockroachdb() {
+            return !matches!(self.column_type_change(columns), Some(ColumnTypeChange::NotCastable));
+        }
+
+        // Vanilla Postgres takes an `ACCESS EXCLUSIVE` lock and rewrites every row for most type
+        // changes, including casts that lose no data, like widening a `smallint` to an `integer`.
+        // The exceptions are a handful of changes it recognizes as not requiring a rewrite at
+        // all: increasing (or removing the limit on) a `varchar`/`char` length, and increasing a
+        // `numeric`'s precision while keeping its scale unchanged.
+        let previous_type: Option<&PostgresType> = columns.previous.column_native_type();
+        let next_type: Option<&PostgresType> = columns.next.column_native_type();
+
+        matches!(
+            (previous_type, next_type),
+            (Some(PostgresType::VarChar(Some(prev))), Some(PostgresType::VarChar(Some(next)))) if next >= prev
+        ) || matches!(
+            (previous_type, next_type),
+            (Some(PostgresType::VarChar(Some(_))), Some(PostgresType::VarChar(None)))
+        ) || matches!(
+            (previous_type, next_type),
+            (Some(PostgresType::Char(Some(prev))), Some(PostgresType::Char(Some(next)))) if next >= prev
+        ) || matches!(
+            (previous_type, next_type),
+            (
+                Some(PostgresType::Decimal(Some((prev_precision, prev_scale)))),
+                Some(PostgresType::Decimal(Some((next_precision, next_scale)))),
+            ) if next_precision >= prev_precision && next_scale == prev_scale
+        )
+    }
+
     fn push_enum_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
         for enum_differ in db.enum_pairs() {
             let mut alter_enum = AlterEnum {
@@ -97,11 +155,16 @@ impl SqlSchemaDifferFlavour for PostgresFlavour {
         }
     }
 
+    // Gated to Postgres-family connectors simply by being overridden here; every other flavour
+    // keeps the empty default and never reaches this comparison.
+    //
+    // Only columns with a `nextval('seq'::regclass)` default (`SERIAL`/`BIGSERIAL`, or
+    // CockroachDB's `GENERATED BY DEFAULT AS IDENTITY`, which still owns a real sequence under
+    // the hood) are covered here. A genuine Postgres `GENERATED ... AS IDENTITY` column has no
+    // default expression to resolve a sequence name from, so its sequence options aren't tracked
+    // by this loop; the describer would need to additionally record the owned sequence for those
+    // columns (e.g. via `pg_get_serial_sequence`) before they could be diffed the same way.
     fn push_alter_sequence_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
-        if !self.is_cockroachdb() {
-            return;
-        }
-
         let schemas: MigrationPair<(&SqlDatabaseSchema, &PostgresSchemaExt)> = db
             .schemas
             .map(|schema| (schema, schema.describer_schema.downcast_connector_data()));
@@ -157,6 +220,22 @@ impl SqlSchemaDifferFlavour for PostgresFlavour {
         }
     }
 
+    fn compares_index_column_collation(&self) -> bool {
+        true
+    }
+
+    fn compares_generated_column_storage(&self) -> bool {
+        true
+    }
+
+    fn compares_column_storage(&self) -> bool {
+        true
+    }
+
+    fn compares_not_null_constraint_names(&self) -> bool {
+        true
+    }
+
     fn indexes_match(&self, a: IndexWalker<'_>, b: IndexWalker<'_>) -> bool {
         let columns_previous = a.columns();
         let columns_next = b.columns();
@@ -187,6 +266,10 @@ impl SqlSchemaDifferFlavour for PostgresFlavour {
         true
     }
 
+    fn supports_filtered_indexes(&self) -> bool {
+        true
+    }
+
     fn index_should_be_renamed(&self, pair: MigrationPair<IndexWalker<'_>>) -> bool {
         // Implements correct comparison for truncated index names.
         let (previous_name, next_name) = pair.map(|idx| idx.name()).into_tuple();
@@ -273,6 +356,212 @@ impl SqlSchemaDifferFlavour for PostgresFlavour {
         }
     }
 
+    fn push_domain_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+        for domain in db.dropped_domains() {
+            steps.push(SqlMigrationStep::DropDomain(DropDomain { id: domain.id }));
+        }
+
+        for domains in db.domain_pairs() {
+            let mut changes = Vec::new();
+
+            if domains.previous.base_type() != domains.next.base_type() {
+                changes.push(DomainChange::AlterBaseType);
+            }
+
+            if domains.previous.not_null() != domains.next.not_null() {
+                changes.push(DomainChange::AlterNotNull);
+            }
+
+            if domains.previous.default() != domains.next.default() {
+                changes.push(DomainChange::AlterDefault);
+            }
+
+            if domains.previous.check() != domains.next.check() {
+                changes.push(DomainChange::AlterCheck);
+            }
+
+            if !changes.is_empty() {
+                steps.push(SqlMigrationStep::AlterDomain(AlterDomain {
+                    ids: MigrationPair::new(domains.previous.id, domains.next.id),
+                    changes,
+                }));
+            }
+        }
+
+        for domain in db.created_domains() {
+            steps.push(SqlMigrationStep::CreateDomain(CreateDomain { id: domain.id }));
+        }
+    }
+
+    fn push_trigger_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+        for trigger in db.dropped_triggers() {
+            steps.push(SqlMigrationStep::DropTrigger(DropTrigger { id: trigger.id }));
+        }
+
+        for triggers in db.trigger_pairs() {
+            if triggers.previous.timing() != triggers.next.timing()
+                || triggers.previous.event() != triggers.next.event()
+                || triggers.previous.definition() != triggers.next.definition()
+            {
+                steps.push(SqlMigrationStep::DropTrigger(DropTrigger {
+                    id: triggers.previous.id,
+                }));
+                steps.push(SqlMigrationStep::CreateTrigger(CreateTrigger { id: triggers.next.id }));
+            }
+        }
+
+        for trigger in db.created_triggers() {
+            steps.push(SqlMigrationStep::CreateTrigger(CreateTrigger { id: trigger.id }));
+        }
+    }
+
+    fn push_policy_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+        for table in db.created_tables() {
+            let ext: &PostgresSchemaExt = table.schema.downcast_connector_data();
+
+            if table.has_row_level_security() {
+                steps.push(SqlMigrationStep::EnableRowLevelSecurity { table_id: table.id });
+            }
+
+            for (id, _) in ext.table_policies(table.id) {
+                steps.push(SqlMigrationStep::CreatePolicy(CreatePolicy { id }));
+            }
+        }
+
+        for tables in db.table_pairs() {
+            let table_ids = tables.table_ids();
+            let exts: MigrationPair<&PostgresSchemaExt> = tables
+                .db
+                .schemas
+                .map(|schema| schema.describer_schema.downcast_connector_data());
+
+            match tables.tables.map(|t| t.has_row_level_security()).into_tuple() {
+                (false, true) => steps.push(SqlMigrationStep::EnableRowLevelSecurity {
+                    table_id: table_ids.next,
+                }),
+                (true, false) => steps.push(SqlMigrationStep::DisableRowLevelSecurity {
+                    table_id: table_ids.previous,
+                }),
+                _ => (),
+            }
+
+            let previous_policies: Vec<_> = exts.previous.table_policies(table_ids.previous).collect();
+            let next_policies: Vec<_> = exts.next.table_policies(table_ids.next).collect();
+
+            for (previous_id, previous_policy) in &previous_policies {
+                let matching_next = next_policies
+                    .iter()
+                    .find(|(_, next_policy)| next_policy.name == previous_policy.name);
+
+                let (next_id, next_policy) = match matching_next {
+                    Some(pair) => pair,
+                    None => {
+                        steps.push(SqlMigrationStep::DropPolicy(DropPolicy { id: *previous_id }));
+                        continue;
+                    }
+                };
+
+                // `permissive` and `command` have no `ALTER POLICY` equivalent, so a change to
+                // either one is modeled as a drop followed by a recreate.
+                if previous_policy.permissive != next_policy.permissive || previous_policy.command != next_policy.command
+                {
+                    steps.push(SqlMigrationStep::DropPolicy(DropPolicy { id: *previous_id }));
+                    steps.push(SqlMigrationStep::CreatePolicy(CreatePolicy { id: *next_id }));
+                    continue;
+                }
+
+                let mut changes = Vec::new();
+
+                if previous_policy.roles != next_policy.roles {
+                    changes.push(PolicyChange::Roles);
+                }
+
+                if previous_policy.using != next_policy.using {
+                    changes.push(PolicyChange::Using);
+                }
+
+                if previous_policy.with_check != next_policy.with_check {
+                    changes.push(PolicyChange::WithCheck);
+                }
+
+                if !changes.is_empty() {
+                    steps.push(SqlMigrationStep::AlterPolicy(AlterPolicy {
+                        ids: MigrationPair::new(*previous_id, *next_id),
+                        changes,
+                    }));
+                }
+            }
+
+            for (next_id, next_policy) in &next_policies {
+                let existed_before = previous_policies
+                    .iter()
+                    .any(|(_, previous_policy)| previous_policy.name == next_policy.name);
+
+                if !existed_before {
+                    steps.push(SqlMigrationStep::CreatePolicy(CreatePolicy { id: *next_id }));
+                }
+            }
+        }
+    }
+
+    fn push_table_inheritance_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+        for table in db.created_tables() {
+            let ext: &PostgresSchemaExt = table.schema.downcast_connector_data();
+
+            for parent_table_id in ext.parent_tables(table.id) {
+                steps.push(SqlMigrationStep::AddTableInheritance {
+                    table_id: table.id,
+                    parent_table_id,
+                });
+            }
+        }
+
+        for tables in db.table_pairs() {
+            let table_ids = tables.table_ids();
+            let exts: MigrationPair<&PostgresSchemaExt> = tables
+                .db
+                .schemas
+                .map(|schema| schema.describer_schema.downcast_connector_data());
+
+            let previous_parents: Vec<TableWalker<'_>> = exts
+                .previous
+                .parent_tables(table_ids.previous)
+                .map(|id| tables.db.schemas.previous.describer_schema.walk(id))
+                .collect();
+            let next_parents: Vec<TableWalker<'_>> = exts
+                .next
+                .parent_tables(table_ids.next)
+                .map(|id| tables.db.schemas.next.describer_schema.walk(id))
+                .collect();
+
+            for previous_parent in &previous_parents {
+                let still_a_parent = next_parents
+                    .iter()
+                    .any(|next_parent| tables_match(*previous_parent, *next_parent));
+
+                if !still_a_parent {
+                    steps.push(SqlMigrationStep::DropTableInheritance {
+                        table_id: table_ids.previous,
+                        parent_table_id: previous_parent.id,
+                    });
+                }
+            }
+
+            for next_parent in &next_parents {
+                let was_already_a_parent = previous_parents
+                    .iter()
+                    .any(|previous_parent| tables_match(*previous_parent, *next_parent));
+
+                if !was_already_a_parent {
+                    steps.push(SqlMigrationStep::AddTableInheritance {
+                        table_id: table_ids.next,
+                        parent_table_id: next_parent.id,
+                    });
+                }
+            }
+        }
+    }
+
     fn define_extensions(&self, db: &mut DifferDatabase<'_>) {
         let schemas: MigrationPair<(&SqlDatabaseSchema, &PostgresSchemaExt)> = db
             .schemas
@@ -288,6 +577,101 @@ impl SqlSchemaDifferFlavour for PostgresFlavour {
             entry.next = Some(extension.id);
         }
     }
+
+    fn push_exclusion_constraint_changes(
+        &self,
+        table: &crate::sql_schema_differ::TableDiffer<'_, '_>,
+        changes: &mut Vec<TableChange>,
+    ) {
+        let exts: MigrationPair<&PostgresSchemaExt> = table
+            .db
+            .schemas
+            .map(|schema| schema.describer_schema.downcast_connector_data());
+        let table_ids = table.tables.map(|t| t.id);
+
+        let previous: Vec<(&str, &str)> = exts.previous.exclude_constraints_with_definitions(table_ids.previous).collect();
+        let next: Vec<(&str, &str)> = exts.next.exclude_constraints_with_definitions(table_ids.next).collect();
+
+        for (name, definition) in &previous {
+            if !next.iter().any(|(next_name, next_def)| next_name == name && next_def == definition) {
+                changes.push(TableChange::DropExclusionConstraint {
+                    constraint_name: name.to_string(),
+                });
+            }
+        }
+
+        for (name, definition) in &next {
+            if !previous
+                .iter()
+                .any(|(prev_name, prev_def)| prev_name == name && prev_def == definition)
+            {
+                changes.push(TableChange::AddExclusionConstraint {
+                    constraint_name: name.to_string(),
+                    definition: definition.to_string(),
+                });
+            }
+        }
+    }
+
+    fn push_table_persistence_changes(
+        &self,
+        table: &crate::sql_schema_differ::TableDiffer<'_, '_>,
+        changes: &mut Vec<TableChange>,
+    ) {
+        let unlogged = table.tables.map(|t| t.is_unlogged());
+
+        if unlogged.previous != unlogged.next {
+            changes.push(TableChange::AlterTablePersistence {
+                unlogged: unlogged.next,
+            });
+        }
+    }
+
+    fn push_table_tablespace_changes(
+        &self,
+        table: &crate::sql_schema_differ::TableDiffer<'_, '_>,
+        changes: &mut Vec<TableChange>,
+    ) {
+        let tablespace = table.tables.map(|t| t.tablespace());
+
+        if tablespace.previous != tablespace.next {
+            // Moving back to the database's default tablespace (`None`) isn't introspected as a
+            // distinguishable name, so there is nothing we could render `SET TABLESPACE` with;
+            // only an actual named tablespace produces a change.
+            if let Some(next) = tablespace.next {
+                changes.push(TableChange::AlterTableTablespace {
+                    tablespace: next.to_owned(),
+                });
+            }
+        }
+    }
+
+    fn compares_index_tablespaces(&self) -> bool {
+        true
+    }
+
+    fn compares_foreign_key_match_types(&self) -> bool {
+        true
+    }
+
+    fn supports_concurrent_index_creation(&self) -> bool {
+        true
+    }
+
+    fn supports_multiple_alter_table_clauses(&self) -> bool {
+        true
+    }
+
+    fn supports_object_comments(&self) -> bool {
+        true
+    }
+}
+
+/// Whether `a` and `b` are the same table, by namespace and name rather than by id — used to pair
+/// up a table's parents across the previous and next schema, since a `TableId` is only meaningful
+/// within the schema it was walked from.
+fn tables_match(a: TableWalker<'_>, b: TableWalker<'_>) -> bool {
+    a.namespace() == b.namespace() && a.name() == b.name()
 }
 
 fn cockroach_column_type_change(columns: MigrationPair<TableColumnWalker<'_>>) -> Option<ColumnTypeChange> {
@@ -577,7 +961,10 @@ fn postgres_native_type_change_riskyness(previous: &PostgresType, next: &Postgre
                 Char(Some(len)) | VarChar(Some(len)) if *len > 22 => SafeCast,
                 PostgresType::Timestamp(None) => return None,
                 PostgresType::Timestamp(Some(b)) if a.is_none() || *a == Some(*b) => return None,
-                Timestamp(_) | Timestamptz(_) | Date | Time(_) | Timetz(_) => SafeCast,
+                // Reinterprets every value according to the session time zone, so it's not a
+                // no-op cast even though no bytes on disk need to change.
+                Timestamptz(_) => RiskyCast,
+                Timestamp(_) | Date | Time(_) | Timetz(_) => SafeCast,
                 _ => NotCastable,
             },
             Timestamptz(a) => match next {
@@ -585,7 +972,10 @@ fn postgres_native_type_change_riskyness(previous: &PostgresType, next: &Postgre
                 Char(Some(len)) | VarChar(Some(len)) if *len > 27 => SafeCast,
                 PostgresType::Timestamptz(None) => return None,
                 PostgresType::Timestamptz(Some(b)) if a.is_none() || *a == Some(*b) => return None,
-                Timestamp(_) | Timestamptz(_) | Date | Time(_) | Timetz(_) => SafeCast,
+                // Reinterprets every value according to the session time zone, so it's not a
+                // no-op cast even though no bytes on disk need to change.
+                Timestamp(_) => RiskyCast,
+                Timestamptz(_) | Date | Time(_) | Timetz(_) => SafeCast,
                 _ => NotCastable,
             },
             Date => match next {
@@ -671,6 +1061,78 @@ fn postgres_native_type_change_riskyness(previous: &PostgresType, next: &Postgre
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use psl::datamodel_connector::NativeTypeInstance;
+    use sql_schema_describer::{Column, ColumnArity, ColumnType, ColumnTypeFamily, SqlSchema, TableColumnId};
+
+    fn column_schema(native_type: PostgresType, family: ColumnTypeFamily) -> (SqlSchema, TableColumnId) {
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("A".to_owned(), Default::default(), None);
+        let column_id = schema.push_table_column(
+            table_id,
+            Column {
+                name: "a".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: String::new(),
+                    family,
+                    arity: ColumnArity::Required,
+                    native_type: Some(NativeTypeInstance::new(native_type)),
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        (schema, column_id)
+    }
+
+    #[test]
+    fn increasing_a_varchar_length_is_online() {
+        let (previous, previous_id) = column_schema(PostgresType::VarChar(Some(20)), ColumnTypeFamily::String);
+        let (next, next_id) = column_schema(PostgresType::VarChar(Some(40)), ColumnTypeFamily::String);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(PostgresFlavour::default().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn shrinking_a_varchar_length_is_not_online() {
+        let (previous, previous_id) = column_schema(PostgresType::VarChar(Some(40)), ColumnTypeFamily::String);
+        let (next, next_id) = column_schema(PostgresType::VarChar(Some(20)), ColumnTypeFamily::String);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(!PostgresFlavour::default().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn widening_an_integer_is_not_online_on_vanilla_postgres() {
+        let (previous, previous_id) = column_schema(PostgresType::SmallInt, ColumnTypeFamily::Int);
+        let (next, next_id) = column_schema(PostgresType::Integer, ColumnTypeFamily::Int);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(!PostgresFlavour::new_postgres().column_type_change_is_online(columns));
+    }
+
+    #[test]
+    fn widening_an_integer_is_online_on_cockroachdb() {
+        let (previous, previous_id) = column_schema(PostgresType::SmallInt, ColumnTypeFamily::Int);
+        let (next, next_id) = column_schema(PostgresType::Integer, ColumnTypeFamily::Int);
+
+        let columns = MigrationPair::new(previous.walk(previous_id), next.walk(next_id));
+
+        assert!(PostgresFlavour::new_cockroach().column_type_change_is_online(columns));
+    }
+}
+
 fn push_alter_enum_previous_usages_as_default(db: &DifferDatabase<'_>, alter_enum: &mut AlterEnum) {
     let mut previous_usages_as_default: Vec<(_, Option<_>)> = Vec::new();
 
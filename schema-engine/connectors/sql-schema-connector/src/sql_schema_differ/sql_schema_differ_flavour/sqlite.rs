@@ -1,11 +1,40 @@
 use super::SqlSchemaDifferFlavour;
 use crate::{
-    flavour::SqliteFlavour, migration_pair::MigrationPair, sql_schema_differ::column::ColumnTypeChange,
+    flavour::SqliteFlavour,
+    migration_pair::MigrationPair,
+    sql_migration::{CreateTrigger, DropTrigger, SqlMigrationStep},
+    sql_schema_differ::column::ColumnTypeChange,
     sql_schema_differ::differ_database::DifferDatabase,
 };
-use sql_schema_describer::{walkers::TableColumnWalker, ColumnTypeFamily};
+use sql_schema_describer::{sqlite::SqliteSchemaExt, walkers::TableColumnWalker, ColumnTypeFamily};
+use versions::Versioning;
+
+/// `ALTER TABLE ... RENAME COLUMN` was added in this SQLite version.
+const MIN_RENAME_COLUMN_VERSION: &str = "3.25.0";
 
 impl SqlSchemaDifferFlavour for SqliteFlavour {
+    fn push_trigger_steps(&self, steps: &mut Vec<SqlMigrationStep>, db: &DifferDatabase<'_>) {
+        for trigger in db.dropped_triggers() {
+            steps.push(SqlMigrationStep::DropTrigger(DropTrigger { id: trigger.id }));
+        }
+
+        for triggers in db.trigger_pairs() {
+            if triggers.previous.timing() != triggers.next.timing()
+                || triggers.previous.event() != triggers.next.event()
+                || triggers.previous.definition() != triggers.next.definition()
+            {
+                steps.push(SqlMigrationStep::DropTrigger(DropTrigger {
+                    id: triggers.previous.id,
+                }));
+                steps.push(SqlMigrationStep::CreateTrigger(CreateTrigger { id: triggers.next.id }));
+            }
+        }
+
+        for trigger in db.created_triggers() {
+            steps.push(SqlMigrationStep::CreateTrigger(CreateTrigger { id: trigger.id }));
+        }
+    }
+
     fn can_rename_foreign_key(&self) -> bool {
         false
     }
@@ -18,8 +47,23 @@ impl SqlSchemaDifferFlavour for SqliteFlavour {
         false
     }
 
-    fn column_autoincrement_changed(&self, _columns: MigrationPair<TableColumnWalker<'_>>) -> bool {
-        false
+    fn can_rename_column(&self) -> bool {
+        supports_native_rename_column(quaint::connector::sqlite_version())
+    }
+
+    fn column_autoincrement_changed(&self, columns: MigrationPair<TableColumnWalker<'_>>) -> bool {
+        // The rowid-alias-ness of an `INTEGER PRIMARY KEY` column (`Column::auto_increment`) is
+        // entirely determined by the table's primary key shape, so it can't differ between two
+        // schemas without the primary key itself already triggering a redefine. Whether the
+        // `CREATE TABLE` text literally spells out `AUTOINCREMENT`, tracked separately in
+        // `SqliteSchemaExt`, can change on its own (e.g. a migration adds or removes the
+        // keyword without touching the primary key), and SQLite has no `ALTER TABLE` for it.
+        let has_autoincrement = |column: TableColumnWalker<'_>| {
+            let ext: &SqliteSchemaExt = column.schema.downcast_connector_data();
+            ext.column_has_autoincrement(column.id)
+        };
+
+        has_autoincrement(columns.previous) != has_autoincrement(columns.next)
     }
 
     fn column_type_change(&self, differ: MigrationPair<TableColumnWalker<'_>>) -> Option<ColumnTypeChange> {
@@ -30,6 +74,13 @@ impl SqlSchemaDifferFlavour for SqliteFlavour {
         }
     }
 
+    // SQLite has no `ALTER TABLE` syntax for changing a column's type: any type change already
+    // forces a full table redefinition (see `column_type_change` above and `any_column_changed`
+    // in `set_tables_to_redefine` below), so none of them can be applied online.
+    fn column_type_change_is_online(&self, _columns: MigrationPair<TableColumnWalker<'_>>) -> bool {
+        false
+    }
+
     fn should_drop_indexes_from_dropped_tables(&self) -> bool {
         true
     }
@@ -46,6 +97,8 @@ impl SqlSchemaDifferFlavour for SqliteFlavour {
                     || differ.any_column_changed()
                     || differ.created_foreign_keys().next().is_some()
                     || differ.dropped_foreign_keys().next().is_some()
+                    || differ.checks_changed()
+                    || table_without_rowid_changed(differ.tables)
             })
             .map(|table| table.table_ids())
             .collect();
@@ -63,3 +116,141 @@ impl SqlSchemaDifferFlavour for SqliteFlavour {
         true
     }
 }
+
+/// Whether a table pair toggled the `WITHOUT ROWID` table option. SQLite has no `ALTER TABLE`
+/// syntax for this, so any change here has to go through a full table redefinition.
+fn table_without_rowid_changed(tables: MigrationPair<sql_schema_describer::walkers::TableWalker<'_>>) -> bool {
+    let is_without_rowid = |table: sql_schema_describer::walkers::TableWalker<'_>| {
+        let ext: &SqliteSchemaExt = table.schema.downcast_connector_data();
+        ext.table_is_without_rowid(table.id)
+    };
+
+    is_without_rowid(tables.previous) != is_without_rowid(tables.next)
+}
+
+fn supports_native_rename_column(sqlite_version: &str) -> bool {
+    let version = Versioning::new(sqlite_version).unwrap_or_default();
+    let min_version = Versioning::new(MIN_RENAME_COLUMN_VERSION).unwrap();
+
+    version >= min_version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sql_schema_describer::TableColumnId;
+
+    #[test]
+    fn old_sqlite_versions_fall_back_to_redefine() {
+        assert!(!supports_native_rename_column("3.24.0"));
+    }
+
+    #[test]
+    fn sqlite_3_25_and_later_can_rename_columns_natively() {
+        assert!(supports_native_rename_column("3.25.0"));
+        assert!(supports_native_rename_column("3.40.1"));
+    }
+
+    fn int_pk_column_schema(has_autoincrement_keyword: bool) -> (sql_schema_describer::SqlSchema, TableColumnId) {
+        use sql_schema_describer::{Column, ColumnArity, ColumnType, ColumnTypeFamily, SqlSchema};
+
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("Chicken".to_owned(), Default::default(), None);
+        let column_id = schema.push_table_column(
+            table_id,
+            Column {
+                name: "id".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: "INTEGER".to_owned(),
+                    family: ColumnTypeFamily::Int,
+                    arity: ColumnArity::Required,
+                    native_type: None,
+                },
+                auto_increment: true,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        let mut sqlite_ext = SqliteSchemaExt::default();
+        if has_autoincrement_keyword {
+            sqlite_ext.autoincrement_columns.insert(column_id);
+        }
+        schema.set_connector_data(Box::new(sqlite_ext));
+
+        (schema, column_id)
+    }
+
+    #[test]
+    fn column_autoincrement_changed_detects_a_toggled_autoincrement_keyword() {
+        let (without_keyword, without_keyword_column_id) = int_pk_column_schema(false);
+        let (with_keyword, with_keyword_column_id) = int_pk_column_schema(true);
+
+        let previous = without_keyword.walk(without_keyword_column_id);
+        let next = with_keyword.walk(with_keyword_column_id);
+
+        assert!(SqliteFlavour::default().column_autoincrement_changed(MigrationPair::new(previous, next)));
+        assert!(!SqliteFlavour::default().column_autoincrement_changed(MigrationPair::new(previous, previous)));
+        assert!(!SqliteFlavour::default().column_autoincrement_changed(MigrationPair::new(next, next)));
+    }
+
+    fn text_pk_table_schema(is_without_rowid: bool) -> (sql_schema_describer::SqlSchema, sql_schema_describer::TableId) {
+        use sql_schema_describer::{Column, ColumnArity, ColumnType, ColumnTypeFamily, SqlSchema};
+
+        let mut schema = SqlSchema::default();
+        let table_id = schema.push_table("Chicken".to_owned(), Default::default(), None);
+        // WITHOUT ROWID tables are most useful with a non-integer primary key: a single-column
+        // `INTEGER PRIMARY KEY` is already a rowid alias and gets most of the same benefit for
+        // free on an ordinary rowid table.
+        schema.push_table_column(
+            table_id,
+            Column {
+                name: "id".to_owned(),
+                tpe: ColumnType {
+                    full_data_type: "TEXT".to_owned(),
+                    family: ColumnTypeFamily::String,
+                    arity: ColumnArity::Required,
+                    native_type: None,
+                },
+                auto_increment: false,
+                description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
+            },
+        );
+
+        let mut sqlite_ext = SqliteSchemaExt::default();
+        if is_without_rowid {
+            sqlite_ext.without_rowid_tables.insert(table_id);
+        }
+        schema.set_connector_data(Box::new(sqlite_ext));
+
+        (schema, table_id)
+    }
+
+    #[test]
+    fn table_without_rowid_changed_detects_a_toggled_without_rowid_option() {
+        let (rowid, rowid_table_id) = text_pk_table_schema(false);
+        let (without_rowid, without_rowid_table_id) = text_pk_table_schema(true);
+
+        let previous = rowid.walk(rowid_table_id);
+        let next = without_rowid.walk(without_rowid_table_id);
+
+        assert!(table_without_rowid_changed(MigrationPair::new(previous, next)));
+        assert!(!table_without_rowid_changed(MigrationPair::new(previous, previous)));
+        assert!(!table_without_rowid_changed(MigrationPair::new(next, next)));
+    }
+
+    #[test]
+    fn column_type_change_is_never_online() {
+        let (schema, column_id) = int_pk_column_schema(false);
+        let column = schema.walk(column_id);
+
+        assert!(!SqliteFlavour::default().column_type_change_is_online(MigrationPair::new(column, column)));
+    }
+}
@@ -106,6 +106,21 @@ impl<'schema, 'b> TableDiffer<'schema, 'b> {
         })
     }
 
+    /// Did the set of named check constraints change? Only ever true today when `next` actually
+    /// carries check constraints of its own, because the calculated ("next") schema never gets any
+    /// — there is no Prisma schema attribute to declare one — so this stays dormant rather than
+    /// tripping on every migration for tables that merely have pre-existing, introspected checks.
+    pub(crate) fn checks_changed(&self) -> bool {
+        if !self.tables.next.has_check_constraints() {
+            return false;
+        }
+
+        let previous: HashSet<&str> = self.tables.previous.check_constraints().collect();
+        let next: HashSet<&str> = self.tables.next.check_constraints().collect();
+
+        previous != next
+    }
+
     pub(crate) fn primary_key_changed(&self) -> bool {
         match self.tables.as_ref().map(|t| t.primary_key()).into_tuple() {
             (Some(previous_pk), Some(next_pk)) => {
@@ -195,11 +210,13 @@ fn indexes_match(first: IndexWalker<'_>, second: IndexWalker<'_>, flavour: &dyn
     left_cols.len() == right_cols.len()
         && left_cols.zip(right_cols).all(|(a, b)| {
             let names_match = a.as_column().name() == b.as_column().name();
-            let lengths_match = a.length() == b.length();
+            let lengths_match = flavour.index_column_lengths_match(a, b);
             let orders_match = a.sort_order().unwrap_or_default() == b.sort_order().unwrap_or_default();
+            let collations_match = !flavour.compares_index_column_collation() || a.collation() == b.collation();
 
-            names_match && lengths_match && orders_match
+            names_match && lengths_match && orders_match && collations_match
         })
         && first.index_type() == second.index_type()
+        && (!flavour.supports_filtered_indexes() || first.predicate() == second.predicate())
         && flavour.indexes_match(first, second)
 }
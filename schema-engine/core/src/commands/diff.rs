@@ -62,7 +62,9 @@ pub async fn diff(params: DiffParams, host: Arc<dyn ConnectorHost>) -> CoreResul
     let migration = connector.diff(from, to);
 
     if params.script {
-        let mut script_string = connector.render_script(&migration, &Default::default())?;
+        let emit_comments = params.comments.unwrap_or(true);
+        let mut script_string =
+            connector.render_script_with_options(&migration, &Default::default(), emit_comments)?;
         if !script_string.ends_with('\n') {
             script_string.push('\n');
         }
@@ -223,6 +223,50 @@ fn evaluate_data_loss_returns_warnings_for_the_local_database_for_the_next_migra
         .assert_steps_count(2);
 }
 
+// A table a destructive step targets may not exist yet on the database we inspect row counts
+// on, e.g. when the migrations directory has migrations that were never applied to it. Counting
+// rows on such a table used to error out the whole evaluation; it should instead fall back to a
+// generic, count-less warning for that step.
+#[test_connector]
+fn evaluate_data_loss_tolerates_dropping_a_table_that_was_never_applied(api: TestApi) {
+    let dm1 = api.datamodel_with_provider(
+        r#"
+        model Cat {
+            id Int @id
+            name String
+        }
+
+        model Dog {
+            id Int @id
+            name String
+        }
+    "#,
+    );
+
+    let directory = api.create_migrations_directory();
+    api.create_migration("1-initial", &dm1, &directory).send_sync();
+
+    let dm2 = api.datamodel_with_provider(
+        r#"
+        model Cat {
+            id Int @id
+            name String
+        }
+    "#,
+    );
+
+    let warn = format!(
+        "You are about to drop the `{}` table. If the table is not empty, all the data it contains will be lost.",
+        api.normalize_identifier("Dog")
+    );
+
+    api.evaluate_data_loss(&directory, dm2)
+        .send()
+        .assert_warnings(&[warn.into()])
+        .assert_unexecutable(&[])
+        .assert_steps_count(1);
+}
+
 #[test_connector(capabilities(Enums))]
 fn evaluate_data_loss_maps_warnings_to_the_right_steps(api: TestApi) {
     let dm1 = api.datamodel_with_provider(
@@ -348,3 +392,36 @@ fn evaluate_data_loss_multi_file_maps_warnings_to_the_right_steps(api: TestApi)
             ("Added the required column `isGoodDog` to the `Dog` table without a default value. There are 1 rows in this table, it is not possible to execute this step.".into(), if is_postgres { 2 } else { 1 }),
         ]);
 }
+
+// On SQLite, dropping every existing column of a table while adding at least one new column
+// leaves the table non-empty, so this must still be possible through the redefine mechanism.
+#[test_connector(tags(Sqlite))]
+fn dropping_all_columns_while_adding_a_new_one_is_fine_on_sqlite(api: TestApi) {
+    let dm1 = api.datamodel_with_provider(
+        r#"
+        model Cat {
+            id Int @id
+            name String
+        }
+    "#,
+    );
+
+    let directory = api.create_migrations_directory();
+
+    api.create_migration("initial", &dm1, &directory).send_sync();
+    api.apply_migrations(&directory).send_sync();
+
+    let dm2 = api.datamodel_with_provider(
+        r#"
+        model Cat {
+            id Int @id
+            age Int
+        }
+    "#,
+    );
+
+    api.evaluate_data_loss(&directory, dm2)
+        .send()
+        .assert_unexecutable(&[])
+        .assert_steps_count(1);
+}
@@ -731,11 +731,9 @@ fn enum_variants_can_be_dropped_without_data_loss(api: TestApi) {
         .force(true)
         .send();
 
-    if api.is_mysql() {
-        res.assert_warnings(&["The values [OUTRAGED] on the enum `Human_mood` will be removed. If these variants are still used in the database, this will fail.".into(), "The values [OUTRAGED] on the enum `Human_mood` will be removed. If these variants are still used in the database, this will fail.".into()]);
-    } else {
-        res.assert_warnings(&["The values [OUTRAGED] on the enum `Mood` will be removed. If these variants are still used in the database, this will fail.".into()]);
-    }
+    // OUTRAGED is not used by any row, so the data loss check finds it safe to remove and does
+    // not warn.
+    res.assert_warnings(&[]);
 
     // Assertions
     {
@@ -784,6 +782,65 @@ fn enum_variants_can_be_dropped_without_data_loss(api: TestApi) {
     }
 }
 
+// Excluding Vitess because schema changes being asynchronous messes with our assertions
+// (dump_table).
+#[test_connector(tags(Mysql, Postgres), exclude(Vitess))]
+fn dropping_an_enum_value_still_in_use_warns_about_the_rows_using_it(api: TestApi) {
+    let dm1 = r#"
+        model Cat {
+            id String @id
+            mood Mood
+        }
+
+        enum Mood {
+            OUTRAGED
+            HAPPY
+            HUNGRY
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm1)
+        .migration_id(Some("initial-setup"))
+        .send()
+        .assert_green();
+
+    {
+        let cat_inserts = quaint::ast::Insert::multi_into(api.render_table_name("Cat"), ["id", "mood"])
+            .values((Value::text("felix"), Value::enum_variant("OUTRAGED")));
+
+        api.query(cat_inserts.into());
+    }
+
+    let dm2 = r#"
+        model Cat {
+            id String @id
+            mood Mood
+        }
+
+        enum Mood {
+            HAPPY
+            HUNGRY
+        }
+    "#;
+
+    let res = api
+        .schema_push_w_datasource(dm2)
+        .migration_id(Some("drop-outraged-variant"))
+        .force(true)
+        .send();
+
+    let enm = if api.is_mysql() {
+        api.normalize_identifier("Cat_mood").into_owned()
+    } else {
+        "Mood".to_owned()
+    };
+
+    res.assert_warnings(&[format!(
+        "The values [OUTRAGED] on the enum `{enm}` will be removed, but are still used by: Cat.OUTRAGED (1 row(s)). This will fail unless those rows are migrated off the removed values first."
+    )
+    .into()]);
+}
+
 #[test_connector]
 fn set_default_current_timestamp_on_existing_column_works(api: TestApi) {
     let dm1 = r#"
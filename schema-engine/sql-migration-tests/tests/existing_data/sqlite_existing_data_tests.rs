@@ -2,6 +2,95 @@ use quaint::{prelude::Insert, ValueType};
 use sql_migration_tests::test_api::*;
 use sql_schema_describer::DefaultValue;
 
+#[test_connector(tags(Sqlite))]
+fn changing_a_column_from_optional_to_required_without_a_default_is_unexecutable(api: TestApi) {
+    let dm = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int?
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    let insert = Insert::multi_into(api.render_table_name("Test"), ["id", "age"])
+        .values(("a", 12))
+        .values(("b", ValueType::Int32(None)));
+
+    api.query(insert.into());
+
+    let dm2 = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int
+        }
+    "#;
+
+    // Without a default on the column and without a configured coalesce sentinel, the migration
+    // is reported as unexecutable rather than attempted: the data copy would fail on the `NULL`
+    // row even with `force`.
+    api.schema_push_w_datasource(dm2)
+        .force(true)
+        .send()
+        .assert_unexecutable(&["Made the column `age` on table `Test` required, but there are 1 existing NULL values.".to_owned()]);
+}
+
+// This reconfigures the test connector with a connection string carrying the
+// `not_null_coalesce_sentinel` parameter, which the default test engine does not set.
+#[test_connector(tags(Sqlite))]
+fn changing_a_column_from_optional_to_required_without_a_default_is_safe_with_a_coalesce_sentinel(mut api: TestApi) {
+    let dm = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int?
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    let insert = Insert::multi_into(api.render_table_name("Test"), ["id", "age"])
+        .values(("a", 12))
+        .values(("b", 22))
+        .values(("c", ValueType::Int32(None)));
+
+    api.query(insert.into());
+
+    let connection_string = format!("{}?not_null_coalesce_sentinel=0", api.connection_string());
+
+    api.connector
+        .set_params(schema_core::schema_connector::ConnectorParams {
+            connection_string,
+            preview_features: Default::default(),
+            shadow_database_connection_string: None,
+        })
+        .unwrap();
+
+    let dm2 = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm2).force(true).send().assert_green();
+
+    api.assert_schema()
+        .assert_table("Test", |table| table.assert_column("age", |column| column.assert_is_required()));
+
+    // Check that no data was lost, and that the NULL row fell back to the sentinel rather than
+    // failing the copy.
+    {
+        let data = api.dump_table("Test");
+        assert_eq!(data.len(), 3);
+        let ages: Vec<Option<i64>> = data
+            .into_iter()
+            .map(|row| row.get("age").unwrap().as_integer())
+            .collect();
+
+        assert_eq!(ages, &[Some(12), Some(22), Some(0)]);
+    }
+}
+
 #[test_connector(tags(Sqlite))]
 fn changing_a_column_from_optional_to_required_with_a_default_is_safe(api: TestApi) {
     let dm = r#"
@@ -51,3 +140,61 @@ fn changing_a_column_from_optional_to_required_with_a_default_is_safe(api: TestA
         assert_eq!(ages, &[Some(12), Some(22), Some(30)]);
     }
 }
+
+// `RedefineTable` recreates the table under a temporary name and renames it back (see
+// `render_redefine_tables` in the SQLite renderer), so the `AUTOINCREMENT` high-water-mark in
+// `sqlite_sequence` has to survive that rename instead of resetting to `max(id)` — otherwise a
+// row inserted after the redefine could reuse the id of a row that was deleted before it.
+#[test_connector(tags(Sqlite))]
+fn the_autoincrement_counter_survives_a_redefine_even_after_deletes(api: TestApi) {
+    let dm = r#"
+        model Test {
+            id   Int    @id @default(autoincrement())
+            name String
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    let insert = Insert::multi_into(api.render_table_name("Test"), ["name"])
+        .values(("a",))
+        .values(("b",))
+        .values(("c",));
+
+    api.query(insert.into());
+
+    // Deleting the highest id leaves the `sqlite_sequence` counter (3) above `max(id)` (2), the
+    // edge case the redefine needs to preserve rather than recompute.
+    api.raw_cmd(r#"DELETE FROM "Test" WHERE "id" = 3"#);
+
+    let counter_before = api.query_raw(r#"SELECT "seq" FROM "sqlite_sequence" WHERE "name" = 'Test'"#, &[]);
+    assert_eq!(counter_before.first().unwrap().at(0).unwrap().as_integer(), Some(3));
+
+    // A required column with no existing data can't be added in place on SQLite, forcing a
+    // `RedefineTable` of `Test`.
+    let dm2 = r#"
+        model Test {
+            id   Int    @id @default(autoincrement())
+            name String
+            flag Boolean @default(true)
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm2).force(true).send().assert_green();
+
+    let counter_after = api.query_raw(r#"SELECT "seq" FROM "sqlite_sequence" WHERE "name" = 'Test'"#, &[]);
+    assert_eq!(
+        counter_after.first().unwrap().at(0).unwrap().as_integer(),
+        Some(3),
+        "the autoincrement counter must survive the redefine unchanged"
+    );
+
+    let insert = Insert::multi_into(api.render_table_name("Test"), ["name"]).values(("d",));
+    api.query(insert.into());
+
+    let data = api.dump_table("Test");
+    let ids: Vec<Option<i64>> = data.into_iter().map(|row| row.get("id").unwrap().as_integer()).collect();
+
+    // The new row must get id 4, not the deleted row's id 3.
+    assert_eq!(ids, &[Some(1), Some(2), Some(4)]);
+}
@@ -377,6 +377,52 @@ fn column_defaults_must_be_migrated(api: TestApi) {
     });
 }
 
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn rewriting_a_default_as_an_equivalent_dbgenerated_expression_does_not_migrate(api: TestApi) {
+    let dm1 = r#"
+        model Fruit {
+            id    Int @id
+            count Int @default(5)
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm1).send().assert_green();
+
+    let dm2 = r#"
+        model Fruit {
+            id    Int @id
+            count Int @default(dbgenerated("5"))
+        }
+    "#;
+
+    // The column already defaults to `5` in the database. Rewriting the same value as a
+    // `dbgenerated` expression in the schema is a cosmetic change, not a migration.
+    api.schema_push_w_datasource(dm2).send().assert_green().assert_no_steps();
+}
+
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn rewriting_a_default_as_a_different_dbgenerated_expression_still_migrates(api: TestApi) {
+    let dm1 = r#"
+        model Fruit {
+            id    Int @id
+            count Int @default(5)
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm1).send().assert_green();
+
+    let dm2 = r#"
+        model Fruit {
+            id    Int @id
+            count Int @default(dbgenerated("floor(random() * 100)"))
+        }
+    "#;
+
+    // `floor(random() * 100)` is runtime-dependent: it cannot be known to have the same effective
+    // value as the literal `5`, so this must still be treated as a default change.
+    api.schema_push_w_datasource(dm2).send().assert_green().assert_has_executed_steps();
+}
+
 #[test_connector(tags(Mssql))]
 fn default_constraint_names_should_work(api: TestApi) {
     let dm = r#"
@@ -284,6 +284,80 @@ fn changing_a_foreign_key_constrained_column_from_nullable_to_required_and_back_
     api.schema_push_w_datasource(dm).send().assert_green();
 }
 
+// Step ordering is a property of `SqlMigrationStep`'s variants (`CreateTable` sorts before
+// `AddForeignKey`, see the comment on the enum in `sql_migration.rs`), so a self-referencing
+// table is no different from any other: the table is always created in full — including the
+// self-referencing column — before its foreign key is added. There are no rows yet at that
+// point regardless of whether the table references itself, so this never runs into the
+// required-column-on-a-populated-table problem that adding the same column later could.
+#[test_connector(exclude(Vitess))]
+fn self_referencing_required_foreign_key_works_on_initial_migration(api: TestApi) {
+    let dm = r#"
+        model Employee {
+            id        Int      @id @default(autoincrement())
+            managerId Int
+            manager   Employee @relation("EmployeeManager", fields: [managerId], references: [id])
+            reports   Employee[] @relation("EmployeeManager")
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    api.assert_schema()
+        .assert_table("Employee", |table| table.assert_foreign_keys_count(1));
+}
+
+// The same required self-referencing foreign key, added to an already-existing table instead of
+// at creation time, hits the generic "required column added to a table with data" unexecutable
+// check like any other required column would — an empty table is fine, a populated one isn't.
+#[test_connector(exclude(Vitess))]
+fn self_referencing_required_foreign_key_added_later_is_fine_on_an_empty_table(api: TestApi) {
+    let dm1 = r#"
+        model Employee {
+            id Int @id @default(autoincrement())
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm1).send().assert_green();
+
+    let dm2 = r#"
+        model Employee {
+            id        Int      @id @default(autoincrement())
+            managerId Int
+            manager   Employee @relation("EmployeeManager", fields: [managerId], references: [id])
+            reports   Employee[] @relation("EmployeeManager")
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm2).send().assert_green();
+}
+
+#[test_connector(exclude(Vitess))]
+fn self_referencing_required_foreign_key_added_later_is_unexecutable_on_a_populated_table(api: TestApi) {
+    let dm1 = r#"
+        model Employee {
+            id Int @id @default(autoincrement())
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm1).send().assert_green();
+
+    api.insert("Employee").value("id", 1).result_raw();
+
+    let dm2 = r#"
+        model Employee {
+            id        Int      @id @default(autoincrement())
+            managerId Int
+            manager   Employee @relation("EmployeeManager", fields: [managerId], references: [id])
+            reports   Employee[] @relation("EmployeeManager")
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm2).send().assert_unexecutable(&[
+        "Added the required column `managerId` to the `Employee` table without a default value. There are 1 rows in this table, it is not possible to execute this step.".into(),
+    ]);
+}
+
 #[test_connector(exclude(CockroachDb))]
 fn changing_all_referenced_columns_of_foreign_key_works(api: TestApi) {
     let dm1 = r#"
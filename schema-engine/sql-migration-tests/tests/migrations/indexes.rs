@@ -692,6 +692,74 @@ fn removal_length_prefix_index(api: TestApi) {
     });
 }
 
+#[test_connector(tags(Mysql8))]
+fn changing_length_prefix_recreates_the_index(api: TestApi) {
+    let dm = formatdoc! {r#"
+        {}
+
+        model A {{
+          id Int    @id
+          a  String @db.VarChar(255)
+
+          @@index([a(length: 30)])
+        }}
+    "#, api.datasource_block()};
+
+    api.schema_push(&dm).send().assert_green();
+
+    api.assert_schema().assert_table("A", |table| {
+        table.assert_index_on_columns(&["a"], |index| index.assert_column("a", |attrs| attrs.assert_length_prefix(30)))
+    });
+
+    let dm = formatdoc! {r#"
+        {}
+
+        model A {{
+          id Int    @id
+          a  String @db.VarChar(255)
+
+          @@index([a(length: 10)])
+        }}
+    "#, api.datasource_block()};
+
+    api.schema_push(&dm).send().assert_green();
+
+    api.assert_schema().assert_table("A", |table| {
+        table.assert_index_on_columns(&["a"], |index| index.assert_column("a", |attrs| attrs.assert_length_prefix(10)))
+    });
+}
+
+#[test_connector(tags(Mysql8))]
+fn length_prefix_covering_the_full_column_is_equivalent_to_no_prefix(api: TestApi) {
+    let dm = formatdoc! {r#"
+        {}
+
+        model A {{
+          id Int    @id
+          a  String @db.VarChar(20)
+
+          @@index([a(length: 20)])
+        }}
+    "#, api.datasource_block()};
+
+    api.schema_push(&dm).send().assert_green();
+
+    let dm = formatdoc! {r#"
+        {}
+
+        model A {{
+          id Int    @id
+          a  String @db.VarChar(20)
+
+          @@index([a])
+        }}
+    "#, api.datasource_block()};
+
+    // A prefix length equal to the full column length is the same index as one with no
+    // explicit prefix, so this should not generate a migration.
+    api.schema_push(&dm).send().assert_green().assert_no_steps();
+}
+
 #[test_connector(exclude(Mysql56, Mysql57, Mariadb))]
 fn descending_compound_index(api: TestApi) {
     let dm = formatdoc! {r#"
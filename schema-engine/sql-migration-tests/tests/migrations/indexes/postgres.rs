@@ -27,3 +27,89 @@ fn hash_index(api: TestApi) {
 
     api.schema_push_w_datasource(dm).send().assert_no_steps();
 }
+
+// Collation is not expressible in the Prisma schema, so a unique index whose collation was
+// changed out-of-band (e.g. by a DBA) cannot be seen by diffing the introspected schema against
+// the calculated one. What we can assert is that the differ notices a collation mismatch between
+// two already-introspected schemas and recreates the index instead of silently ignoring it.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn unique_index_collation_change_triggers_recreate(api: TestApi) {
+    let dm = r#"
+        model A {
+          id Int    @id
+          a  String @unique
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    let schema = api.assert_schema().into_schema();
+    let index_name = schema
+        .table_walkers()
+        .find(|t| t.name() == "A")
+        .unwrap()
+        .indexes()
+        .find(|idx| !idx.is_primary_key())
+        .unwrap()
+        .name()
+        .to_owned();
+
+    api.raw_cmd(&format!(
+        r#"DROP INDEX "{index_name}"; CREATE UNIQUE INDEX "{index_name}" ON "A" (a COLLATE "C")"#
+    ));
+
+    // The collation recorded by introspection no longer matches the `None` the calculator always
+    // produces for the Prisma-schema side, so re-pushing the same schema recreates the index
+    // instead of being a no-op.
+    api.schema_push_w_datasource(dm).send().assert_green().assert_has_executed_steps();
+}
+
+// A partial index's predicate, like its collation above, isn't expressible in the Prisma schema.
+// The differ must still notice when one was added out-of-band and recreate the index to match the
+// calculated (fully unique) schema.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn partial_unique_index_predicate_change_triggers_recreate(api: TestApi) {
+    let dm = r#"
+        model A {
+          id        Int     @id
+          a         String  @unique
+          deletedAt DateTime?
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    let schema = api.assert_schema().into_schema();
+    let index_name = schema
+        .table_walkers()
+        .find(|t| t.name() == "A")
+        .unwrap()
+        .indexes()
+        .find(|idx| !idx.is_primary_key())
+        .unwrap()
+        .name()
+        .to_owned();
+
+    api.raw_cmd(&format!(
+        r#"DROP INDEX "{index_name}"; CREATE UNIQUE INDEX "{index_name}" ON "A" (a) WHERE "deletedAt" IS NULL"#
+    ));
+
+    api.assert_schema().assert_table("A", |table| {
+        table.assert_index_on_columns(&["a"], |idx| idx.assert_is_unique())
+    });
+
+    // The introspected index now carries a predicate that the calculated schema (which has no
+    // way to express one) doesn't, so re-pushing recreates it as a fully unique index again.
+    api.schema_push_w_datasource(dm).send().assert_green().assert_has_executed_steps();
+
+    let schema = api.assert_schema().into_schema();
+    let index = schema
+        .table_walkers()
+        .find(|t| t.name() == "A")
+        .unwrap()
+        .indexes()
+        .find(|idx| !idx.is_primary_key())
+        .unwrap();
+
+    assert_eq!(index.predicate(), None);
+}
@@ -634,3 +634,40 @@ fn bigint_defaults_work(api: TestApi) {
     api.schema_push(schema).send().assert_green();
     api.schema_push(schema).send().assert_green().assert_no_steps();
 }
+
+#[test_connector(tags(Mysql8))]
+fn added_column_is_positioned_with_after(api: TestApi) {
+    let dir = api.create_migrations_directory();
+
+    let initial_dm = api.datamodel_with_provider(
+        r#"
+        model A {
+            id    Int    @id
+            first String
+            third String
+        }
+    "#,
+    );
+
+    api.create_migration("01init", &initial_dm, &dir).send_sync();
+
+    let next_dm = api.datamodel_with_provider(
+        r#"
+        model A {
+            id     Int    @id
+            first  String
+            second String
+            third  String
+        }
+    "#,
+    );
+
+    let expected_script = expect![[r#"
+        -- AlterTable
+        ALTER TABLE `A` ADD COLUMN `second` VARCHAR(191) NOT NULL AFTER `first`;
+    "#]];
+
+    api.create_migration("02addcolumn", &next_dm, &dir)
+        .send_sync()
+        .assert_migration("02addcolumn", move |migration| migration.expect_contents(expected_script));
+}
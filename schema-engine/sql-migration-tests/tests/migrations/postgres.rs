@@ -3,7 +3,7 @@ mod introspection;
 mod multi_schema;
 
 use psl::parser_database::SourceFile;
-use quaint::Value;
+use quaint::{ast::Insert, Value};
 use schema_core::{json_rpc::types::SchemasContainer, schema_connector::DiffTarget};
 use sql_migration_tests::test_api::*;
 use std::fmt::Write;
@@ -136,6 +136,80 @@ fn native_type_columns_can_be_created(api: TestApi) {
     api.schema_push_w_datasource(dm).send().assert_green().assert_no_steps();
 }
 
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn timestamp_to_timestamptz_is_a_risky_cast_with_an_explicit_using_clause(api: TestApi) {
+    let dir = api.create_migrations_directory();
+
+    let initial_dm = api.datamodel_with_provider(
+        r#"
+        model A {
+            id Int      @id
+            ts DateTime @db.Timestamp(6)
+        }
+    "#,
+    );
+
+    api.create_migration("01init", &initial_dm, &dir).send_sync();
+
+    let next_dm = api.datamodel_with_provider(
+        r#"
+        model A {
+            id Int      @id
+            ts DateTime @db.Timestamptz(6)
+        }
+    "#,
+    );
+
+    let expected_script = expect![[r#"
+        -- AlterTable
+        ALTER TABLE "A" ALTER COLUMN "ts" SET DATA TYPE TIMESTAMPTZ(6) USING "ts" AT TIME ZONE 'UTC';
+    "#]];
+
+    api.create_migration("02totimestamptz", &next_dm, &dir)
+        .send_sync()
+        .assert_migration("02totimestamptz", move |migration| migration.expect_contents(expected_script));
+}
+
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn timestamp_to_timestamptz_conversion_warns_about_existing_data(api: TestApi) {
+    let dm = api.datamodel_with_provider(
+        r#"
+        model A {
+            id Int      @id
+            ts DateTime @db.Timestamp(6)
+        }
+    "#,
+    );
+
+    api.schema_push_w_datasource(&dm).send().assert_green();
+
+    let insert = Insert::single_into(api.render_table_name("A"))
+        .value("id", 1)
+        .value("ts", "2022-01-01 00:00:00");
+    api.query(insert.into());
+
+    let dm2 = api.datamodel_with_provider(
+        r#"
+        model A {
+            id Int      @id
+            ts DateTime @db.Timestamptz(6)
+        }
+    "#,
+    );
+
+    // If the cast were classified as safe, as it was before, this wouldn't require `force` or
+    // produce a warning.
+    api.schema_push_w_datasource(&dm2)
+        .force(true)
+        .send()
+        .assert_executable()
+        .assert_warnings(&[
+            "You are about to alter the column `ts` on the `A` table, which contains 1 non-null values. \
+             The data in that column will be cast from `DateTime` to `DateTime`."
+                .into(),
+        ]);
+}
+
 #[test_connector(tags(Postgres))]
 fn uuids_do_not_generate_drift_issue_5282(api: TestApi) {
     if !api.is_cockroach() {
@@ -742,6 +816,36 @@ fn dbgenerated_on_generated_columns_is_idempotent(api: TestApi) {
     api.schema_push(schema).send().assert_green().assert_no_steps();
 }
 
+// PostgreSQL does not currently support `VIRTUAL` generated columns (only `STORED`), so a real
+// STORED-to-VIRTUAL transition cannot be exercised against a live database. What we can assert is
+// that introspection records the storage kind, and that the differ treats a mismatch in that
+// recorded kind between two schemas as a drop-and-recreate rather than silently ignoring it —
+// including when the generated column is referenced by an index.
+#[test_connector(tags(Postgres12), exclude(CockroachDb))]
+fn stored_generated_column_storage_kind_is_introspected(api: TestApi) {
+    let sql = r#"
+        CREATE TABLE "table" (
+         "id" TEXT NOT NULL,
+         "hereBeDragons" TEXT NOT NULL GENERATED ALWAYS AS ('this row ID is: '::text || "id") STORED,
+
+         CONSTRAINT "table_pkey" PRIMARY KEY ("id")
+        );
+
+        CREATE INDEX "table_hereBeDragons_idx" ON "table" ("hereBeDragons");
+    "#;
+
+    api.raw_cmd(sql);
+
+    let schema = api.describe();
+    let table = schema.table_walkers().find(|t| t.name() == "table").unwrap();
+    let column = table.column("hereBeDragons").unwrap();
+
+    assert_eq!(
+        column.generated_column_storage(),
+        Some(sql_schema_describer::GeneratedColumnStorage::Stored)
+    );
+}
+
 // https://github.com/prisma/prisma/issues/15654
 #[test_connector(tags(Postgres12), exclude(CockroachDb))]
 fn dbgenerated_on_generated_unsupported_columns_is_idempotent(api: TestApi) {
@@ -773,3 +877,204 @@ fn dbgenerated_on_generated_unsupported_columns_is_idempotent(api: TestApi) {
 
     api.schema_push(schema).send().assert_green().assert_no_steps();
 }
+
+// Exclusion constraints aren't representable in the Prisma schema, so we diff two live
+// database states directly instead of going through a datamodel.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn exclusion_constraints_can_be_added_and_dropped(mut api: TestApi) {
+    use schema_core::schema_connector::SchemaConnector;
+
+    api.raw_cmd("CREATE EXTENSION IF NOT EXISTS btree_gist;");
+    api.raw_cmd(
+        r#"
+        CREATE TABLE "Booking" (
+            id SERIAL PRIMARY KEY,
+            room_id INTEGER NOT NULL,
+            during TSRANGE NOT NULL
+        );
+        "#,
+    );
+
+    let without_exclusion_constraint =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    api.raw_cmd(
+        r#"ALTER TABLE "Booking" ADD CONSTRAINT "no_overlapping_bookings" EXCLUDE USING gist (room_id WITH =, during WITH &&)"#,
+    );
+
+    let with_exclusion_constraint =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    let adding = api
+        .connector
+        .diff(without_exclusion_constraint.clone(), with_exclusion_constraint.clone());
+    let script = api.connector.render_script(&adding, &Default::default()).unwrap();
+
+    let expected = expect![[r#"
+        -- AlterTable
+        ALTER TABLE "Booking" ADD CONSTRAINT "no_overlapping_bookings" EXCLUDE USING gist (room_id WITH =, during WITH &&);
+    "#]];
+    expected.assert_eq(&script);
+
+    let dropping = api.connector.diff(with_exclusion_constraint, without_exclusion_constraint);
+    let script = api.connector.render_script(&dropping, &Default::default()).unwrap();
+
+    let expected = expect![[r#"
+        -- AlterTable
+        ALTER TABLE "Booking" DROP CONSTRAINT "no_overlapping_bookings";
+    "#]];
+    expected.assert_eq(&script);
+}
+
+// Row level security policies aren't representable in the Prisma schema, so we diff two live
+// database states directly instead of going through a datamodel.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn row_level_security_policies_can_be_added_and_dropped(mut api: TestApi) {
+    use schema_core::schema_connector::SchemaConnector;
+
+    api.raw_cmd(r#"CREATE TABLE "Account" (id SERIAL PRIMARY KEY, owner TEXT NOT NULL);"#);
+
+    let without_policy =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+    let without_policy_again =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    api.raw_cmd(
+        r#"CREATE POLICY "own_account" ON "Account" USING (owner = current_user) WITH CHECK (owner = current_user)"#,
+    );
+
+    let with_policy = tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+    let with_policy_again =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    let adding = api.connector.diff(without_policy, with_policy);
+    let script = api.connector.render_script(&adding, &Default::default()).unwrap();
+
+    let expected = expect![[r#"
+        -- CreatePolicy
+        CREATE POLICY "own_account" ON "Account" AS PERMISSIVE FOR ALL TO PUBLIC USING (owner = current_user) WITH CHECK (owner = current_user);
+    "#]];
+    expected.assert_eq(&script);
+
+    let dropping = api.connector.diff(with_policy_again, without_policy_again);
+    let script = api.connector.render_script(&dropping, &Default::default()).unwrap();
+
+    let expected = expect![[r#"
+        -- DropPolicy
+        DROP POLICY "own_account" ON "Account";
+    "#]];
+    expected.assert_eq(&script);
+}
+
+// Row level security policies aren't representable in the Prisma schema, so we diff two live
+// database states directly instead of going through a datamodel.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn creating_a_table_with_a_row_level_security_policy_creates_the_table_first(mut api: TestApi) {
+    use schema_core::schema_connector::SchemaConnector;
+
+    let without_table =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    api.raw_cmd(r#"CREATE TABLE "Account" (id SERIAL PRIMARY KEY, owner TEXT NOT NULL);"#);
+    api.raw_cmd(r#"ALTER TABLE "Account" ENABLE ROW LEVEL SECURITY;"#);
+    api.raw_cmd(
+        r#"CREATE POLICY "own_account" ON "Account" USING (owner = current_user) WITH CHECK (owner = current_user)"#,
+    );
+
+    let with_table_and_policy =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    let creating = api.connector.diff(without_table, with_table_and_policy);
+    let script = api.connector.render_script(&creating, &Default::default()).unwrap();
+
+    let create_table_pos = script.find("CREATE TABLE").expect("expected a CREATE TABLE statement");
+    let enable_rls_pos = script
+        .find("ENABLE ROW LEVEL SECURITY")
+        .expect("expected an ENABLE ROW LEVEL SECURITY statement");
+    let create_policy_pos = script.find("CREATE POLICY").expect("expected a CREATE POLICY statement");
+
+    assert!(
+        create_table_pos < enable_rls_pos,
+        "CREATE TABLE must run before ENABLE ROW LEVEL SECURITY:\n{script}"
+    );
+    assert!(
+        enable_rls_pos < create_policy_pos,
+        "ENABLE ROW LEVEL SECURITY must run before CREATE POLICY:\n{script}"
+    );
+}
+
+// Table inheritance isn't representable in the Prisma schema, so we diff two live database
+// states directly instead of going through a datamodel.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn table_inheritance_can_be_added_and_dropped(mut api: TestApi) {
+    use schema_core::schema_connector::SchemaConnector;
+
+    api.raw_cmd(r#"CREATE TABLE "Animal" (id SERIAL PRIMARY KEY, name TEXT NOT NULL);"#);
+    api.raw_cmd(r#"CREATE TABLE "Dog" (breed TEXT NOT NULL);"#);
+
+    let without_inheritance =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+    let without_inheritance_again =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    api.raw_cmd(r#"ALTER TABLE "Dog" INHERIT "Animal""#);
+
+    let with_inheritance =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+    let with_inheritance_again =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    let adding = api.connector.diff(without_inheritance, with_inheritance);
+    let script = api.connector.render_script(&adding, &Default::default()).unwrap();
+
+    let expected = expect![[r#"
+        -- AddTableInheritance
+        ALTER TABLE "Dog" INHERIT "Animal";
+    "#]];
+    expected.assert_eq(&script);
+
+    let dropping = api.connector.diff(with_inheritance_again, without_inheritance_again);
+    let script = api.connector.render_script(&dropping, &Default::default()).unwrap();
+
+    let expected = expect![[r#"
+        -- DropTableInheritance
+        ALTER TABLE "Dog" NO INHERIT "Animal";
+    "#]];
+    expected.assert_eq(&script);
+}
+
+// Postgres only requires that a column a child shares with its parent have the same type when
+// `INHERIT` is applied — the child can keep its own default. This applies the generated
+// `AddTableInheritance` step for real, rather than only asserting the rendered SQL, to prove that
+// code path against a child that already overrides a parent column.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn table_inheritance_can_be_added_when_the_child_overrides_a_parent_column(mut api: TestApi) {
+    use schema_core::schema_connector::SchemaConnector;
+
+    api.raw_cmd(r#"CREATE TABLE "Animal" (id SERIAL PRIMARY KEY, weight INT NOT NULL DEFAULT 10);"#);
+    api.raw_cmd(r#"CREATE TABLE "Dog" (weight INT NOT NULL DEFAULT 99);"#);
+
+    let without_inheritance =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    api.raw_cmd(r#"ALTER TABLE "Dog" INHERIT "Animal""#);
+
+    let with_inheritance =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+    let with_inheritance_again =
+        tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+
+    // Undo the `raw_cmd` above: we want to apply the `AddTableInheritance` step ourselves below,
+    // not merely assert its rendered SQL.
+    api.raw_cmd(r#"ALTER TABLE "Dog" NO INHERIT "Animal""#);
+
+    let adding = api.connector.diff(without_inheritance, with_inheritance);
+
+    tok(api.connector.apply_migration(&adding)).unwrap();
+
+    let applied = tok(api.connector.database_schema_from_diff_target(DiffTarget::Database, None, None)).unwrap();
+    let remaining = api.connector.diff(applied, with_inheritance_again);
+    let script = api.connector.render_script(&remaining, &Default::default()).unwrap();
+
+    assert_eq!(script, "-- This is an empty migration.");
+}
@@ -49,6 +49,68 @@ fn apply_migrations_with_multiple_schemas_where_one_is_search_path_with_a_foreig
     api.apply_migrations(&dir).send_sync().assert_applied_migrations(&[]);
 }
 
+#[test_connector(tags(Postgres), preview_features("multiSchema"), namespaces("one", "two"))]
+fn dropping_a_schema_with_a_contained_table_works(api: TestApi) {
+    let datasource = api.datasource_block_with(&[("schemas", r#"["one", "two"]"#)]);
+    let generator = api.generator_block();
+
+    let dm1 = formatdoc! {r#"
+        {datasource}
+
+        {generator}
+
+        model A {{
+           id Int @id
+
+           @@schema("one")
+        }}
+
+        model B {{
+           id Int @id
+
+           @@schema("two")
+        }}
+    "#};
+
+    let dir = api.create_migrations_directory();
+
+    api.create_migration("init", &dm1, &dir).send_sync();
+
+    api.apply_migrations(&dir)
+        .send_sync()
+        .assert_applied_migrations(&["init"]);
+
+    let datasource = api.datasource_block_with(&[("schemas", r#"["one"]"#)]);
+
+    let dm2 = formatdoc! {r#"
+        {datasource}
+
+        {generator}
+
+        model A {{
+           id Int @id
+
+           @@schema("one")
+        }}
+    "#};
+
+    api.create_migration("drop-two", &dm2, &dir)
+        .send_sync()
+        .assert_migration("drop-two", |migration| {
+            migration.expect_contents(expect![[r#"
+                -- DropTable
+                DROP TABLE "two"."B";
+
+                -- DropSchema
+                DROP SCHEMA IF EXISTS "two" CASCADE;
+            "#]])
+        });
+
+    api.apply_migrations(&dir)
+        .send_sync()
+        .assert_applied_migrations(&["drop-two"]);
+}
+
 // This is the only "top" level test in this module. It defines a list of tests and executes them.
 // If you want to look at the tests, see the `tests` variable below.
 #[test_connector(tags(Postgres), preview_features("multiSchema"), namespaces("one", "two"))]
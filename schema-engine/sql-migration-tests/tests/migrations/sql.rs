@@ -1,5 +1,6 @@
 mod vitess;
 
+use schema_core::schema_connector::DiffTarget;
 use sql_migration_tests::test_api::*;
 
 #[test_connector]
@@ -134,6 +135,74 @@ fn relations_to_models_with_no_pk_and_a_single_unique_required_field_work(api: T
         });
 }
 
+// Reserved keywords don't actually break anything: every identifier we render is quoted, so the
+// migration below still applies fine (see `reserved_sql_keywords_must_work`). The check is purely
+// advisory, for callers whose stack also reads the database through unquoted SQL elsewhere.
+#[test_connector]
+fn check_reserved_identifiers_flags_a_table_named_order(mut api: TestApi) {
+    let dm = api.datamodel_with_provider(
+        r#"
+        model order {
+            id Int @id
+        }
+    "#,
+    );
+
+    let from = tok(api.connector.database_schema_from_diff_target(DiffTarget::Empty, None, None)).unwrap();
+    let to = tok(api.connector.database_schema_from_diff_target(
+        DiffTarget::Datamodel(vec![("schema.prisma".to_string(), dm.clone().into())]),
+        None,
+        None,
+    ))
+    .unwrap();
+
+    let migration = api.connector.diff(from, to);
+
+    let error = api.connector.check_reserved_identifiers(&migration).unwrap_err();
+    let table_name = api.normalize_identifier("order").into_owned();
+    assert!(
+        error.to_string().contains(&table_name),
+        "expected the error to mention `{table_name}`, got: {error}"
+    );
+
+    api.schema_push_w_datasource(&dm).send().assert_green();
+}
+
+// Whether an over-long name is silently truncated, rejected outright, or something else again is
+// entirely up to the database — this check is advisory and runs ahead of applying anything, so it
+// doesn't matter which; we only assert that it flags the name here, not that the migration can
+// still be applied afterwards.
+#[test_connector]
+fn check_identifier_lengths_flags_an_over_long_index_name(mut api: TestApi) {
+    let long_name = "a".repeat(250);
+    let dm = api.datamodel_with_provider(&format!(
+        r#"
+        model Test {{
+            id Int @id
+            value Int
+
+            @@index([value], map: "{long_name}")
+        }}
+    "#
+    ));
+
+    let from = tok(api.connector.database_schema_from_diff_target(DiffTarget::Empty, None, None)).unwrap();
+    let to = tok(api.connector.database_schema_from_diff_target(
+        DiffTarget::Datamodel(vec![("schema.prisma".to_string(), dm.clone().into())]),
+        None,
+        None,
+    ))
+    .unwrap();
+
+    let migration = api.connector.diff(from, to);
+
+    let error = api.connector.check_identifier_lengths(&migration).unwrap_err();
+    assert!(
+        error.to_string().contains(&long_name),
+        "expected the error to mention the over-long index name, got: {error}"
+    );
+}
+
 #[test_connector(exclude(Vitess))]
 fn reserved_sql_keywords_must_work(api: TestApi) {
     // Group is a reserved keyword
@@ -1,5 +1,7 @@
+use psl::parser_database::SourceFile;
 use quaint::prelude::Insert;
 use schema_core::json_rpc::types::SchemasContainer;
+use schema_core::schema_connector::{DiffTarget, SchemaConnector};
 use sql_migration_tests::test_api::*;
 
 #[test_connector(tags(Sqlite))]
@@ -219,3 +221,86 @@ fn introspecting_a_non_existing_db_fails() {
     "#]];
     expected.assert_eq(&err.to_string());
 }
+
+#[test_connector(tags(Sqlite))]
+fn render_script_with_options_can_omit_step_comments(mut api: TestApi) {
+    // Adding a required column without a default forces SQLite to redefine the table, which
+    // renders as several statements (CREATE TABLE, INSERT, DROP, ALTER ... RENAME, ...). The
+    // comment for that step should still appear exactly once when comments are enabled.
+    let dm1 = r#"
+        model A {
+            id   Int    @id
+            name String
+        }
+    "#;
+
+    let dm2 = r#"
+        model A {
+            id   Int    @id
+            name String
+            age  Int
+        }
+    "#;
+
+    let from = tok(api.connector.database_schema_from_diff_target(
+        DiffTarget::Datamodel(vec![("schema.prisma".to_string(), SourceFile::new_static(dm1))]),
+        None,
+        None,
+    ))
+    .unwrap();
+    let to = tok(api.connector.database_schema_from_diff_target(
+        DiffTarget::Datamodel(vec![("schema.prisma".to_string(), SourceFile::new_static(dm2))]),
+        None,
+        None,
+    ))
+    .unwrap();
+    let migration = api.connector.diff(from, to);
+
+    let with_comments = api
+        .connector
+        .render_script_with_options(&migration, &Default::default(), true)
+        .unwrap();
+    assert_eq!(with_comments.matches("-- RedefineTables").count(), 1);
+
+    let without_comments = api
+        .connector
+        .render_script_with_options(&migration, &Default::default(), false)
+        .unwrap();
+    assert!(!without_comments.contains("-- RedefineTables"));
+}
+
+// This reconfigures the test connector with a connection string carrying the
+// `migration_statement_chunk_size` parameter, which the default test engine does not set.
+#[test_connector(tags(Sqlite))]
+fn many_statements_are_applied_with_chunked_commits(mut api: TestApi) {
+    let connection_string = format!("{}?migration_statement_chunk_size=3", api.connection_string());
+
+    api.connector
+        .set_params(schema_core::schema_connector::ConnectorParams {
+            connection_string,
+            preview_features: Default::default(),
+            shadow_database_connection_string: None,
+        })
+        .unwrap();
+
+    // Ten models, each becoming its own `CREATE TABLE` statement: with a chunk size of three,
+    // applying the migration must span several BEGIN/COMMIT cycles.
+    let dm = api.datamodel_with_provider(
+        r#"
+        model Model0 { id Int @id }
+        model Model1 { id Int @id }
+        model Model2 { id Int @id }
+        model Model3 { id Int @id }
+        model Model4 { id Int @id }
+        model Model5 { id Int @id }
+        model Model6 { id Int @id }
+        model Model7 { id Int @id }
+        model Model8 { id Int @id }
+        model Model9 { id Int @id }
+    "#,
+    );
+
+    api.schema_push(dm).send().assert_green();
+
+    api.assert_schema().assert_tables_count(10);
+}
@@ -173,3 +173,71 @@ fn unique_is_allowed_on_an_id_field(api: TestApi) {
         t.assert_index_on_columns(&["id"], |idx| idx.assert_is_unique())
     });
 }
+
+// Unique constraints have no representation distinct from indexes in this codebase (they are
+// indexes with `IndexType::Unique`), and index column pairing already compares columns
+// positionally, so a reordered unique constraint already fails to match its previous definition
+// and gets recreated, the same as a reordered plain index would.
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn reordering_unique_constraint_columns_triggers_a_recreate(api: TestApi) {
+    let dm1 = r#"
+        model A {
+          id Int @id
+          a  Int
+          b  Int
+
+          @@unique([a, b])
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm1).send().assert_green();
+    api.assert_schema().assert_table("A", |t| {
+        t.assert_index_on_columns(&["a", "b"], |idx| idx.assert_is_unique())
+    });
+
+    let dm2 = r#"
+        model A {
+          id Int @id
+          a  Int
+          b  Int
+
+          @@unique([b, a])
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm2)
+        .send()
+        .assert_green()
+        .assert_has_executed_steps();
+
+    api.assert_schema().assert_table("A", |t| {
+        t.assert_index_on_columns(&["b", "a"], |idx| idx.assert_is_unique())
+    });
+}
+
+#[test_connector(tags(Postgres), exclude(CockroachDb))]
+fn unique_constraint_and_normal_index_with_reordered_columns_are_distinct(api: TestApi) {
+    let dm = r#"
+        model A {
+          id Int @id
+          a  Int
+          b  Int
+
+          @@unique([a, b])
+          @@index([b, a])
+        }
+    "#;
+
+    api.schema_push_w_datasource(dm).send().assert_green();
+
+    api.assert_schema().assert_table("A", |t| {
+        t.assert_indexes_count(2)
+            .assert_index_on_columns(&["a", "b"], |idx| idx.assert_is_unique())
+            .assert_index_on_columns(&["b", "a"], |idx| idx.assert_is_not_unique())
+    });
+
+    // Re-pushing the identical schema is a no-op: the unique constraint and the plain index
+    // are not confused with one another even though they cover the same columns in opposite
+    // order.
+    api.schema_push_w_datasource(dm).send().assert_no_steps();
+}
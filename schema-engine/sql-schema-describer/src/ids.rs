@@ -51,3 +51,7 @@ pub struct TableDefaultValueId(pub(crate) u32);
 /// The identifier for a table default value in the database.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Serialize, Deserialize, Ord, Hash)]
 pub struct ViewDefaultValueId(pub(crate) u32);
+
+/// The identifier for a trigger in the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TriggerId(pub(crate) u32);
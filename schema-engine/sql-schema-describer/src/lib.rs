@@ -88,6 +88,8 @@ pub struct SqlSchema {
     procedures: Vec<Procedure>,
     /// The user-defined types procedures.
     user_defined_types: Vec<UserDefinedType>,
+    /// Triggers defined on tables.
+    triggers: Vec<Trigger>,
     /// Connector-specific data
     connector_data: connector_data::ConnectorData,
 }
@@ -206,6 +208,31 @@ impl SqlSchema {
         }
     }
 
+    /// Make the index a partial index, rendered with a `WHERE` clause using `predicate` as-is.
+    pub fn set_index_predicate(&mut self, index_id: IndexId, predicate: String) {
+        self.indexes[index_id.0 as usize].predicate = Some(predicate);
+    }
+
+    /// Set the index's comment, as introspected from the database.
+    pub fn set_index_description(&mut self, index_id: IndexId, description: String) {
+        self.indexes[index_id.0 as usize].description = Some(description);
+    }
+
+    /// PostgreSQL-only: set the tablespace an index is stored in, as found during introspection.
+    pub fn set_index_tablespace(&mut self, index_id: IndexId, tablespace: String) {
+        self.indexes[index_id.0 as usize].tablespace = Some(tablespace);
+    }
+
+    /// Set the foreign key's comment, as introspected from the database.
+    pub fn set_foreign_key_description(&mut self, foreign_key_id: ForeignKeyId, description: String) {
+        self.foreign_keys[foreign_key_id.0 as usize].description = Some(description);
+    }
+
+    /// PostgreSQL-only: set the foreign key's `MATCH` type, as introspected from the database.
+    pub fn set_foreign_key_match_type(&mut self, foreign_key_id: ForeignKeyId, match_type: ForeignKeyMatchType) {
+        self.foreign_keys[foreign_key_id.0 as usize].match_type = match_type;
+    }
+
     /// Add a table column to the schema.
     pub fn push_table_column(&mut self, table_id: TableId, column: Column) -> TableColumnId {
         let id = TableColumnId(self.table_columns.len() as u32);
@@ -247,6 +274,9 @@ impl SqlSchema {
             table_id,
             index_name,
             tpe: IndexType::Fulltext,
+            predicate: None,
+            description: None,
+            tablespace: None,
         });
         id
     }
@@ -258,6 +288,9 @@ impl SqlSchema {
             table_id,
             index_name,
             tpe: IndexType::Normal,
+            predicate: None,
+            description: None,
+            tablespace: None,
         });
         id
     }
@@ -283,6 +316,9 @@ impl SqlSchema {
             table_id,
             index_name,
             tpe: IndexType::PrimaryKey,
+            predicate: None,
+            description: None,
+            tablespace: None,
         });
         id
     }
@@ -294,6 +330,9 @@ impl SqlSchema {
             table_id,
             index_name,
             tpe: IndexType::Unique,
+            predicate: None,
+            description: None,
+            tablespace: None,
         });
         id
     }
@@ -317,6 +356,8 @@ impl SqlSchema {
             referenced_table,
             on_delete_action,
             on_update_action,
+            description: None,
+            match_type: ForeignKeyMatchType::Simple,
         });
         id
     }
@@ -347,6 +388,8 @@ impl SqlSchema {
             name,
             properties: TableProperties::empty(),
             description,
+            default_collation: None,
+            tablespace: None,
         });
 
         id
@@ -371,6 +414,28 @@ impl SqlSchema {
         id
     }
 
+    /// Add a trigger to the schema.
+    pub fn push_trigger(
+        &mut self,
+        table_id: TableId,
+        name: String,
+        timing: String,
+        event: String,
+        definition: String,
+    ) -> TriggerId {
+        let id = TriggerId(self.triggers.len() as u32);
+
+        self.triggers.push(Trigger {
+            table_id,
+            name,
+            timing,
+            event,
+            definition,
+        });
+
+        id
+    }
+
     pub fn push_table_with_properties(
         &mut self,
         name: String,
@@ -385,11 +450,23 @@ impl SqlSchema {
             name,
             properties,
             description,
+            default_collation: None,
+            tablespace: None,
         });
 
         id
     }
 
+    /// MySQL-only: set a table's default collation, as found during introspection.
+    pub fn set_table_default_collation(&mut self, table_id: TableId, collation: String) {
+        self.tables[table_id.0 as usize].default_collation = Some(collation);
+    }
+
+    /// PostgreSQL-only: set the tablespace a table is stored in, as found during introspection.
+    pub fn set_table_tablespace(&mut self, table_id: TableId, tablespace: String) {
+        self.tables[table_id.0 as usize].tablespace = Some(tablespace);
+    }
+
     pub fn namespaces_count(&self) -> usize {
         self.namespaces.len()
     }
@@ -455,6 +532,11 @@ impl SqlSchema {
         (0..self.enums.len()).map(move |enum_index| self.walk(EnumId(enum_index as u32)))
     }
 
+    /// Traverse all the triggers in the schema.
+    pub fn trigger_walkers(&self) -> impl ExactSizeIterator<Item = TriggerWalker<'_>> {
+        (0..self.triggers.len()).map(move |trigger_index| self.walk(TriggerId(trigger_index as u32)))
+    }
+
     pub fn walk_foreign_keys(&self) -> impl Iterator<Item = ForeignKeyWalker<'_>> {
         (0..self.foreign_keys.len()).map(move |fk_idx| ForeignKeyWalker {
             schema: self,
@@ -495,6 +577,10 @@ pub enum TableProperties {
     IsPartition,
     HasSubclass,
     HasRowLevelSecurity,
+    /// Postgres-only: the table is `UNLOGGED`, i.e. its writes skip the WAL. Unlogged tables are
+    /// faster to write to but aren't crash-safe and aren't replicated, so they're typically used
+    /// for throwaway or cache data.
+    Unlogged,
 }
 
 /// A table found in a schema.
@@ -504,6 +590,12 @@ pub struct Table {
     name: String,
     properties: BitFlags<TableProperties>,
     description: Option<String>,
+    /// MySQL-only: the table's default collation, applied to new columns that don't specify
+    /// their own. `None` means the table inherits the database's default.
+    default_collation: Option<String>,
+    /// PostgreSQL-only: the tablespace the table is stored in. `None` means the table lives in
+    /// the database's default tablespace.
+    tablespace: Option<String>,
 }
 
 /// The type of an index.
@@ -553,6 +645,9 @@ pub struct IndexColumn {
     pub column_id: TableColumnId,
     pub sort_order: Option<SQLSortOrder>,
     pub length: Option<u32>,
+    /// The collation the column is indexed with, when it overrides the column's own collation.
+    /// `None` means the column is indexed with its own collation. Only populated on PostgreSQL.
+    pub collation: Option<String>,
 }
 
 /// An index on a table.
@@ -561,6 +656,14 @@ struct Index {
     table_id: TableId,
     index_name: String,
     tpe: IndexType,
+    /// The `WHERE` clause of a partial index, verbatim as it should be rendered in the
+    /// connector's SQL dialect. `None` for a regular, non-partial index.
+    predicate: Option<String>,
+    /// The comment in the database
+    description: Option<String>,
+    /// PostgreSQL-only: the tablespace the index is stored in. `None` means the index lives in
+    /// the database's default tablespace.
+    tablespace: Option<String>,
 }
 
 /// A stored procedure (like, the function inside your database).
@@ -574,6 +677,23 @@ pub struct Procedure {
     pub definition: Option<String>,
 }
 
+/// A trigger defined on a table.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Trigger {
+    /// The table the trigger is defined on.
+    table_id: TableId,
+    /// Trigger name.
+    pub name: String,
+    /// When the trigger fires relative to the event, e.g. `BEFORE`, `AFTER`, `INSTEAD OF`.
+    pub timing: String,
+    /// The event that fires the trigger, e.g. `INSERT`, `UPDATE`, `DELETE`.
+    pub event: String,
+    /// The trigger's defining SQL. Its shape is connector-specific: SQLite and PostgreSQL report
+    /// the full `CREATE TRIGGER` statement verbatim, while MySQL only exposes the action
+    /// statement, without the `CREATE TRIGGER ... ON ... FOR EACH ROW` header.
+    pub definition: String,
+}
+
 /// A user-defined type. Can map to another type, or be declared as assembly.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct UserDefinedType {
@@ -595,6 +715,61 @@ pub struct Column {
     pub auto_increment: bool,
     /// The comment in the database
     pub description: Option<String>,
+    /// If this is a generated column (`GENERATED ALWAYS AS (...)`), whether its value is
+    /// persisted (`STORED`) or computed on read (`VIRTUAL`). `None` for a regular column, or
+    /// when the connector does not introspect this (only PostgreSQL currently does).
+    pub generated_as: Option<GeneratedColumnStorage>,
+    /// The column's TOAST storage strategy (`ALTER COLUMN ... SET STORAGE`), controlling whether
+    /// large values are stored out-of-line and/or compressed. `None` when the connector does not
+    /// introspect this (only PostgreSQL currently does) or the column uses its type's implicit
+    /// default strategy.
+    pub toast_storage: Option<ColumnStorage>,
+    /// The name of this column's `NOT NULL` constraint, if it has one and it was explicitly
+    /// named (`CONSTRAINT <name> NOT NULL` rather than a bare `NOT NULL`). `None` for a nullable
+    /// column, a `NOT NULL` column with an implicit/unnamed constraint, or when the connector
+    /// does not introspect this (only PostgreSQL currently does).
+    pub not_null_constraint_name: Option<String>,
+    /// Whether the column is defined with `ON UPDATE CURRENT_TIMESTAMP`, updating itself to the
+    /// current time whenever the row is updated. Always `false` when the connector does not
+    /// introspect this (only MySQL currently does).
+    pub on_update_now: bool,
+}
+
+/// Whether a generated column's value is persisted to disk or computed on read.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedColumnStorage {
+    /// The value is computed on read and not stored (`GENERATED ALWAYS AS (...) VIRTUAL`).
+    Virtual,
+    /// The value is computed on write and stored (`GENERATED ALWAYS AS (...) STORED`).
+    Stored,
+}
+
+/// A Postgres TOAST storage strategy for a column, as set with `ALTER COLUMN ... SET STORAGE`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnStorage {
+    /// Always stored in-line, uncompressed. Only valid for fixed-length types that are never
+    /// TOASTable in the first place.
+    Plain,
+    /// Stored out-of-line in the TOAST table, uncompressed.
+    External,
+    /// Stored in-line if it fits, compressed, otherwise moved out-of-line to the TOAST table,
+    /// compressed. The default for most TOASTable types.
+    Extended,
+    /// Stored in-line if it fits, uncompressed, otherwise moved out-of-line to the TOAST table,
+    /// uncompressed.
+    Main,
+}
+
+impl ColumnStorage {
+    /// The SQL keyword this strategy is rendered as in `SET STORAGE <keyword>`.
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            ColumnStorage::Plain => "PLAIN",
+            ColumnStorage::External => "EXTERNAL",
+            ColumnStorage::Extended => "EXTENDED",
+            ColumnStorage::Main => "MAIN",
+        }
+    }
 }
 
 /// The type of a column.
@@ -756,6 +931,21 @@ impl ForeignKeyAction {
     }
 }
 
+/// PostgreSQL-only: a foreign key's `MATCH` type, controlling how a composite foreign key handles
+/// rows where some but not all of the constrained columns are `NULL`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum ForeignKeyMatchType {
+    /// `MATCH SIMPLE` (the implicit default when no `MATCH` clause is specified): the constraint
+    /// is satisfied if any constrained column is `NULL`.
+    #[default]
+    Simple,
+    /// `MATCH FULL`: the constraint is satisfied only if all constrained columns are `NULL`, or
+    /// none of them are.
+    Full,
+    /// `MATCH PARTIAL`: part of the SQL standard, but not implemented by PostgreSQL.
+    Partial,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct ForeignKey {
     /// The table the foreign key is defined on.
@@ -766,6 +956,10 @@ struct ForeignKey {
     constraint_name: Option<String>,
     on_delete_action: ForeignKeyAction,
     on_update_action: ForeignKeyAction,
+    /// The comment in the database
+    description: Option<String>,
+    /// PostgreSQL-only: the foreign key's `MATCH` type.
+    match_type: ForeignKeyMatchType,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -396,6 +396,10 @@ impl<'a> SqlSchemaDescriber<'a> {
                 tpe,
                 auto_increment,
                 description: None,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now: false,
             };
 
             match container_id {
@@ -532,6 +536,7 @@ impl<'a> SqlSchemaDescriber<'a> {
                 column_id,
                 sort_order: Some(sort_order),
                 length: None,
+                collation: None,
             });
         }
 
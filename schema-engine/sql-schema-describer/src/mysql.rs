@@ -95,6 +95,7 @@ impl super::SqlSchemaDescriberBackend for SqlSchemaDescriber<'_> {
         push_indexes(&table_names, schema, &mut sql_schema, self.conn).await?;
 
         sql_schema.procedures = self.get_procedures(schema).await?;
+        self.get_triggers(schema, &table_names, &mut sql_schema).await?;
 
         Ok(sql_schema)
     }
@@ -201,6 +202,7 @@ async fn push_indexes(
             column_id,
             sort_order,
             length,
+            collation: None,
         });
     }
 
@@ -280,6 +282,50 @@ impl<'a> SqlSchemaDescriber<'a> {
         Ok(procedures)
     }
 
+    #[tracing::instrument(skip(self))]
+    async fn get_triggers(
+        &self,
+        schema: &str,
+        table_ids: &IndexMap<String, TableId>,
+        sql_schema: &mut SqlSchema,
+    ) -> DescriberResult<()> {
+        // `information_schema.TRIGGERS` does not expose the `CREATE TRIGGER ... FOR EACH ROW`
+        // header, only the action's body, unlike PostgreSQL's `pg_get_triggerdef()` or SQLite's
+        // `sqlite_master.sql`.
+        let sql = indoc! {r#"
+            SELECT
+                TRIGGER_NAME AS trigger_name,
+                EVENT_OBJECT_TABLE AS table_name,
+                ACTION_TIMING AS timing,
+                EVENT_MANIPULATION AS event,
+                ACTION_STATEMENT AS definition
+            FROM information_schema.TRIGGERS
+            WHERE TRIGGER_SCHEMA = ?
+        "#};
+
+        let rows = self.conn.query_raw(sql, &[schema.into()]).await?;
+
+        for row in rows.into_iter() {
+            let table_name = row.get_expect_string("table_name");
+
+            let table_id = if let Some(id) = table_ids.get(table_name.as_str()) {
+                *id
+            } else {
+                continue;
+            };
+
+            sql_schema.push_trigger(
+                table_id,
+                row.get_expect_string("trigger_name"),
+                row.get_expect_string("timing"),
+                row.get_expect_string("event"),
+                row.get_expect_string("definition"),
+            );
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     async fn get_table_names(
         &self,
@@ -291,7 +337,8 @@ impl<'a> SqlSchemaDescriber<'a> {
             SELECT DISTINCT
               BINARY table_info.table_name AS table_name,
               table_info.create_options AS create_options,
-              table_info.table_comment AS table_comment
+              table_info.table_comment AS table_comment,
+              table_info.table_collation AS table_collation
             FROM information_schema.tables AS table_info
             JOIN information_schema.columns AS column_info
                 ON BINARY column_info.table_name = BINARY table_info.table_name
@@ -309,12 +356,13 @@ impl<'a> SqlSchemaDescriber<'a> {
                     .filter(|c| c.as_str() == "partitioned")
                     .is_some(),
                 row.get_string("table_comment").filter(|c| !c.is_empty()),
+                row.get_string("table_collation"),
             )
         });
 
         let mut map = IndexMap::default();
 
-        for (name, is_partition, description) in names {
+        for (name, is_partition, description, collation) in names {
             let cloned_name = name.clone();
             let id = if is_partition {
                 sql_schema.push_table_with_properties(
@@ -326,6 +374,11 @@ impl<'a> SqlSchemaDescriber<'a> {
             } else {
                 sql_schema.push_table(name, Default::default(), description)
             };
+
+            if let Some(collation) = collation {
+                sql_schema.set_table_default_collation(id, collation);
+            }
+
             map.insert(cloned_name, id);
         }
 
@@ -446,6 +499,10 @@ impl<'a> SqlSchemaDescriber<'a> {
             );
             let extra = col.get_expect_string("extra").to_lowercase();
             let auto_increment = matches!(extra.as_str(), "auto_increment");
+            // `extra` can combine several keywords separated by spaces (e.g.
+            // `DEFAULT_GENERATED on update CURRENT_TIMESTAMP`), so this has to be a substring
+            // check rather than an exact match like the one above.
+            let on_update_now = extra.contains("on update current_timestamp");
 
             let default = match default_value {
                 None => None,
@@ -557,6 +614,10 @@ impl<'a> SqlSchemaDescriber<'a> {
                 tpe,
                 auto_increment,
                 description,
+                generated_as: None,
+                toast_storage: None,
+                not_null_constraint_name: None,
+                on_update_now,
             };
 
             match container_id {
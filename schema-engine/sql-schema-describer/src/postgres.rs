@@ -1,9 +1,11 @@
 //! Postgres schema description.
 
 mod default;
+mod domains;
 mod extensions;
 
 use either::Either;
+pub use domains::{Domain, DomainId, DomainWalker};
 pub use extensions::{DatabaseExtension, ExtensionId, ExtensionWalker};
 
 use self::default::get_default_value;
@@ -144,6 +146,35 @@ pub enum ConstraintOption {
     Deferrable,
 }
 
+/// The command(s) a row level security policy applies to. Mirrors `pg_policy.polcmd`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyCommand {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    All,
+}
+
+/// The identifier of a [`Policy`] in [`PostgresSchemaExt::policies`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PolicyId(pub(crate) u32);
+
+/// A row level security policy, as described by `pg_policy`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    pub table_id: TableId,
+    pub name: String,
+    pub permissive: bool,
+    pub command: PolicyCommand,
+    /// The roles the policy applies to, or `["public"]` if it applies to every role.
+    pub roles: Vec<String>,
+    /// The `USING` expression, absent for policies that only restrict `INSERT`.
+    pub using: Option<String>,
+    /// The `WITH CHECK` expression, absent for policies that only restrict `SELECT`/`DELETE`.
+    pub with_check: Option<String>,
+}
+
 #[derive(Default, Debug)]
 pub struct PostgresSchemaExt {
     pub opclasses: Vec<(IndexColumnId, SQLOperatorClass)>,
@@ -152,11 +183,20 @@ pub struct PostgresSchemaExt {
     pub index_null_position: HashMap<IndexColumnId, IndexNullPosition>,
     pub constraint_options: HashMap<Constraint, BitFlags<ConstraintOption>>,
     pub table_options: Vec<BTreeMap<String, String>>,
-    pub exclude_constraints: Vec<(TableId, String)>,
+    /// Table, constraint name, `EXCLUDE` constraint definition (e.g. `EXCLUDE USING gist (...)`).
+    pub exclude_constraints: Vec<(TableId, String, String)>,
     /// The schema's sequences.
     pub sequences: Vec<Sequence>,
     /// The extensions included in the schema(s).
     extensions: Vec<DatabaseExtension>,
+    /// The domains included in the schema(s).
+    domains: Vec<Domain>,
+    /// `(child, parent)` pairs recorded from Postgres table inheritance (`INHERITS`), sorted by
+    /// the child's `table_id`. Partition children are excluded, since they are already tracked
+    /// separately through `TableProperties::IsPartition`/`HasSubclass`.
+    pub table_inheritance: Vec<(TableId, TableId)>,
+    /// The row level security policies included in the schema(s), sorted by `table_id`.
+    pub policies: Vec<Policy>,
 }
 
 impl PostgresSchemaExt {
@@ -206,6 +246,25 @@ impl PostgresSchemaExt {
         self.extensions.clear();
     }
 
+    pub fn domain_walkers(&self) -> impl Iterator<Item = DomainWalker<'_>> {
+        (0..self.domains.len()).map(move |idx| DomainWalker {
+            schema_ext: self,
+            id: DomainId(idx as u32),
+        })
+    }
+
+    pub fn domain_walker<'a>(&'a self, name: &str) -> Option<DomainWalker<'a>> {
+        self.domain_walkers().find(|domain| domain.name() == name)
+    }
+
+    pub fn push_domain(&mut self, domain: Domain) {
+        self.domains.push(domain);
+    }
+
+    pub fn get_domain(&self, id: DomainId) -> &Domain {
+        &self.domains[id.0 as usize]
+    }
+
     pub fn non_default_null_position(&self, column: IndexColumnWalker<'_>) -> bool {
         let position = self.index_null_position.get(&column.id);
 
@@ -252,17 +311,52 @@ impl PostgresSchemaExt {
     }
 
     pub fn exclude_constraints(&self, table_id: TableId) -> impl ExactSizeIterator<Item = &str> {
-        let low = self.exclude_constraints.partition_point(|(id, _)| *id < table_id);
-        let high = self.exclude_constraints[low..].partition_point(|(id, _)| *id <= table_id);
+        let low = self.exclude_constraints.partition_point(|(id, _, _)| *id < table_id);
+        let high = self.exclude_constraints[low..].partition_point(|(id, _, _)| *id <= table_id);
+
+        self.exclude_constraints[low..low + high]
+            .iter()
+            .map(|(_, name, _)| name.as_str())
+    }
+
+    /// The parent tables `table_id` directly inherits from via Postgres `INHERITS`.
+    pub fn parent_tables(&self, table_id: TableId) -> impl Iterator<Item = TableId> + '_ {
+        let low = self.table_inheritance.partition_point(|(child, _)| *child < table_id);
+        let high = self.table_inheritance[low..].partition_point(|(child, _)| *child <= table_id);
+
+        self.table_inheritance[low..low + high].iter().map(|(_, parent)| *parent)
+    }
+
+    /// The row level security policies defined on `table_id`, along with their ids.
+    pub fn table_policies(&self, table_id: TableId) -> impl Iterator<Item = (PolicyId, &Policy)> {
+        let low = self.policies.partition_point(|p| p.table_id < table_id);
+        let high = self.policies[low..].partition_point(|p| p.table_id <= table_id);
+
+        self.policies[low..low + high]
+            .iter()
+            .enumerate()
+            .map(move |(i, policy)| (PolicyId((low + i) as u32), policy))
+    }
+
+    /// The policy referred to by `id`.
+    pub fn get_policy(&self, id: PolicyId) -> &Policy {
+        &self.policies[id.0 as usize]
+    }
+
+    /// Like [`Self::exclude_constraints`], but also returns each constraint's definition (e.g.
+    /// `EXCLUDE USING gist (...)`), as needed to render `ADD CONSTRAINT` steps.
+    pub fn exclude_constraints_with_definitions(&self, table_id: TableId) -> impl Iterator<Item = (&str, &str)> {
+        let low = self.exclude_constraints.partition_point(|(id, _, _)| *id < table_id);
+        let high = self.exclude_constraints[low..].partition_point(|(id, _, _)| *id <= table_id);
 
         self.exclude_constraints[low..low + high]
             .iter()
-            .map(|(_, name)| name.as_str())
+            .map(|(_, name, definition)| (name.as_str(), definition.as_str()))
     }
 
     pub fn uses_exclude_constraint(&self, id: TableId) -> bool {
         self.exclude_constraints
-            .binary_search_by_key(&id, |(id, _)| *id)
+            .binary_search_by_key(&id, |(id, _, _)| *id)
             .is_ok()
     }
 }
@@ -559,6 +653,9 @@ impl<'a> super::SqlSchemaDescriberBackend for SqlSchemaDescriber<'a> {
         //TODO(matthias) can we get rid of the table names map and instead just use tablewalker_ns everywhere like in get_columns?
         let table_names = self.get_table_names(&mut sql_schema, &mut pg_ext).await?;
 
+        self.get_table_inheritance(&table_names, &sql_schema, &mut pg_ext)
+            .await?;
+
         // order matters
         self.get_constraints(&table_names, &mut sql_schema, &mut pg_ext).await?;
         self.get_views(&mut sql_schema).await?;
@@ -569,7 +666,10 @@ impl<'a> super::SqlSchemaDescriberBackend for SqlSchemaDescriber<'a> {
         self.get_indices(&table_names, &mut pg_ext, &mut sql_schema).await?;
 
         self.get_procedures(&mut sql_schema).await?;
+        self.get_triggers(&table_names, &mut sql_schema).await?;
         self.get_extensions(&mut pg_ext).await?;
+        self.get_domains(schemas, &mut pg_ext).await?;
+        self.get_policies(&table_names, &sql_schema, &mut pg_ext).await?;
 
         //Todo(matthias) understand this
         self.get_sequences(&sql_schema, &mut pg_ext).await?;
@@ -632,6 +732,103 @@ impl<'a> SqlSchemaDescriber<'a> {
         Ok(())
     }
 
+    async fn get_domains(&self, schemas: &[&str], pg_ext: &mut PostgresSchemaExt) -> DescriberResult<()> {
+        // CockroachDB does not support domains.
+        if self.is_cockroach() {
+            return Ok(());
+        }
+
+        let sql = indoc! {r#"
+            SELECT
+                t.typname AS domain_name,
+                n.nspname AS domain_schema,
+                format_type(t.typbasetype, t.typtypmod) AS base_type,
+                t.typnotnull AS not_null,
+                t.typdefault AS default_value,
+                (
+                    SELECT pg_get_constraintdef(c.oid)
+                    FROM pg_constraint c
+                    WHERE c.contypid = t.oid AND c.contype = 'c'
+                    ORDER BY c.oid
+                    LIMIT 1
+                ) AS check_definition
+            FROM pg_type t
+            INNER JOIN pg_namespace n ON t.typnamespace = n.oid
+            WHERE t.typtype = 'd' AND n.nspname = ANY ( $1 )
+            ORDER BY t.typname ASC
+        "#};
+
+        let rows = self.conn.query_raw(sql, &[Value::array(schemas)]).await?;
+        let mut domains = Vec::with_capacity(rows.len());
+
+        for row in rows.into_iter() {
+            domains.push(Domain {
+                name: row.get_expect_string("domain_name"),
+                schema: row.get_expect_string("domain_schema"),
+                base_type: row.get_expect_string("base_type"),
+                not_null: row.get_expect_bool("not_null"),
+                default: row.get_string("default_value"),
+                check: row.get_string("check_definition").map(|check| {
+                    check
+                        .strip_prefix("CHECK (")
+                        .and_then(|check| check.strip_suffix(')'))
+                        .map(str::to_owned)
+                        .unwrap_or(check)
+                }),
+            });
+        }
+
+        pg_ext.domains = domains;
+
+        Ok(())
+    }
+
+    async fn get_policies(
+        &self,
+        table_names: &IndexMap<(String, String), TableId>,
+        sql_schema: &SqlSchema,
+        pg_ext: &mut PostgresSchemaExt,
+    ) -> DescriberResult<()> {
+        let namespaces = &sql_schema.namespaces;
+        let sql = include_str!("postgres/policies_query.sql");
+        let rows = self.conn.query_raw(sql, &[Value::array(namespaces)]).await?;
+
+        let mut policies = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            let namespace = row.get_expect_string("namespace");
+            let table_name = row.get_expect_string("table_name");
+
+            let table_id = match table_names.get(&(namespace, table_name)) {
+                Some(id) => *id,
+                None => continue,
+            };
+
+            let command = match row.get_expect_char("command") {
+                'r' => PolicyCommand::Select,
+                'a' => PolicyCommand::Insert,
+                'w' => PolicyCommand::Update,
+                'd' => PolicyCommand::Delete,
+                _ => PolicyCommand::All,
+            };
+
+            policies.push(Policy {
+                table_id,
+                name: row.get_expect_string("policy_name"),
+                permissive: row.get_expect_bool("is_permissive"),
+                command,
+                roles: row.get_string_array("roles").unwrap_or_default(),
+                using: row.get_string("using_expression"),
+                with_check: row.get_string("with_check_expression"),
+            });
+        }
+
+        policies.sort_by(|a, b| a.table_id.cmp(&b.table_id).then_with(|| a.name.cmp(&b.name)));
+        pg_ext.policies = policies;
+
+        Ok(())
+    }
+
     async fn get_databases(&self) -> DescriberResult<Vec<String>> {
         let sql = "select schema_name from information_schema.schemata;";
         let rows = self.conn.query_raw(sql, &[]).await?;
@@ -682,6 +879,66 @@ impl<'a> SqlSchemaDescriber<'a> {
         Ok(())
     }
 
+    async fn get_triggers(
+        &self,
+        table_names: &IndexMap<(String, String), TableId>,
+        sql_schema: &mut SqlSchema,
+    ) -> DescriberResult<()> {
+        // CockroachDB does not support triggers.
+        if self.is_cockroach() {
+            return Ok(());
+        }
+
+        // A trigger can fire on more than one event (e.g. `INSERT OR UPDATE`), in which case
+        // `tgtype` has more than one event bit set. We only surface one event per trigger, since
+        // the differ only needs enough to detect a change and decide to drop and recreate.
+        let sql = indoc! {r#"
+            SELECT
+                tg.tgname AS trigger_name,
+                n.nspname AS namespace,
+                c.relname AS table_name,
+                CASE
+                    WHEN tg.tgtype & 64 <> 0 THEN 'INSTEAD OF'
+                    WHEN tg.tgtype & 2 <> 0 THEN 'BEFORE'
+                    ELSE 'AFTER'
+                END AS timing,
+                CASE
+                    WHEN tg.tgtype & 4 <> 0 THEN 'INSERT'
+                    WHEN tg.tgtype & 8 <> 0 THEN 'DELETE'
+                    WHEN tg.tgtype & 16 <> 0 THEN 'UPDATE'
+                    ELSE 'TRUNCATE'
+                END AS event,
+                pg_get_triggerdef(tg.oid) AS definition
+            FROM pg_trigger tg
+            INNER JOIN pg_class c ON tg.tgrelid = c.oid
+            INNER JOIN pg_namespace n ON c.relnamespace = n.oid
+            WHERE NOT tg.tgisinternal
+            ORDER BY n.nspname ASC, c.relname ASC, tg.tgname ASC
+        "#};
+
+        let rows = self.conn.query_raw(sql, &[]).await?;
+
+        for row in rows.into_iter() {
+            let namespace = row.get_expect_string("namespace");
+            let table_name = row.get_expect_string("table_name");
+
+            let table_id = match table_names.get(&(namespace, table_name)) {
+                Some(id) => *id,
+                None => continue,
+            };
+
+            sql_schema.push_trigger(
+                table_id,
+                row.get_expect_string("trigger_name"),
+                row.get_expect_string("timing"),
+                row.get_expect_string("event"),
+                row.get_expect_string("definition"),
+            );
+        }
+
+        Ok(())
+    }
+
     async fn get_namespaces(&self, sql_schema: &mut SqlSchema, namespaces: &[&str]) -> DescriberResult<()> {
         let sql = include_str!("postgres/namespaces_query.sql");
 
@@ -733,7 +990,9 @@ impl<'a> SqlSchemaDescriber<'a> {
                 row.get_expect_bool("is_partition"),
                 row.get_expect_bool("has_subclass"),
                 row.get_expect_bool("has_row_level_security"),
+                row.get_expect_bool("is_unlogged"),
                 row.get_string("description"),
+                row.get_string("tablespace"),
             ));
 
             pg_ext.table_options.push(options);
@@ -741,7 +1000,17 @@ impl<'a> SqlSchemaDescriber<'a> {
 
         let mut map = IndexMap::default();
 
-        for (table_name, namespace, is_partition, has_subclass, has_row_level_security, description) in names {
+        for (
+            table_name,
+            namespace,
+            is_partition,
+            has_subclass,
+            has_row_level_security,
+            is_unlogged,
+            description,
+            tablespace,
+        ) in names
+        {
             let cloned_name = table_name.clone();
 
             let partition = if is_partition {
@@ -760,6 +1029,11 @@ impl<'a> SqlSchemaDescriber<'a> {
             } else {
                 BitFlags::empty()
             };
+            let unlogged = if is_unlogged {
+                BitFlags::from_flag(TableProperties::Unlogged)
+            } else {
+                BitFlags::empty()
+            };
 
             let constraints_key = (namespace.clone(), cloned_name);
 
@@ -770,16 +1044,58 @@ impl<'a> SqlSchemaDescriber<'a> {
             let id = sql_schema.push_table_with_properties(
                 table_name,
                 sql_schema.get_namespace_id(&namespace).unwrap(),
-                partition | subclass | row_level_security,
+                partition | subclass | row_level_security | unlogged,
                 description,
             );
 
+            if let Some(tablespace) = tablespace {
+                sql_schema.set_table_tablespace(id, tablespace);
+            }
+
             map.insert(constraints_key, id);
         }
 
         Ok(map)
     }
 
+    async fn get_table_inheritance(
+        &self,
+        table_names: &IndexMap<(String, String), TableId>,
+        sql_schema: &SqlSchema,
+        pg_ext: &mut PostgresSchemaExt,
+    ) -> DescriberResult<()> {
+        // CockroachDB does not support table inheritance.
+        if self.is_cockroach() {
+            return Ok(());
+        }
+
+        let sql = include_str!("postgres/table_inheritance_query.sql");
+        let namespaces = &sql_schema.namespaces;
+
+        let rows = self.conn.query_raw(sql, &[Value::array(namespaces)]).await?;
+
+        for row in rows.into_iter() {
+            let child_key = (row.get_expect_string("child_namespace"), row.get_expect_string("child_name"));
+            let parent_key = (
+                row.get_expect_string("parent_namespace"),
+                row.get_expect_string("parent_name"),
+            );
+
+            // The parent or child could be a table we filtered out (e.g. it belongs to a
+            // namespace we are not introspecting).
+            let (Some(&child_id), Some(&parent_id)) = (table_names.get(&child_key), table_names.get(&parent_key))
+            else {
+                continue;
+            };
+
+            pg_ext.table_inheritance.push((child_id, parent_id));
+        }
+
+        pg_ext.table_inheritance.sort_by_key(|(child, _)| *child);
+
+        Ok(())
+    }
+
     async fn get_size(&self, schema: &str) -> DescriberResult<usize> {
         if self.circumstances.contains(Circumstances::Cockroach) {
             return Ok(0); // TODO
@@ -857,6 +1173,9 @@ impl<'a> SqlSchemaDescriber<'a> {
                 info.is_nullable,
                 info.is_identity,
                 info.character_maximum_length,
+                att.attgenerated AS generated_as,
+                att.attstorage AS storage,
+                nncon.conname AS not_null_constraint_name,
                 col_description(att.attrelid, ordinal_position) AS description
             FROM information_schema.columns info
             JOIN pg_attribute att ON att.attname = info.column_name
@@ -866,10 +1185,13 @@ impl<'a> SqlSchemaDescriber<'a> {
                  JOIN pg_namespace on pg_namespace.oid = pg_class.relnamespace
                  AND pg_namespace.nspname = ANY ( $1 )
                  WHERE reltype > 0
-                ) as oid on oid.oid = att.attrelid 
+                ) as oid on oid.oid = att.attrelid
                   AND relname = info.table_name
                   AND namespace = info.table_schema
             LEFT OUTER JOIN pg_attrdef attdef ON attdef.adrelid = att.attrelid AND attdef.adnum = att.attnum AND table_schema = namespace
+            LEFT OUTER JOIN pg_constraint nncon ON nncon.contype = 'n'
+                  AND nncon.conrelid = att.attrelid
+                  AND nncon.conkey = ARRAY[att.attnum]
             WHERE table_schema = ANY ( $1 ) {is_visible_clause}
             ORDER BY namespace, table_name, ordinal_position;
         "#
@@ -916,6 +1238,22 @@ impl<'a> SqlSchemaDescriber<'a> {
 
             let description = col.get_string("description");
 
+            let generated_as = match col.get_string("generated_as").as_deref() {
+                Some("s") => Some(GeneratedColumnStorage::Stored),
+                Some("v") => Some(GeneratedColumnStorage::Virtual),
+                _ => None,
+            };
+
+            let toast_storage = match col.get_string("storage").as_deref() {
+                Some("p") => Some(ColumnStorage::Plain),
+                Some("e") => Some(ColumnStorage::External),
+                Some("x") => Some(ColumnStorage::Extended),
+                Some("m") => Some(ColumnStorage::Main),
+                _ => None,
+            };
+
+            let not_null_constraint_name = col.get_string("not_null_constraint_name");
+
             let auto_increment = is_identity
                 || matches!(default.as_ref().map(|d| &d.kind), Some(DefaultKind::Sequence(_)))
                 || (self.is_cockroach()
@@ -938,6 +1276,10 @@ impl<'a> SqlSchemaDescriber<'a> {
                 tpe,
                 auto_increment,
                 description,
+                generated_as,
+                toast_storage,
+                not_null_constraint_name,
+                on_update_now: false,
             };
 
             match container_id {
@@ -1052,15 +1394,17 @@ impl<'a> SqlSchemaDescriber<'a> {
                 att.attname     AS "parent_column",
                 con.confdeltype,
                 con.confupdtype,
+                con.confmatchtype,
                 rel_ns.nspname  AS "referenced_schema_name",
                 conname         AS constraint_name,
                 child,
                 parent,
-                table_name, 
+                table_name,
                 namespace,
                 condeferrable,
-                condeferred
-            FROM (SELECT 
+                condeferred,
+                obj_description(con.oid, 'pg_constraint') AS description
+            FROM (SELECT
                         ns.nspname AS "namespace",
                         unnest(con1.conkey)                AS "parent",
                         unnest(con1.confkey)                AS "child",
@@ -1073,6 +1417,7 @@ impl<'a> SqlSchemaDescriber<'a> {
                         con1.conname,
                         con1.confdeltype,
                         con1.confupdtype,
+                        con1.confmatchtype,
                         con1.condeferrable                  AS condeferrable,
                         con1.condeferred                    AS condeferred
                 FROM pg_class cl
@@ -1175,6 +1520,17 @@ impl<'a> SqlSchemaDescriber<'a> {
                 _ => panic!("unrecognized foreign key action (on update) '{confupdtype}'"),
             };
 
+            let confmatchtype = row
+                .get_char("confmatchtype")
+                .unwrap_or_else(|| row.get_expect_string("confmatchtype").chars().next().unwrap());
+
+            let match_type = match confmatchtype {
+                's' => ForeignKeyMatchType::Simple,
+                'f' => ForeignKeyMatchType::Full,
+                'p' => ForeignKeyMatchType::Partial,
+                _ => panic!("unrecognized foreign key match type '{confmatchtype}'"),
+            };
+
             match current_fk {
                 Some((current_oid, _)) if current_oid == id => (),
                 None | Some(_) => {
@@ -1198,6 +1554,12 @@ impl<'a> SqlSchemaDescriber<'a> {
                         .constraint_options
                         .insert(Constraint::ForeignKey(fkid), constraint_options);
 
+                    if let Some(description) = row.get_string("description") {
+                        sql_schema.set_foreign_key_description(fkid, description);
+                    }
+
+                    sql_schema.set_foreign_key_match_type(fkid, match_type);
+
                     current_fk = Some((id, fkid));
                 }
             }
@@ -1228,6 +1590,7 @@ impl<'a> SqlSchemaDescriber<'a> {
             let table_name = row.get_expect_string("table_name");
             let constraint_name = row.get_expect_string("constraint_name");
             let constraint_type = row.get_expect_char("constraint_type");
+            let constraint_definition = row.get_expect_string("constraint_definition");
 
             let table_id = match table_names.get(&(namespace, table_name)) {
                 Some(id) => *id,
@@ -1239,14 +1602,16 @@ impl<'a> SqlSchemaDescriber<'a> {
                     sql_schema.check_constraints.push((table_id, constraint_name));
                 }
                 'x' => {
-                    pg_ext.exclude_constraints.push((table_id, constraint_name));
+                    pg_ext
+                        .exclude_constraints
+                        .push((table_id, constraint_name, constraint_definition));
                 }
                 _ => (),
             }
         }
 
         sql_schema.check_constraints.sort_by_key(|(id, _)| *id);
-        pg_ext.exclude_constraints.sort_by_key(|(id, _)| *id);
+        pg_ext.exclude_constraints.sort_by_key(|(id, _, _)| *id);
 
         Ok(())
     }
@@ -1491,6 +1856,18 @@ fn index_from_row(
                     .insert(Constraint::Index(index_id), constraint_options);
             }
 
+            if let Some(predicate) = row.get_string("predicate") {
+                sql_schema.set_index_predicate(index_id, predicate);
+            }
+
+            if let Some(description) = row.get_string("description") {
+                sql_schema.set_index_description(index_id, description);
+            }
+
+            if let Some(tablespace) = row.get_string("tablespace") {
+                sql_schema.set_index_tablespace(index_id, tablespace);
+            }
+
             current_index = Some(index_id);
         }
 
@@ -1506,11 +1883,14 @@ fn index_from_row(
             None
         };
 
+        let collation = row.get_string("collation");
+
         let index_field_id = sql_schema.push_index_column(IndexColumn {
             index_id,
             column_id,
             sort_order,
             length: None,
+            collation,
         });
 
         pg_ext.indexes.push((index_id, algorithm));
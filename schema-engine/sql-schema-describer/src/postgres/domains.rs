@@ -0,0 +1,62 @@
+use super::PostgresSchemaExt;
+
+/// A Postgres domain: a base type constrained by a `NOT NULL` flag, a default, and/or a `CHECK`
+/// expression. <https://www.postgresql.org/docs/current/sql-createdomain.html>
+#[derive(Debug, Clone)]
+pub struct Domain {
+    pub name: String,
+    pub schema: String,
+    /// The rendered base type, e.g. `integer` or `character varying(255)`.
+    pub base_type: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+    /// The `CHECK` expression, if any, without the `CHECK (...)` wrapper.
+    pub check: Option<String>,
+}
+
+/// The identifier for a domain in a Postgres database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DomainId(pub(crate) u32);
+
+/// Traverse a domain.
+#[derive(Clone, Copy)]
+pub struct DomainWalker<'a> {
+    pub id: DomainId,
+    pub(super) schema_ext: &'a PostgresSchemaExt,
+}
+
+impl<'a> DomainWalker<'a> {
+    /// The name of the domain.
+    pub fn name(self) -> &'a str {
+        &self.domain().name
+    }
+
+    /// The schema the domain is defined in.
+    pub fn schema(self) -> &'a str {
+        &self.domain().schema
+    }
+
+    /// The rendered base type, e.g. `integer` or `character varying(255)`.
+    pub fn base_type(self) -> &'a str {
+        &self.domain().base_type
+    }
+
+    /// Whether the domain disallows `NULL` values.
+    pub fn not_null(self) -> bool {
+        self.domain().not_null
+    }
+
+    /// The default expression, if any.
+    pub fn default(self) -> Option<&'a str> {
+        self.domain().default.as_deref()
+    }
+
+    /// The `CHECK` expression, if any, without the `CHECK (...)` wrapper.
+    pub fn check(self) -> Option<&'a str> {
+        self.domain().check.as_deref()
+    }
+
+    fn domain(self) -> &'a Domain {
+        &self.schema_ext.domains[self.id.0 as usize]
+    }
+}
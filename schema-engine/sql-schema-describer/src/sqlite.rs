@@ -15,6 +15,31 @@ use quaint::{
 use std::{any::type_name, borrow::Cow, collections::BTreeMap, convert::TryInto, fmt::Debug, path::Path};
 use tracing::trace;
 
+/// SQLite-specific schema information that doesn't fit into the generic [`SqlSchema`] shape,
+/// stored in [`SqlSchema::connector_data`](crate::SqlSchema) and accessed through
+/// [`SqlSchema::downcast_connector_data`](crate::SqlSchema::downcast_connector_data).
+#[derive(Debug, Default)]
+pub struct SqliteSchemaExt {
+    /// The primary key columns whose `CREATE TABLE` text literally spells out the
+    /// `AUTOINCREMENT` keyword, as opposed to merely being a rowid-alias integer primary key.
+    /// See <https://www.sqlite.org/autoinc.html>.
+    pub autoincrement_columns: std::collections::HashSet<TableColumnId>,
+    /// The tables whose `CREATE TABLE` text spells out the `WITHOUT ROWID` table option. Such a
+    /// table has no hidden rowid column: its primary key is used directly as the clustering key.
+    /// See <https://www.sqlite.org/withoutrowid.html>.
+    pub without_rowid_tables: std::collections::HashSet<TableId>,
+}
+
+impl SqliteSchemaExt {
+    pub fn column_has_autoincrement(&self, id: TableColumnId) -> bool {
+        self.autoincrement_columns.contains(&id)
+    }
+
+    pub fn table_is_without_rowid(&self, id: TableId) -> bool {
+        self.without_rowid_tables.contains(&id)
+    }
+}
+
 #[async_trait::async_trait]
 pub trait Connection {
     async fn query_raw<'a>(
@@ -101,17 +126,30 @@ impl<'a> SqlSchemaDescriber<'a> {
 
     pub async fn describe_impl(&self) -> DescriberResult<SqlSchema> {
         let mut schema = SqlSchema::default();
+        let mut sqlite_ext = SqliteSchemaExt::default();
         let container_ids = self.get_table_names(&mut schema).await?;
         let table_ids: IndexMap<&str, TableId> = container_ids
             .iter()
-            .filter_map(|(name, id)| id.left().map(|id| (name.as_str(), id)))
+            .filter_map(|(name, (id, _))| id.left().map(|id| (name.as_str(), id)))
             .collect();
 
-        for (container_name, container_id) in &container_ids {
-            push_columns(container_name, *container_id, &mut schema, self.conn).await?;
+        for (container_name, (container_id, definition)) in &container_ids {
+            push_columns(
+                container_name,
+                *container_id,
+                definition.as_deref(),
+                &mut schema,
+                &mut sqlite_ext,
+                self.conn,
+            )
+            .await?;
 
             if let Either::Left(table_id) = container_id {
                 push_indexes(container_name, *table_id, &mut schema, self.conn).await?;
+
+                if definition.as_deref().is_some_and(|definition| WITHOUT_ROWID_RE.is_match(definition)) {
+                    sqlite_ext.without_rowid_tables.insert(*table_id);
+                }
             }
         }
 
@@ -120,6 +158,12 @@ impl<'a> SqlSchemaDescriber<'a> {
                 .await?;
         }
 
+        push_triggers(&table_ids, &mut schema, self.conn).await?;
+
+        schema.connector_data = crate::connector_data::ConnectorData {
+            data: Some(Box::new(sqlite_ext)),
+        };
+
         Ok(schema)
     }
 
@@ -148,7 +192,7 @@ impl<'a> SqlSchemaDescriber<'a> {
     async fn get_table_names(
         &self,
         schema: &mut SqlSchema,
-    ) -> DescriberResult<IndexMap<String, Either<TableId, ViewId>>> {
+    ) -> DescriberResult<IndexMap<String, (Either<TableId, ViewId>, Option<String>)>> {
         let sql = r#"SELECT name, type, sql FROM sqlite_master WHERE type='table' OR type='view' ORDER BY name ASC"#;
 
         let result_set = self.conn.query_raw(sql, &[]).await?;
@@ -172,11 +216,16 @@ impl<'a> SqlSchemaDescriber<'a> {
             match r#type.as_str() {
                 "table" => {
                     let id = schema.push_table(name, Default::default(), None);
-                    map.insert(cloned_name, Either::Left(id));
+
+                    if let Some(definition) = &definition {
+                        push_check_constraints(id, definition, schema);
+                    }
+
+                    map.insert(cloned_name, (Either::Left(id), definition));
                 }
                 "view" => {
-                    let id = schema.push_view(name, Default::default(), definition, None);
-                    map.insert(cloned_name, Either::Right(id));
+                    let id = schema.push_view(name, Default::default(), definition.clone(), None);
+                    map.insert(cloned_name, (Either::Right(id), definition));
                 }
                 _ => unreachable!(),
             }
@@ -321,10 +370,20 @@ impl<'a> SqlSchemaDescriber<'a> {
     }
 }
 
+/// Matches the literal `AUTOINCREMENT` keyword in a `CREATE TABLE` statement. SQLite only allows
+/// it on a single-column `INTEGER PRIMARY KEY`, so a match anywhere in the table's definition
+/// unambiguously applies to that column.
+static AUTOINCREMENT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)\bAUTOINCREMENT\b"#).unwrap());
+
+/// Matches the `WITHOUT ROWID` table option at the end of a `CREATE TABLE` statement.
+static WITHOUT_ROWID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)\)\s*WITHOUT\s+ROWID\s*;?\s*$"#).unwrap());
+
 async fn push_columns(
     table_name: &str,
     container_id: Either<TableId, ViewId>,
+    definition: Option<&str>,
     schema: &mut SqlSchema,
+    sqlite_ext: &mut SqliteSchemaExt,
     conn: &(dyn Connection + Send + Sync),
 ) -> DescriberResult<()> {
     let sql = format!(r#"PRAGMA table_info ("{table_name}")"#);
@@ -371,23 +430,41 @@ async fn push_columns(
                             Some(float_value) => DefaultValue::value(float_value),
                             None => DefaultValue::db_generated(default_string),
                         },
+                        // Booleans have no dedicated SQLite storage class, so drivers and
+                        // hand-written schemas spell them as `1`/`0`, `true`/`false`, or the
+                        // quoted Postgres-ism `'t'`/`'f'`. Canonicalize all of them to the same
+                        // `PrismaValue::Boolean` so equivalent defaults don't churn in the differ.
                         ColumnTypeFamily::Boolean => match SqlSchemaDescriber::parse_int(&default_string) {
                             Some(PrismaValue::Int(1)) => DefaultValue::value(true),
                             Some(PrismaValue::Int(0)) => DefaultValue::value(false),
                             _ => match SqlSchemaDescriber::parse_bool(&default_string) {
                                 Some(bool_value) => DefaultValue::value(bool_value),
-                                None => DefaultValue::db_generated(default_string),
+                                None => match unquote_sqlite_string_default(&default_string).to_lowercase().as_str() {
+                                    "t" => DefaultValue::value(true),
+                                    "f" => DefaultValue::value(false),
+                                    _ => DefaultValue::db_generated(default_string),
+                                },
                             },
                         },
                         ColumnTypeFamily::String => {
-                            DefaultValue::value(unquote_sqlite_string_default(&default_string).into_owned())
+                            // A parenthesized default (e.g. `DEFAULT (datetime('now'))`) is an
+                            // expression, not a string literal, even though it contains no
+                            // surrounding quotes. Only unquote and treat as a literal value when
+                            // the default is an actual quoted string.
+                            if is_sqlite_expression_default(&default_string) {
+                                DefaultValue::db_generated(default_string)
+                            } else {
+                                DefaultValue::value(unquote_sqlite_string_default(&default_string).into_owned())
+                            }
                         }
-                        ColumnTypeFamily::DateTime => match default_string.to_lowercase().as_str() {
-                            "current_timestamp" | "datetime(\'now\')" | "datetime(\'now\', \'localtime\')" => {
-                                DefaultValue::now()
+                        ColumnTypeFamily::DateTime => {
+                            match strip_sqlite_expression_parens(&default_string).to_lowercase().as_str() {
+                                "current_timestamp" | "datetime('now')" | "datetime('now', 'localtime')" => {
+                                    DefaultValue::now()
+                                }
+                                _ => DefaultValue::db_generated(default_string),
                             }
-                            _ => DefaultValue::db_generated(default_string),
-                        },
+                        }
                         ColumnTypeFamily::Binary => DefaultValue::db_generated(default_string),
                         ColumnTypeFamily::Json => DefaultValue::db_generated(default_string),
                         ColumnTypeFamily::Uuid => DefaultValue::db_generated(default_string),
@@ -404,6 +481,10 @@ async fn push_columns(
             tpe,
             auto_increment: false,
             description: None,
+            generated_as: None,
+            toast_storage: None,
+            not_null_constraint_name: None,
+            on_update_now: false,
         };
 
         match container_id {
@@ -438,6 +519,7 @@ async fn push_columns(
                     column_id: *column_id,
                     sort_order: None,
                     length: None,
+                    collation: None,
                 });
             }
 
@@ -449,6 +531,16 @@ async fn push_columns(
                 if pk_col.1.tpe.full_data_type.eq_ignore_ascii_case("INTEGER") {
                     pk_col.1.auto_increment = true;
                     pk_col.1.tpe.arity = ColumnArity::Required;
+
+                    // `auto_increment` above only tells us that this column is a rowid alias,
+                    // which is true of every single-column `INTEGER PRIMARY KEY`. Whether the
+                    // `CREATE TABLE` text spells out the `AUTOINCREMENT` keyword is a separate
+                    // fact: it changes how SQLite picks the next rowid (never reusing one from a
+                    // deleted row, via the `sqlite_sequence` table) but can't be detected from
+                    // `PRAGMA table_info`, so we have to pattern-match the stored SQL text.
+                    if definition.is_some_and(|definition| AUTOINCREMENT_RE.is_match(definition)) {
+                        sqlite_ext.autoincrement_columns.insert(pk_col_id);
+                    }
                 }
             }
         }
@@ -538,6 +630,7 @@ async fn push_indexes(
                 column_id,
                 sort_order: Some(sort_order),
                 length: None,
+                collation: None,
             });
         }
     }
@@ -593,6 +686,25 @@ fn get_column_type(mut tpe: String, arity: ColumnArity) -> ColumnType {
 // using the backslash character are not supported because they are not standard SQL."
 //
 // - https://www.sqlite.org/lang_expr.html
+/// Returns whether a raw SQLite default expression is parenthesized, which is how SQLite
+/// represents any default that isn't a bare literal value (e.g. `DEFAULT (datetime('now'))`).
+fn is_sqlite_expression_default(s: &str) -> bool {
+    let trimmed = s.trim();
+    trimmed.starts_with('(') && trimmed.ends_with(')')
+}
+
+/// Strips a single layer of parentheses from a SQLite default expression, if present, so
+/// parenthesized and unparenthesized spellings of the same expression compare equal.
+fn strip_sqlite_expression_parens(s: &str) -> &str {
+    let trimmed = s.trim();
+
+    if is_sqlite_expression_default(trimmed) {
+        trimmed[1..trimmed.len() - 1].trim()
+    } else {
+        trimmed
+    }
+}
+
 fn unquote_sqlite_string_default(s: &str) -> Cow<'_, str> {
     static SQLITE_STRING_DEFAULT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?ms)^'(.*)'$|^"(.*)"$"#).unwrap());
     static SQLITE_ESCAPED_CHARACTER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"''"#).unwrap());
@@ -603,6 +715,54 @@ fn unquote_sqlite_string_default(s: &str) -> Cow<'_, str> {
     }
 }
 
+/// Pushes the table's named `CONSTRAINT <name> CHECK (...)` clauses, parsed out of its stored
+/// `CREATE TABLE` text. SQLite doesn't expose check constraints through a system catalog the way
+/// MySQL and Postgres do, so `sqlite_master.sql` is the only source for them. Unnamed
+/// `CHECK (...)` clauses are skipped, since there is no identifier to track them by.
+fn push_check_constraints(table_id: TableId, definition: &str, schema: &mut SqlSchema) {
+    static SQLITE_CHECK_CONSTRAINT_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"(?i)CONSTRAINT\s+["`']?([^\s"`']+)["`']?\s+CHECK\s*\("#).unwrap());
+
+    for capture in SQLITE_CHECK_CONSTRAINT_RE.captures_iter(definition) {
+        let constraint_name = capture[1].to_owned();
+        schema.check_constraints.push((table_id, constraint_name));
+    }
+}
+
+/// Matches the trigger's timing and event out of its stored `CREATE TRIGGER` text. SQLite has no
+/// system catalog columns for these, so `sqlite_master.sql` is the only source for them.
+static SQLITE_TRIGGER_TIMING_EVENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)CREATE\s+TRIGGER\s+\S+\s+(BEFORE|AFTER|INSTEAD\s+OF)\s+(INSERT|UPDATE|DELETE)"#).unwrap()
+});
+
+async fn push_triggers(
+    table_ids: &IndexMap<&str, TableId>,
+    schema: &mut SqlSchema,
+    conn: &(dyn Connection + Send + Sync),
+) -> DescriberResult<()> {
+    let sql = r#"SELECT name, tbl_name, sql FROM sqlite_master WHERE type='trigger' ORDER BY name ASC"#;
+    let result_set = conn.query_raw(sql, &[]).await?;
+
+    for row in result_set.into_iter() {
+        let name = row.get_expect_string("name");
+        let table_name = row.get_expect_string("tbl_name");
+        let definition = row.get_expect_string("sql");
+
+        let Some(table_id) = table_ids.get(table_name.as_str()) else {
+            continue;
+        };
+
+        let (timing, event) = match SQLITE_TRIGGER_TIMING_EVENT_RE.captures(&definition) {
+            Some(captures) => (captures[1].to_uppercase(), captures[2].to_uppercase()),
+            None => continue,
+        };
+
+        schema.push_trigger(*table_id, name, timing, event, definition);
+    }
+
+    Ok(())
+}
+
 /// Returns whether a table is one of the SQLite system tables or a Cloudflare D1 specific table.
 fn is_table_ignored(table_name: &str) -> bool {
     SQLITE_IGNORED_TABLES.iter().any(|table| table_name == *table)
@@ -9,6 +9,7 @@ mod foreign_key;
 mod index;
 mod namespace;
 mod table;
+mod trigger;
 mod user_defined_type;
 mod view;
 
@@ -21,6 +22,7 @@ pub use index::IndexWalker;
 pub use namespace::NamespaceWalker;
 pub use r#enum::{EnumVariantWalker, EnumWalker};
 pub use table::TableWalker;
+pub use trigger::TriggerWalker;
 pub use user_defined_type::UserDefinedTypeWalker;
 pub use view::ViewWalker;
 
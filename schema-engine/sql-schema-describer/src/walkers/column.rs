@@ -79,6 +79,26 @@ impl<'a> ColumnWalker<'a> {
         self.get().auto_increment
     }
 
+    /// If this is a generated column, whether it is `STORED` or `VIRTUAL`.
+    pub fn generated_column_storage(self) -> Option<crate::GeneratedColumnStorage> {
+        self.get().generated_as
+    }
+
+    /// The column's TOAST storage strategy, as set with `ALTER COLUMN ... SET STORAGE`.
+    pub fn toast_storage(self) -> Option<crate::ColumnStorage> {
+        self.get().toast_storage
+    }
+
+    /// The name of this column's `NOT NULL` constraint, if it has one and it was explicitly named.
+    pub fn not_null_constraint_name(self) -> Option<&'a str> {
+        self.get().not_null_constraint_name.as_deref()
+    }
+
+    /// Is this column defined with `ON UPDATE CURRENT_TIMESTAMP`?
+    pub fn is_on_update_current_timestamp(self) -> bool {
+        self.get().on_update_now
+    }
+
     /// the default value for the column.
     pub fn default(self) -> Option<DefaultValueWalker<'a>> {
         match self.id {
@@ -24,6 +24,11 @@ impl<'a> IndexColumnWalker<'a> {
         self.get().sort_order
     }
 
+    /// The collation the column is indexed with, when it overrides the column's own collation.
+    pub fn collation(self) -> Option<&'a str> {
+        self.get().collation.as_deref()
+    }
+
     /// The table where the column is located.
     pub fn table(self) -> TableWalker<'a> {
         self.index().table()
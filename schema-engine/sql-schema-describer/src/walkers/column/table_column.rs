@@ -74,6 +74,26 @@ impl<'a> TableColumnWalker<'a> {
         self.coarsen().is_autoincrement()
     }
 
+    /// If this is a generated column, whether it is `STORED` or `VIRTUAL`.
+    pub fn generated_column_storage(self) -> Option<crate::GeneratedColumnStorage> {
+        self.coarsen().generated_column_storage()
+    }
+
+    /// The column's TOAST storage strategy, as set with `ALTER COLUMN ... SET STORAGE`.
+    pub fn toast_storage(self) -> Option<crate::ColumnStorage> {
+        self.coarsen().toast_storage()
+    }
+
+    /// The name of this column's `NOT NULL` constraint, if it has one and it was explicitly named.
+    pub fn not_null_constraint_name(self) -> Option<&'a str> {
+        self.coarsen().not_null_constraint_name()
+    }
+
+    /// Is this column defined with `ON UPDATE CURRENT_TIMESTAMP`?
+    pub fn is_on_update_current_timestamp(self) -> bool {
+        self.coarsen().is_on_update_current_timestamp()
+    }
+
     /// Returns whether two columns are named the same and belong to the same table.
     pub fn is_same_column(self, other: TableColumnWalker<'_>) -> bool {
         self.name() == other.name()
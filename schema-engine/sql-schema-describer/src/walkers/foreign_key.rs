@@ -1,4 +1,7 @@
-use crate::{ForeignKey, ForeignKeyAction, ForeignKeyColumn, ForeignKeyId, TableColumnWalker, TableWalker, Walker};
+use crate::{
+    ForeignKey, ForeignKeyAction, ForeignKeyColumn, ForeignKeyId, ForeignKeyMatchType, TableColumnWalker, TableWalker,
+    Walker,
+};
 
 /// Traverse a foreign key.
 pub type ForeignKeyWalker<'a> = Walker<'a, ForeignKeyId>;
@@ -19,6 +22,11 @@ impl<'schema> ForeignKeyWalker<'schema> {
         self.foreign_key().constraint_name.as_deref()
     }
 
+    /// The comment on the foreign key constraint, if any.
+    pub fn description(self) -> Option<&'schema str> {
+        self.foreign_key().description.as_deref()
+    }
+
     fn foreign_key(self) -> &'schema ForeignKey {
         &self.schema.foreign_keys[self.id.0 as usize]
     }
@@ -33,6 +41,11 @@ impl<'schema> ForeignKeyWalker<'schema> {
         self.foreign_key().on_update_action
     }
 
+    /// PostgreSQL-only: the foreign key's `MATCH` type.
+    pub fn match_type(self) -> ForeignKeyMatchType {
+        self.foreign_key().match_type
+    }
+
     /// The columns referenced by the foreign key on the referenced table.
     pub fn referenced_columns(self) -> impl ExactSizeIterator<Item = TableColumnWalker<'schema>> {
         self.columns().iter().map(move |col| self.walk(col.referenced_column))
@@ -44,6 +44,22 @@ impl<'a> IndexWalker<'a> {
         &self.get().index_name
     }
 
+    /// The `WHERE` clause of a partial index, if any.
+    pub fn predicate(self) -> Option<&'a str> {
+        self.get().predicate.as_deref()
+    }
+
+    /// The comment on the index, if any.
+    pub fn description(self) -> Option<&'a str> {
+        self.get().description.as_deref()
+    }
+
+    /// PostgreSQL-only: the tablespace the index is stored in. `None` means the index lives in
+    /// the database's default tablespace.
+    pub fn tablespace(self) -> Option<&'a str> {
+        self.get().tablespace.as_deref()
+    }
+
     /// Traverse to the table of the index.
     pub fn table(self) -> TableWalker<'a> {
         self.walk(self.get().table_id)
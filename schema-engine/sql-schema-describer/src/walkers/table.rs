@@ -110,6 +110,17 @@ impl<'a> TableWalker<'a> {
         self.table().properties.contains(TableProperties::HasRowLevelSecurity)
     }
 
+    /// Postgres-only: is the table `UNLOGGED`?
+    pub fn is_unlogged(self) -> bool {
+        self.table().properties.contains(TableProperties::Unlogged)
+    }
+
+    /// MySQL-only: the table's default collation, if introspection found one that differs from
+    /// the database's default.
+    pub fn default_collation(self) -> Option<&'a str> {
+        self.table().default_collation.as_deref()
+    }
+
     /// Does the table have check constraints?
     pub fn has_check_constraints(self) -> bool {
         self.schema
@@ -133,6 +144,12 @@ impl<'a> TableWalker<'a> {
         self.table().description.as_deref()
     }
 
+    /// PostgreSQL-only: the tablespace the table is stored in. `None` means the table lives in
+    /// the database's default tablespace.
+    pub fn tablespace(self) -> Option<&'a str> {
+        self.table().tablespace.as_deref()
+    }
+
     /// Reference to the underlying `Table` struct.
     fn table(self) -> &'a Table {
         &self.schema.tables[self.id.0 as usize]
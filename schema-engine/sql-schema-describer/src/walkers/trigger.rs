@@ -0,0 +1,36 @@
+use crate::{TableWalker, Trigger, TriggerId, Walker};
+
+/// Traverse a trigger.
+pub type TriggerWalker<'a> = Walker<'a, TriggerId>;
+
+impl<'a> TriggerWalker<'a> {
+    /// The name of the trigger.
+    pub fn name(self) -> &'a str {
+        &self.get().name
+    }
+
+    /// When the trigger fires relative to the event, e.g. `BEFORE`, `AFTER`, `INSTEAD OF`.
+    pub fn timing(self) -> &'a str {
+        &self.get().timing
+    }
+
+    /// The event that fires the trigger, e.g. `INSERT`, `UPDATE`, `DELETE`.
+    pub fn event(self) -> &'a str {
+        &self.get().event
+    }
+
+    /// The trigger's defining SQL. See [`Trigger::definition`] for how its shape differs by
+    /// connector.
+    pub fn definition(self) -> &'a str {
+        &self.get().definition
+    }
+
+    /// The table the trigger is defined on.
+    pub fn table(self) -> TableWalker<'a> {
+        self.walk(self.get().table_id)
+    }
+
+    fn get(self) -> &'a Trigger {
+        &self.schema.triggers[self.id.0 as usize]
+    }
+}
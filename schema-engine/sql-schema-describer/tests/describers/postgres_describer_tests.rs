@@ -1635,6 +1635,32 @@ fn index_sort_order_composite_type_asc_desc_is_handled(api: TestApi) {
     assert_eq!(Some(SQLSortOrder::Desc), columns[1].sort_order());
 }
 
+#[test_connector(tags(Postgres))]
+fn index_collation_is_handled(api: TestApi) {
+    let sql = indoc! {r#"
+        CREATE TABLE A (
+            id INT PRIMARY KEY,
+            a  TEXT NOT NULL,
+            b  TEXT NOT NULL
+        );
+
+        CREATE UNIQUE INDEX foo ON A (a COLLATE "C", b);
+    "#};
+
+    api.raw_cmd(sql);
+
+    let schema = api.describe();
+    let table = schema.table_walkers().next().unwrap();
+    let index = table.indexes().nth(1).unwrap();
+    let columns = index.columns().collect::<Vec<_>>();
+
+    assert_eq!(2, columns.len());
+    assert_eq!("a", columns[0].as_column().name());
+    assert_eq!(Some("C"), columns[0].collation());
+    assert_eq!("b", columns[1].as_column().name());
+    assert_eq!(None, columns[1].collation());
+}
+
 #[test_connector(tags(Postgres), exclude(CockroachDb))]
 fn array_column_defaults(api: TestApi) {
     let schema = r#"
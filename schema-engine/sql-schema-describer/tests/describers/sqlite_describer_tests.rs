@@ -29,6 +29,26 @@ fn multi_column_foreign_keys_must_work(api: TestApi) {
     });
 }
 
+#[test_connector(tags(Sqlite))]
+fn named_check_constraints_are_described(api: TestApi) {
+    let sql = r#"
+        CREATE TABLE "Fruit" (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            ripeness INTEGER NOT NULL,
+            CONSTRAINT "name_not_empty" CHECK (name != ''),
+            CHECK (ripeness BETWEEN 0 AND 100)
+        );
+    "#;
+    api.raw_cmd(sql);
+    let schema = api.describe();
+
+    let table = schema.table_walker("Fruit").unwrap();
+    // The unnamed `CHECK` on `ripeness` has no identifier to track it by, so only the named one
+    // is described.
+    assert_eq!(vec!["name_not_empty"], table.check_constraints().collect::<Vec<_>>());
+}
+
 #[test_connector(tags(Sqlite))]
 fn views_can_be_described(api: TestApi) {
     let full_sql = r#"
@@ -745,3 +765,72 @@ fn integer_primary_keys_autoincrement(api: TestApi) {
 
     expected.assert_debug_eq(&found);
 }
+
+#[test_connector(tags(Sqlite))]
+fn parenthesized_expression_defaults_are_not_mistaken_for_string_literals(api: TestApi) {
+    let create_table = r#"
+        CREATE TABLE "expression_defaults_test" (
+            created_at DATETIME NOT NULL DEFAULT (datetime('now')),
+            updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            label VARCHAR NOT NULL DEFAULT 'hello',
+            computed VARCHAR NOT NULL DEFAULT (upper('hello'))
+        );
+    "#;
+
+    api.raw_cmd(create_table);
+
+    let result = api.describe();
+    let table = result.table_walker("expression_defaults_test").unwrap();
+
+    let created_at = table.column("created_at").unwrap().default().unwrap();
+    let updated_at = table.column("updated_at").unwrap().default().unwrap();
+    let label = table.column("label").unwrap().default().unwrap();
+    let computed = table.column("computed").unwrap().default().unwrap();
+
+    // `DEFAULT (datetime('now'))` and `DEFAULT CURRENT_TIMESTAMP` both normalize to `Now`, so they
+    // don't churn against each other on repeated introspection.
+    assert_eq!(created_at.kind(), &DefaultKind::Now);
+    assert_eq!(updated_at.kind(), &DefaultKind::Now);
+
+    // A genuine string literal is still read as a literal value, not an expression.
+    assert_eq!(label.kind(), &DefaultKind::Value(PrismaValue::String("hello".to_owned())));
+
+    // An unquoted, parenthesized expression on a string column must not be treated as a string
+    // literal, even though it contains no keyword SQLite recognizes.
+    assert!(matches!(computed.kind(), DefaultKind::DbGenerated(Some(expr)) if expr.contains("upper")));
+}
+
+#[test_connector(tags(Sqlite))]
+fn boolean_defaults_are_normalized_regardless_of_spelling(api: TestApi) {
+    let create_table = r#"
+        CREATE TABLE "boolean_defaults_test" (
+            as_int_true BOOLEAN NOT NULL DEFAULT 1,
+            as_word_true BOOLEAN NOT NULL DEFAULT true,
+            as_quoted_true BOOLEAN NOT NULL DEFAULT 't',
+            as_int_false BOOLEAN NOT NULL DEFAULT 0,
+            as_word_false BOOLEAN NOT NULL DEFAULT false,
+            as_quoted_false BOOLEAN NOT NULL DEFAULT 'f',
+            as_null BOOLEAN
+        );
+    "#;
+
+    api.raw_cmd(create_table);
+
+    let result = api.describe();
+    let table = result.table_walker("boolean_defaults_test").unwrap();
+
+    let default_of = |column: &str| table.column(column).unwrap().default().map(|d| d.kind().clone());
+
+    // `1`, `true` and `'t'` must all normalize to the same value, so none of them churns against
+    // the others on repeated introspection.
+    assert_eq!(default_of("as_int_true"), Some(DefaultKind::Value(PrismaValue::Boolean(true))));
+    assert_eq!(default_of("as_word_true"), Some(DefaultKind::Value(PrismaValue::Boolean(true))));
+    assert_eq!(default_of("as_quoted_true"), Some(DefaultKind::Value(PrismaValue::Boolean(true))));
+
+    assert_eq!(default_of("as_int_false"), Some(DefaultKind::Value(PrismaValue::Boolean(false))));
+    assert_eq!(default_of("as_word_false"), Some(DefaultKind::Value(PrismaValue::Boolean(false))));
+    assert_eq!(default_of("as_quoted_false"), Some(DefaultKind::Value(PrismaValue::Boolean(false))));
+
+    // A missing default is genuinely different from an explicit `false` default.
+    assert_eq!(default_of("as_null"), None);
+}